@@ -0,0 +1,89 @@
+//! 命令行参数解析
+//!
+//! 支持 `子命令 --key value`、`--key=value` 与 `--flag` 三种形式
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::app::error::types::{AppError, Result};
+
+/// 解析后的命令行参数
+#[derive(Debug, Clone)]
+pub struct CliArgs {
+    command: String,
+    options: HashMap<String, String>,
+}
+
+impl CliArgs {
+    /// 解析命令行参数（不含程序名），无参数时返回 `None`
+    pub fn parse<I>(args: I) -> Result<Option<Self>>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut args = args.into_iter().peekable();
+        let command = match args.next() {
+            Some(command) => command,
+            None => return Ok(None),
+        };
+
+        let mut options = HashMap::new();
+        while let Some(arg) = args.next() {
+            let key =
+                arg.strip_prefix("--").ok_or_else(
+                    || {
+                        AppError::validation(
+                    "Argument",
+                    format!("Unexpected argument: {arg}"),
+                )
+                    },
+                )?;
+
+            if let Some((key, value)) = key.split_once('=')
+            {
+                options.insert(
+                    key.to_string(),
+                    value.to_string(),
+                );
+                continue;
+            }
+
+            // 下一个参数不是选项时作为当前选项的值，否则视为开关
+            let value = match args.peek() {
+                Some(next) if !next.starts_with("--") => {
+                    args.next().unwrap_or_default()
+                }
+                _ => String::new(),
+            };
+            options.insert(key.to_string(), value);
+        }
+
+        Ok(Some(Self { command, options }))
+    }
+
+    /// 子命令名称
+    pub fn command(&self) -> &str {
+        &self.command
+    }
+
+    /// 获取字符串选项
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.options.get(key).map(|value| value.as_str())
+    }
+
+    /// 获取并解析选项，未提供时使用默认值
+    pub fn get_or<T: FromStr>(
+        &self,
+        key: &str,
+        default: T,
+    ) -> Result<T> {
+        match self.get(key) {
+            Some(value) => value.parse().map_err(|_| {
+                AppError::validation(
+                    format!("--{key}"),
+                    format!("Invalid value: {value}"),
+                )
+            }),
+            None => Ok(default),
+        }
+    }
+}
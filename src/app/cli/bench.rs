@@ -0,0 +1,48 @@
+//! `bench` 子命令 - 回环吞吐量基准测试
+
+use super::args::CliArgs;
+use crate::app::error::types::Result;
+use crate::core::network::bench::{
+    run_benchmark, BenchmarkConfig,
+};
+
+/// 执行基准测试并打印结果表格
+pub async fn run(args: &CliArgs) -> Result<()> {
+    let defaults = BenchmarkConfig::default();
+    let config = BenchmarkConfig {
+        packet_size: args
+            .get_or("size", defaults.packet_size)?,
+        packet_count: args
+            .get_or("count", defaults.packet_count)?,
+        batch_size: args
+            .get_or("batch", defaults.batch_size)?,
+    };
+
+    println!(
+        "Loopback benchmark: {} packets of {} bytes",
+        config.packet_count, config.packet_size
+    );
+    println!(
+        "{:>6} {:>12} {:>10} {:>10} {:>8} {:>8}",
+        "batch",
+        "pps",
+        "Mbps",
+        "received",
+        "loss%",
+        "errors"
+    );
+
+    for result in run_benchmark(&config).await? {
+        println!(
+            "{:>6} {:>12.0} {:>10.1} {:>10} {:>8.2} {:>8}",
+            result.batch_size,
+            result.packets_per_second(),
+            result.megabits_per_second(),
+            result.packets_received,
+            result.loss_percent(),
+            result.send_errors
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+//! `compile` 子命令 - 把 CSV 场景离线编译为数据集
+
+use chrono::Duration;
+use std::path::Path;
+
+use super::args::CliArgs;
+use crate::app::error::types::{AppError, Result};
+use crate::core::csv::expr::parse_timestamp;
+use crate::core::csv::CsvParser;
+use crate::core::dataset::compile::{
+    compile_csv, CompileTiming,
+};
+
+/// 编译 CSV 并打印结果
+pub fn run(args: &CliArgs) -> Result<()> {
+    let csv = args.get("csv").ok_or_else(|| {
+        AppError::validation(
+            "--csv",
+            "CSV file is required",
+        )
+    })?;
+    let output = args.get("output").ok_or_else(|| {
+        AppError::validation(
+            "--output",
+            "Output directory is required",
+        )
+    })?;
+    // 数据集名称默认取 CSV 文件名
+    let csv_path = Path::new(csv);
+    let name = match args.get("name") {
+        Some(name) => name,
+        None => csv_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("dataset"),
+    };
+
+    let defaults = CompileTiming::default();
+    let start = match args.get("start") {
+        Some(value) => {
+            parse_timestamp(value).map_err(|e| {
+                AppError::validation("--start", e)
+            })?
+        }
+        None => defaults.start,
+    };
+    let interval_ms: f64 = args.get_or(
+        "interval-ms",
+        defaults.interval.num_milliseconds() as f64,
+    )?;
+    if !interval_ms.is_finite() || interval_ms <= 0.0 {
+        return Err(AppError::validation(
+            "--interval-ms",
+            "Interval must be greater than 0",
+        ));
+    }
+    let timing = CompileTiming {
+        start,
+        interval: Duration::nanoseconds(
+            (interval_ms * 1e6).round() as i64,
+        ),
+    };
+
+    let parser = CsvParser::from_file(csv_path)?;
+    let summary = compile_csv(
+        &parser,
+        Path::new(output),
+        name,
+        &timing,
+    )?;
+    println!(
+        "Compiled {} rows ({} bytes) into {}",
+        summary.packets,
+        summary.bytes,
+        Path::new(output).join(name).display()
+    );
+    if let Some((first, last)) = summary.span {
+        println!(
+            "Timestamps {} to {} ({})",
+            first.format("%Y-%m-%dT%H:%M:%S%.9fZ"),
+            last.format("%Y-%m-%dT%H:%M:%S%.9fZ"),
+            if parser.has_timestamps() {
+                "from timestamp column"
+            } else {
+                "from start and interval"
+            }
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! `decode` 子命令 - 按 CSV 表头把数据集解码为 CSV 或 JSON Lines
+
+use std::path::Path;
+
+use super::args::CliArgs;
+use crate::app::error::types::{AppError, Result};
+use crate::core::csv::decode::{
+    decode_dataset, CsvSchema, DecodeFormat,
+};
+
+/// 解码数据集并打印结果
+pub fn run(args: &CliArgs) -> Result<()> {
+    let required = |key: &str, message: &str| {
+        args.get(key).ok_or_else(|| {
+            AppError::validation(
+                format!("--{key}"),
+                message,
+            )
+        })
+    };
+    let dataset =
+        required("dataset", "Dataset path is required")?;
+    let schema =
+        required("schema", "Schema CSV file is required")?;
+    let output =
+        required("output", "Output file is required")?;
+
+    // 未指定格式时按输出文件扩展名选择
+    let format = match args.get("format") {
+        Some(format) => format.parse()?,
+        None if output.ends_with(".jsonl") => {
+            DecodeFormat::JsonLines
+        }
+        None => DecodeFormat::Csv,
+    };
+
+    let schema = CsvSchema::from_file(Path::new(schema))?;
+    let summary = decode_dataset(
+        Path::new(dataset),
+        &schema,
+        Path::new(output),
+        format,
+    )?;
+    println!(
+        "Decoded {} packets to {output} ({format})",
+        summary.packets
+    );
+    if summary.mismatched > 0 {
+        println!(
+            "{} packets did not match the schema length of {} bytes and were written as raw hex",
+            summary.mismatched,
+            schema.packet_len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+//! `export` 子命令 - 把数据集导出为 Wireshark 可打开的抓包文件
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use super::args::CliArgs;
+use crate::app::error::types::{AppError, Result};
+use crate::core::capture::export::{
+    export_dataset, ExportOptions,
+};
+use crate::core::capture::writer::CaptureFormat;
+
+/// 导出数据集并打印结果
+pub fn run(args: &CliArgs) -> Result<()> {
+    let dataset = args.get("dataset").ok_or_else(|| {
+        AppError::validation(
+            "--dataset",
+            "Dataset path is required",
+        )
+    })?;
+    let output = args.get("output").ok_or_else(|| {
+        AppError::validation(
+            "--output",
+            "Output file is required",
+        )
+    })?;
+
+    // 未指定格式时按输出文件扩展名选择
+    let defaults = ExportOptions::default();
+    let format = match args.get("format") {
+        Some(format) => format.parse()?,
+        None if output.ends_with(".pcapng") => {
+            CaptureFormat::PcapNg
+        }
+        None => CaptureFormat::Pcap,
+    };
+    let address = |key: &str, default: SocketAddr| {
+        args.get(key)
+            .map(|value| {
+                value.parse::<SocketAddr>().map_err(|_| {
+                    AppError::validation(
+                        format!("--{key}"),
+                        format!("Invalid address: {value}"),
+                    )
+                })
+            })
+            .unwrap_or(Ok(default))
+    };
+    let options = ExportOptions {
+        format,
+        source: address("source", defaults.source)?,
+        destination: address(
+            "destination",
+            defaults.destination,
+        )?,
+    };
+
+    let summary = export_dataset(
+        Path::new(dataset),
+        Path::new(output),
+        &options,
+    )?;
+    println!(
+        "Exported {} packets to {output} ({format}), {} with recorded metadata",
+        summary.packets, summary.with_metadata
+    );
+    if summary.with_metadata < summary.packets {
+        println!(
+            "Packets without metadata use source {} and destination {}",
+            options.source, options.destination
+        );
+    }
+    if summary.skipped > 0 {
+        println!(
+            "Skipped {} packets larger than a UDP datagram",
+            summary.skipped
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,118 @@
+//! `inspect` 子命令 - 列出数据集中的数据包及其接收元数据
+
+use std::path::Path;
+
+use super::args::CliArgs;
+use crate::app::error::types::{AppError, Result};
+use crate::core::dataset::sidecar::{
+    SidecarReader, SourceFilter,
+};
+use pcapfile_io::{PcapReader, ReaderConfig};
+
+/// 打印数据集中的数据包列表
+pub fn run(args: &CliArgs) -> Result<()> {
+    let dataset = args.get("dataset").ok_or_else(|| {
+        AppError::validation(
+            "--dataset",
+            "Dataset path is required",
+        )
+    })?;
+    let source_filter: Option<SourceFilter> = args
+        .get("source")
+        .map(|value| value.parse())
+        .transpose()?;
+    let limit = args.get_or("limit", usize::MAX)?;
+
+    let dataset_path = Path::new(dataset);
+    let dataset_name = dataset_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("dataset");
+    let mut reader = PcapReader::new_with_config(
+        dataset_path.parent().unwrap_or(dataset_path),
+        dataset_name,
+        ReaderConfig::default(),
+    )?;
+    let mut sidecar = SidecarReader::open(dataset_path)?;
+    if sidecar.is_none() && source_filter.is_some() {
+        return Err(AppError::validation(
+            "--source",
+            "Dataset has no packet metadata",
+        ));
+    }
+
+    println!(
+        "{:>8} {:<30} {:>6} {:<22} {:<22} interface",
+        "index", "time", "len", "source", "endpoint"
+    );
+
+    let mut index = 0u64;
+    let mut printed = 0;
+    while printed < limit {
+        let Some(packet) = reader.read_packet()? else {
+            break;
+        };
+        let record = match &mut sidecar {
+            Some(sidecar) => sidecar.next_record()?,
+            None => None,
+        };
+
+        if let Some(filter) = &source_filter {
+            let matched = record
+                .as_ref()
+                .is_some_and(|r| filter.matches(&r.meta));
+            if !matched {
+                index += 1;
+                continue;
+            }
+        }
+
+        let (source, endpoint, interface) = match &record {
+            Some(record) => (
+                record
+                    .meta
+                    .source
+                    .map(|source| source.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                // 早期数据集只记录了接收端口
+                record
+                    .meta
+                    .endpoint
+                    .map(|endpoint| endpoint.to_string())
+                    .unwrap_or_else(|| {
+                        format!(
+                            ":{}",
+                            record.meta.local_port
+                        )
+                    }),
+                record
+                    .meta
+                    .interface
+                    .as_deref()
+                    .unwrap_or("-")
+                    .to_string(),
+            ),
+            None => (
+                "-".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ),
+        };
+
+        println!(
+            "{:>8} {:<30} {:>6} {:<22} {:<22} {}",
+            index,
+            packet
+                .capture_time()
+                .format("%Y-%m-%dT%H:%M:%S%.9fZ"),
+            packet.packet_length(),
+            source,
+            endpoint,
+            interface
+        );
+        index += 1;
+        printed += 1;
+    }
+
+    Ok(())
+}
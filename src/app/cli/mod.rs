@@ -0,0 +1,87 @@
+//! 命令行模块
+//!
+//! 不带参数启动时进入图形界面，带子命令时执行对应的命令行工具
+
+pub mod args;
+pub mod bench;
+pub mod compile;
+pub mod decode;
+pub mod export;
+pub mod inspect;
+pub mod receive;
+pub mod send;
+pub mod session;
+pub mod throughput;
+
+use crate::app::error::types::{AppError, Result};
+use args::CliArgs;
+
+/// 命令行帮助信息
+const USAGE: &str = "\
+Usage: pcap-transfer [COMMAND] [OPTIONS]
+
+Without a command the graphical interface is started.
+
+Commands:
+  bench    Compare per-packet and batched UDP throughput on loopback
+           --size <bytes> --count <packets> --batch <size>
+  compile  Compile a CSV scenario into a dataset offline
+           --csv <file> --output <dir> [--name <dataset>]
+           [--start <rfc3339|unix seconds>] [--interval-ms <ms>]
+  decode   Decode a dataset into CSV or JSON Lines using a CSV header
+           (row 1 names, row 2 types)
+           --dataset <path> --schema <file.csv> --output <file>
+           [--format <csv|jsonl>]
+  export   Export a dataset as a pcap or pcapng file with synthesized
+           Ethernet/IP/UDP headers
+           --dataset <path> --output <file> [--format <pcap|pcapng>]
+           [--source <ip:port>] [--destination <ip:port>]
+  inspect  List packets in a dataset with their recorded source metadata
+           --dataset <path> [--source <ip[:port]>] [--limit <n>]
+  receive  Receive with the saved receiver configuration until a stop
+           condition is met or Ctrl+C is pressed
+           [--metrics [<ip:port>]]
+  send     Send with the saved sender configuration until the data is
+           exhausted or Ctrl+C is pressed
+           [--metrics [<ip:port>]]
+  throughput
+           Search the highest rate with loss under a threshold
+           [--reflector <ip:port>] --sizes <bytes,...>
+           --search <step|binary> --min-rate <Mbps> --max-rate <Mbps>
+           --step <Mbps> --resolution <Mbps> --loss <percent>
+           --duration-ms <ms> --settle-ms <ms> --batch <size>
+           [--output <file.csv>]
+  help     Print this message";
+
+/// 解析命令行参数，返回 `Ok(false)` 表示未指定子命令
+pub async fn run<I>(args: I) -> Result<bool>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args = match CliArgs::parse(args)? {
+        Some(args) => args,
+        None => return Ok(false),
+    };
+
+    match args.command() {
+        "bench" => bench::run(&args).await?,
+        "compile" => compile::run(&args)?,
+        "decode" => decode::run(&args)?,
+        "export" => export::run(&args)?,
+        "inspect" => inspect::run(&args)?,
+        "receive" => receive::run(&args).await?,
+        "send" => send::run(&args).await?,
+        "throughput" => throughput::run(&args).await?,
+        "help" | "--help" | "-h" => println!("{USAGE}"),
+        other => {
+            return Err(AppError::validation(
+                "Command",
+                format!(
+                    "Unknown command: {other}\n\n{USAGE}"
+                ),
+            ))
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,54 @@
+//! `receive` 子命令 - 不启动图形界面，按已保存的接收器配置接收
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+use super::args::CliArgs;
+use super::session;
+use crate::app::config::validator::ConfigValidator;
+use crate::app::error::types::Result;
+use crate::core::network::receiver::run_receiver_with_gui_stats;
+use crate::core::network::sender::TransferState;
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::metrics::Direction;
+
+/// 执行一次接收，直到满足停止条件或收到 Ctrl+C
+pub async fn run(args: &CliArgs) -> Result<()> {
+    let config_manager = session::load_config()?;
+    let config = config_manager.get_receiver_config();
+    ConfigValidator::validate_receiver_config(&config)?;
+    let metrics =
+        session::start_metrics(args, &config_manager)?;
+
+    let stats =
+        Arc::new(Mutex::new(TransferStats::default()));
+    let state =
+        Arc::new(Mutex::new(TransferState::Running));
+    metrics.register(
+        config.dataset_name.clone(),
+        Direction::Receive,
+        Arc::clone(&stats),
+        Arc::clone(&state),
+    );
+
+    println!(
+        "Receiving on {}:{} into {}, press Ctrl+C to stop",
+        config.address, config.port, config.output_path
+    );
+    session::run(
+        run_receiver_with_gui_stats(
+            PathBuf::from(config.output_path),
+            config.dataset_name,
+            config.address,
+            config.port,
+            config.network_type,
+            config.interface,
+            config.options,
+            Arc::clone(&stats),
+            Arc::clone(&state),
+        ),
+        state,
+        stats,
+    )
+    .await
+}
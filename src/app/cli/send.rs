@@ -0,0 +1,59 @@
+//! `send` 子命令 - 不启动图形界面，按已保存的发送器配置发送
+
+use std::sync::{Arc, Mutex};
+
+use super::args::CliArgs;
+use super::session;
+use crate::app::config::validator::ConfigValidator;
+use crate::app::error::types::Result;
+use crate::core::network::sender::{
+    run_sender_with_gui_stats, TransferState,
+};
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::metrics::Direction;
+use crate::core::stats::report::dataset_stem;
+
+/// 执行一次发送，直到数据发送完毕或收到 Ctrl+C
+pub async fn run(args: &CliArgs) -> Result<()> {
+    let config_manager = session::load_config()?;
+    let config = config_manager.get_sender_config();
+    ConfigValidator::validate_sender_config(&config)?;
+    let metrics =
+        session::start_metrics(args, &config_manager)?;
+
+    let dataset_path = config.source_path();
+    let stats =
+        Arc::new(Mutex::new(TransferStats::default()));
+    let state =
+        Arc::new(Mutex::new(TransferState::Running));
+    metrics.register(
+        dataset_stem(&dataset_path),
+        Direction::Send,
+        Arc::clone(&stats),
+        Arc::clone(&state),
+    );
+
+    println!(
+        "Sending {} to {}:{}",
+        dataset_path.display(),
+        config.address,
+        config.port
+    );
+    session::run(
+        run_sender_with_gui_stats(
+            dataset_path,
+            config.address,
+            config.port,
+            config.network_type,
+            config.interface,
+            config.data_format,
+            config.csv_packet_interval,
+            config.options,
+            Arc::clone(&stats),
+            Arc::clone(&state),
+        ),
+        state,
+        stats,
+    )
+    .await
+}
@@ -0,0 +1,81 @@
+//! `send` 与 `receive` 子命令共用的无界面会话逻辑
+//!
+//! 使用图形界面保存的配置运行一次传输，按需提供 `/metrics`，
+//! 收到 Ctrl+C 时像界面上的停止按钮一样结束传输。
+
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use super::args::CliArgs;
+use crate::app::config::manager::ConfigManager;
+use crate::app::config::validator::ConfigValidator;
+use crate::app::error::types::Result;
+use crate::core::network::sender::TransferState;
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::metrics::{
+    start_metrics_endpoint, MetricsRegistry,
+};
+
+/// 加载已保存的配置
+pub fn load_config() -> Result<ConfigManager> {
+    let mut config_manager =
+        ConfigManager::new("pcap-transfer")?;
+    config_manager.load()?;
+    Ok(config_manager)
+}
+
+/// 启动指标导出（如已启用）并返回指标登记表
+///
+/// `--metrics [<ip:port>]` 启用指标导出，并可覆盖配置中的监听地址。
+pub fn start_metrics(
+    args: &CliArgs,
+    config_manager: &ConfigManager,
+) -> Result<Arc<MetricsRegistry>> {
+    let mut options =
+        config_manager.config().metrics.clone();
+    if let Some(bind) = args.get("metrics") {
+        options.enabled = true;
+        if !bind.is_empty() {
+            options.bind = bind.to_string();
+        }
+    }
+    ConfigValidator::validate_metrics(&options)?;
+
+    let registry = Arc::new(MetricsRegistry::default());
+    if options.enabled {
+        let addr = start_metrics_endpoint(
+            &options.bind,
+            Arc::clone(&registry),
+        )?;
+        println!("Metrics: http://{addr}/metrics");
+    }
+    Ok(registry)
+}
+
+/// 运行传输任务，直到其结束或收到 Ctrl+C
+pub async fn run<F>(
+    task: F,
+    state: Arc<Mutex<TransferState>>,
+    stats: Arc<Mutex<TransferStats>>,
+) -> Result<()>
+where
+    F: Future<Output = Result<()>>,
+{
+    tokio::pin!(task);
+    let result = tokio::select! {
+        result = &mut task => result,
+        _ = tokio::signal::ctrl_c() => {
+            println!("Stopping...");
+            if let Ok(mut state) = state.lock() {
+                *state = TransferState::Idle;
+            }
+            task.await
+        }
+    };
+    result?;
+
+    if let Ok(stats) = stats.lock() {
+        println!("{}", stats.summary());
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! `throughput` 子命令 - 最高无损速率查找（RFC 2544 风格）
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use super::args::CliArgs;
+use crate::app::error::types::{AppError, Result};
+use crate::core::network::throughput::{
+    run_throughput_test, SizeResult, ThroughputConfig,
+};
+
+/// 扫频表 CSV 的表头
+const CSV_HEADER: &str = "packet_size,max_rate_mbps,max_pps,trials,loss_percent_at_max";
+
+/// 执行吞吐量测试并打印每次试验与扫频表
+pub async fn run(args: &CliArgs) -> Result<()> {
+    let defaults = ThroughputConfig::default();
+    let reflector = args
+        .get("reflector")
+        .map(|value| {
+            value.parse::<SocketAddr>().map_err(|_| {
+                AppError::validation(
+                    "--reflector",
+                    format!("Invalid address: {value}"),
+                )
+            })
+        })
+        .transpose()?;
+    let packet_sizes = match args.get("sizes") {
+        Some(value) => value
+            .split(',')
+            .map(|size| {
+                size.trim().parse().map_err(|_| {
+                    AppError::validation(
+                        "--sizes",
+                        format!("Invalid size: {size}"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<usize>>>()?,
+        None => defaults.packet_sizes.clone(),
+    };
+    let config = ThroughputConfig {
+        reflector,
+        packet_sizes,
+        search: args.get_or("search", defaults.search)?,
+        min_rate_mbps: args
+            .get_or("min-rate", defaults.min_rate_mbps)?,
+        max_rate_mbps: args
+            .get_or("max-rate", defaults.max_rate_mbps)?,
+        step_mbps: args
+            .get_or("step", defaults.step_mbps)?,
+        resolution_mbps: args.get_or(
+            "resolution",
+            defaults.resolution_mbps,
+        )?,
+        trial_duration: Duration::from_millis(
+            args.get_or(
+                "duration-ms",
+                defaults.trial_duration.as_millis() as u64,
+            )?,
+        ),
+        settle_time: Duration::from_millis(args.get_or(
+            "settle-ms",
+            defaults.settle_time.as_millis() as u64,
+        )?),
+        loss_threshold_percent: args.get_or(
+            "loss",
+            defaults.loss_threshold_percent,
+        )?,
+        batch_size: args
+            .get_or("batch", defaults.batch_size)?,
+    };
+
+    match config.reflector {
+        Some(reflector) => println!(
+            "Throughput test against reflector {reflector}, loss threshold {}%",
+            config.loss_threshold_percent
+        ),
+        None => println!(
+            "Throughput test on loopback, loss threshold {}%",
+            config.loss_threshold_percent
+        ),
+    }
+    println!(
+        "{:>6} {:>10} {:>10} {:>10} {:>10} {:>8} {:>8} {:>6}",
+        "size",
+        "Mbps",
+        "offered",
+        "sent",
+        "received",
+        "loss%",
+        "errors",
+        "result"
+    );
+
+    let results =
+        run_throughput_test(&config, |size, trial| {
+            println!(
+                "{:>6} {:>10.3} {:>10.3} {:>10} {:>10} {:>8.3} {:>8} {:>6}",
+                size,
+                trial.rate_mbps,
+                trial.offered_mbps,
+                trial.sent,
+                trial.received,
+                trial.loss_percent(),
+                trial.errors,
+                if trial.passed { "pass" } else { "fail" }
+            );
+        })
+        .await?;
+
+    println!();
+    println!(
+        "{:>6} {:>12} {:>12} {:>7}",
+        "size", "max Mbps", "max pps", "trials"
+    );
+    for result in &results {
+        match (
+            result.max_rate_mbps,
+            result.max_packets_per_second(),
+        ) {
+            (Some(rate), Some(pps)) => println!(
+                "{:>6} {:>12.3} {:>12.0} {:>7}",
+                result.packet_size,
+                rate,
+                pps,
+                result.trials.len()
+            ),
+            _ => println!(
+                "{:>6} {:>12} {:>12} {:>7}",
+                result.packet_size,
+                "-",
+                "-",
+                result.trials.len()
+            ),
+        }
+    }
+
+    if let Some(path) = args.get("output") {
+        std::fs::write(path, sweep_csv(&results))?;
+        println!("Sweep table written to {path}");
+    }
+
+    Ok(())
+}
+
+/// 生成扫频表 CSV，最低速率也未通过的包长留空
+fn sweep_csv(results: &[SizeResult]) -> String {
+    let mut csv = format!("{CSV_HEADER}\n");
+    for result in results {
+        let loss = result.max_rate_mbps.and_then(|rate| {
+            result
+                .trials
+                .iter()
+                .find(|trial| trial.rate_mbps == rate)
+                .map(|trial| trial.loss_percent())
+        });
+        let _ = writeln!(
+            csv,
+            "{},{},{},{},{}",
+            result.packet_size,
+            result
+                .max_rate_mbps
+                .map_or(String::new(), |rate| format!(
+                    "{rate:.3}"
+                )),
+            result
+                .max_packets_per_second()
+                .map_or(String::new(), |pps| format!(
+                    "{pps:.0}"
+                )),
+            result.trials.len(),
+            loss.map_or(String::new(), |loss| format!(
+                "{loss:.6}"
+            )),
+        );
+    }
+    csv
+}
@@ -5,6 +5,9 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use super::options::{
+    MetricsOptions, ReceiverOptions, SenderOptions,
+};
 use super::paths::ConfigPaths;
 use super::types::{DataFormat, NetworkType};
 
@@ -13,6 +16,8 @@ use super::types::{DataFormat, NetworkType};
 pub struct AppConfig {
     pub sender: SenderConfig,
     pub receiver: ReceiverConfig,
+    #[serde(default)]
+    pub metrics: MetricsOptions,
 }
 
 /// 网络配置
@@ -27,11 +32,15 @@ pub struct NetworkConfig {
 /// 发送器配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SenderConfig {
-    pub data_format: String, // 数据格式：pcap 或 csv
+    pub data_format: String, // 数据格式：pcap、csv、generator 或 capture
     pub dataset_path: String, // PCAP数据集路径（文件夹）
     pub csv_file: String,    // CSV文件路径（文件）
     pub csv_packet_interval: u64, // CSV发送周期（毫秒）
+    #[serde(default)]
+    pub capture_file: String, // 抓包文件路径（pcap / pcapng）
     pub network: NetworkConfig,
+    #[serde(default)]
+    pub options: SenderOptions,
 }
 
 /// 接收器配置
@@ -41,6 +50,8 @@ pub struct ReceiverConfig {
     pub dataset_name: String,
     pub buffer_size: usize,
     pub network: NetworkConfig,
+    #[serde(default)]
+    pub options: ReceiverOptions,
 }
 
 impl Default for NetworkConfig {
@@ -61,7 +72,9 @@ impl Default for SenderConfig {
             dataset_path: "./dataset".to_string(),
             csv_file: String::new(), // CSV文件路径默认为空
             csv_packet_interval: 1000, // 默认1秒发送周期
+            capture_file: String::new(),
             network: NetworkConfig::default(),
+            options: SenderOptions::default(),
         }
     }
 }
@@ -73,6 +86,7 @@ impl Default for ReceiverConfig {
             dataset_name: "received_data".to_string(),
             buffer_size: 1048576,
             network: NetworkConfig::default(),
+            options: ReceiverOptions::default(),
         }
     }
 }
@@ -179,6 +193,8 @@ impl ConfigManager {
     pub fn get_sender_data_format(&self) -> DataFormat {
         match self.config.sender.data_format.as_str() {
             "csv" => DataFormat::Csv,
+            "generator" => DataFormat::Generator,
+            "capture" => DataFormat::Capture,
             _ => DataFormat::Pcap, // 默认为PCAP
         }
     }
@@ -213,6 +229,43 @@ impl ConfigManager {
         }
     }
 
+    /// 获取发送器配置（统一接口）
+    pub fn get_sender_config(
+        &self,
+    ) -> crate::ui::config::SenderConfig {
+        let sender = &self.config.sender;
+        crate::ui::config::SenderConfig {
+            data_format: self.get_sender_data_format(),
+            pcap_path: sender.dataset_path.clone(),
+            csv_file: sender.csv_file.clone(),
+            csv_packet_interval: sender.csv_packet_interval,
+            capture_file: sender.capture_file.clone(),
+            address: sender.network.address.clone(),
+            port: sender.network.port,
+            network_type: self.get_sender_network_type(),
+            interface: non_empty(&sender.network.interface),
+            options: sender.options.clone(),
+        }
+    }
+
+    /// 获取接收器配置（统一接口）
+    pub fn get_receiver_config(
+        &self,
+    ) -> crate::ui::config::ReceiverConfig {
+        let receiver = &self.config.receiver;
+        crate::ui::config::ReceiverConfig {
+            output_path: receiver.output_path.clone(),
+            dataset_name: receiver.dataset_name.clone(),
+            address: receiver.network.address.clone(),
+            port: receiver.network.port,
+            network_type: self.get_receiver_network_type(),
+            interface: non_empty(
+                &receiver.network.interface,
+            ),
+            options: receiver.options.clone(),
+        }
+    }
+
     /// 更新发送器配置（统一接口）
     pub fn update_sender_config(
         &mut self,
@@ -228,6 +281,9 @@ impl ConfigManager {
             config.csv_file.clone();
         self.config.sender.csv_packet_interval =
             config.csv_packet_interval;
+        self.config.sender.capture_file =
+            config.capture_file.clone();
+        self.config.sender.options = config.options.clone();
 
         // 更新网络配置
         self.update_sender_network_config(
@@ -248,6 +304,8 @@ impl ConfigManager {
             config.output_path.clone();
         self.config.receiver.dataset_name =
             config.dataset_name.clone();
+        self.config.receiver.options =
+            config.options.clone();
 
         // 更新网络配置
         self.update_receiver_network_config(
@@ -266,6 +324,10 @@ impl ConfigManager {
         self.config.sender.data_format = match data_format {
             DataFormat::Pcap => "pcap".to_string(),
             DataFormat::Csv => "csv".to_string(),
+            DataFormat::Generator => {
+                "generator".to_string()
+            }
+            DataFormat::Capture => "capture".to_string(),
         };
     }
 
@@ -321,3 +383,8 @@ impl ConfigManager {
             interface.unwrap_or_default();
     }
 }
+
+/// 空字符串表示未指定
+fn non_empty(value: &str) -> Option<String> {
+    (!value.is_empty()).then(|| value.to_string())
+}
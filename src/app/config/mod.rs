@@ -1,6 +1,7 @@
 //! 配置管理模块
 
 pub mod manager;
+pub mod options;
 pub mod paths;
 pub mod types;
 pub mod validator;
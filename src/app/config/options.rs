@@ -0,0 +1,807 @@
+//! 高级选项模块
+//!
+//! 定义发送器和接收器的可选功能配置，直接序列化到配置文件中
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 接收队列溢出策略
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    /// 队列满时暂停接收，等待写入线程腾出空间
+    #[default]
+    Block,
+    /// 队列满时丢弃新到达的数据包
+    DropNewest,
+    /// 队列满时丢弃队列中最旧的数据包
+    DropOldest,
+}
+
+impl fmt::Display for OverflowPolicy {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            OverflowPolicy::Block => write!(f, "Block"),
+            OverflowPolicy::DropNewest => {
+                write!(f, "Drop Newest")
+            }
+            OverflowPolicy::DropOldest => {
+                write!(f, "Drop Oldest")
+            }
+        }
+    }
+}
+
+/// 接收时间戳来源（按精度从低到高排列）
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampSource {
+    /// recv 返回后在用户态读取系统时间
+    User,
+    /// 内核软件时间戳（SO_TIMESTAMPNS）
+    #[default]
+    Kernel,
+    /// 网卡硬件时间戳（SO_TIMESTAMPING，需指定接收接口），
+    /// 网卡无法配置时退回内核软件时间戳
+    Hardware,
+}
+
+impl fmt::Display for TimestampSource {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            TimestampSource::User => {
+                write!(f, "User Space")
+            }
+            TimestampSource::Kernel => write!(f, "Kernel"),
+            TimestampSource::Hardware => {
+                write!(f, "Hardware")
+            }
+        }
+    }
+}
+
+/// 数据集轮转方式
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum RotationMode {
+    /// 不轮转，全部写入同一个数据集
+    #[default]
+    None,
+    /// 每隔 N 分钟轮转
+    Minutes,
+    /// 每 N 个数据包轮转
+    Packets,
+    /// 每 N 字节轮转
+    Bytes,
+    /// 每个整点轮转
+    Hourly,
+}
+
+impl fmt::Display for RotationMode {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            RotationMode::None => write!(f, "None"),
+            RotationMode::Minutes => {
+                write!(f, "Every N Minutes")
+            }
+            RotationMode::Packets => {
+                write!(f, "Every N Packets")
+            }
+            RotationMode::Bytes => {
+                write!(f, "Every N Bytes")
+            }
+            RotationMode::Hourly => {
+                write!(f, "Top of Hour")
+            }
+        }
+    }
+}
+
+/// 数据集轮转选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct RotationOptions {
+    /// 轮转方式
+    pub mode: RotationMode,
+    /// 轮转间隔（分钟数、包数或字节数，取决于轮转方式）
+    pub interval: u64,
+    /// 轮转后的数据集名称模板
+    pub name_template: String,
+}
+
+impl Default for RotationOptions {
+    fn default() -> Self {
+        Self {
+            mode: RotationMode::None,
+            interval: 0,
+            name_template: "{name}_{yyyyMMdd_HHmmss}_{seq}"
+                .to_string(),
+        }
+    }
+}
+
+/// 接收过滤规则组合方式
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterMode {
+    /// 所有规则都满足时记录（AND）
+    #[default]
+    All,
+    /// 任一规则满足时记录（OR）
+    Any,
+}
+
+impl fmt::Display for FilterMode {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            FilterMode::All => write!(f, "Match All (AND)"),
+            FilterMode::Any => write!(f, "Match Any (OR)"),
+        }
+    }
+}
+
+/// 接收过滤选项
+#[derive(
+    Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct FilterOptions {
+    /// 规则组合方式
+    pub mode: FilterMode,
+    /// 过滤规则，每条一个表达式（为空表示不过滤）
+    pub rules: Vec<String>,
+}
+
+/// 数据集写入器参数
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct WriterOptions {
+    /// 写入缓冲区大小（字节）
+    pub buffer_size: usize,
+    /// 索引缓存大小（条目数）
+    pub index_cache_size: usize,
+    /// 每个 pcap 文件的最大数据包数量
+    pub max_packets_per_file: usize,
+    /// 是否每次写入后自动刷新
+    pub auto_flush: bool,
+}
+
+impl Default for WriterOptions {
+    fn default() -> Self {
+        Self {
+            buffer_size: 8192,
+            index_cache_size: 1000,
+            max_packets_per_file: 1000,
+            auto_flush: true,
+        }
+    }
+}
+
+/// 接收端序号来源
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SequenceMode {
+    /// 不检测序号
+    #[default]
+    Off,
+    /// 从发送器插入的测试头中读取
+    Header,
+    /// 从负载中指定偏移的字段读取
+    Field,
+}
+
+impl fmt::Display for SequenceMode {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            SequenceMode::Off => write!(f, "Off"),
+            SequenceMode::Header => {
+                write!(f, "Test Header")
+            }
+            SequenceMode::Field => {
+                write!(f, "Payload Field")
+            }
+        }
+    }
+}
+
+/// 接收端序号检测选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct SequenceOptions {
+    /// 序号来源
+    pub mode: SequenceMode,
+    /// 序号字段在负载中的偏移（字节，仅 `field` 模式）
+    pub offset: usize,
+    /// 序号字段宽度（1、2、4 或 8 字节，仅 `field` 模式）
+    pub width: usize,
+    /// 序号字段是否为小端字节序（仅 `field` 模式）
+    pub little_endian: bool,
+    /// 乱序判定窗口（包数），早于窗口到达的包记为迟到
+    pub window: u64,
+}
+
+impl Default for SequenceOptions {
+    fn default() -> Self {
+        Self {
+            mode: SequenceMode::Off,
+            offset: 0,
+            width: 4,
+            little_endian: false,
+            window: 1024,
+        }
+    }
+}
+
+/// 发送端测试头选项
+#[derive(
+    Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct TestHeaderOptions {
+    /// 是否在每个数据包前插入测试头
+    pub enabled: bool,
+    /// 写入测试头的数据流 ID
+    pub stream_id: u32,
+    /// 是否在测试头中写入发送时间（用于时延测量）
+    pub timestamp: bool,
+}
+
+/// 接收端时延测量选项
+#[derive(
+    Debug, Clone, Default, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct LatencyOptions {
+    /// 是否根据测试头中的发送时间测量时延
+    pub enabled: bool,
+    /// 时钟偏差（微秒，接收端时钟减去发送端时钟）
+    pub clock_offset_us: i64,
+}
+
+/// 反射模式
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ReflectMode {
+    /// 不回送
+    #[default]
+    Off,
+    /// 回送完整数据包
+    Full,
+    /// 只回送数据包开头的若干字节
+    Prefix,
+}
+
+impl fmt::Display for ReflectMode {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            ReflectMode::Off => write!(f, "Off"),
+            ReflectMode::Full => write!(f, "Full Packet"),
+            ReflectMode::Prefix => write!(f, "Prefix"),
+        }
+    }
+}
+
+/// 接收端反射器选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ReflectorOptions {
+    /// 反射模式
+    pub mode: ReflectMode,
+    /// 回送的字节数（仅 `prefix` 模式，不小于测试头长度）
+    pub prefix_bytes: usize,
+}
+
+impl Default for ReflectorOptions {
+    fn default() -> Self {
+        Self {
+            mode: ReflectMode::Off,
+            prefix_bytes: 64,
+        }
+    }
+}
+
+/// 发送端往返时延测量选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct RttOptions {
+    /// 是否接收反射器回送的数据包并测量往返时延
+    pub enabled: bool,
+    /// 发送结束后等待回送数据包的时长（毫秒）
+    pub timeout_ms: u64,
+}
+
+impl Default for RttOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout_ms: 1000,
+        }
+    }
+}
+
+/// Gilbert–Elliott 突发丢包选项（概率均为百分比）
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct BurstLossOptions {
+    /// 是否启用突发丢包
+    pub enabled: bool,
+    /// 每个数据包由好状态转入坏状态的概率
+    pub enter_bad_percent: f64,
+    /// 每个数据包由坏状态回到好状态的概率
+    pub exit_bad_percent: f64,
+    /// 好状态下的丢包概率
+    pub good_loss_percent: f64,
+    /// 坏状态下的丢包概率
+    pub bad_loss_percent: f64,
+}
+
+impl Default for BurstLossOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            enter_bad_percent: 1.0,
+            exit_bad_percent: 25.0,
+            good_loss_percent: 0.0,
+            bad_loss_percent: 100.0,
+        }
+    }
+}
+
+/// 发送端网络损伤模拟选项（概率均为百分比）
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ImpairmentOptions {
+    /// 是否启用损伤模拟
+    pub enabled: bool,
+    /// 随机数种子，相同种子与配置产生相同的损伤序列
+    pub seed: u64,
+    /// 随机丢包概率
+    pub drop_percent: f64,
+    /// 突发丢包选项
+    pub burst_loss: BurstLossOptions,
+    /// 重复发送概率
+    pub duplicate_percent: f64,
+    /// 乱序概率
+    pub reorder_percent: f64,
+    /// 乱序的数据包推迟到其后第几个数据包之后发送
+    pub reorder_window: usize,
+    /// 附加延迟（毫秒）
+    pub delay_ms: u64,
+    /// 延迟抖动（毫秒，在附加延迟上下均匀分布）
+    pub jitter_ms: u64,
+    /// 翻转一个随机比特的概率
+    pub corrupt_percent: f64,
+}
+
+impl Default for ImpairmentOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seed: 1,
+            drop_percent: 0.0,
+            burst_loss: BurstLossOptions::default(),
+            duplicate_percent: 0.0,
+            reorder_percent: 0.0,
+            reorder_window: 3,
+            delay_ms: 0,
+            jitter_ms: 0,
+            corrupt_percent: 0.0,
+        }
+    }
+}
+
+/// 发送端模糊测试选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct FuzzOptions {
+    /// 是否对每个数据包施加随机变异
+    pub enabled: bool,
+    /// 随机数种子，第 N 个数据包的变异只由种子与 N 决定
+    pub seed: u64,
+    /// 每个数据包最多施加的变异次数
+    pub max_mutations: usize,
+    /// 随机翻转比特
+    pub bit_flip: bool,
+    /// 写入边界值（CSV 数据源按字段类型选择）
+    pub boundary: bool,
+    /// 截断数据包
+    pub truncate: bool,
+    /// 在数据包末尾追加随机字节
+    pub extend: bool,
+    /// 与上一个数据包拼接
+    pub splice: bool,
+    /// 追加的最大字节数
+    pub max_extend: usize,
+    /// 变异日志目录（空表示写在数据集旁边）
+    pub log_directory: String,
+}
+
+impl Default for FuzzOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            seed: 1,
+            max_mutations: 1,
+            bit_flip: true,
+            boundary: true,
+            truncate: true,
+            extend: true,
+            splice: true,
+            max_extend: 64,
+            log_directory: String::new(),
+        }
+    }
+}
+
+/// 合成流量的包长分布
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeDistribution {
+    /// 固定包长
+    #[default]
+    Fixed,
+    /// 在最小与最大包长之间均匀分布
+    Uniform,
+    /// 简单 IMIX：以 7:4:1 的比例混合小、中、大包
+    Imix,
+}
+
+impl fmt::Display for SizeDistribution {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            SizeDistribution::Fixed => write!(f, "Fixed"),
+            SizeDistribution::Uniform => {
+                write!(f, "Uniform")
+            }
+            SizeDistribution::Imix => write!(f, "IMIX"),
+        }
+    }
+}
+
+/// 合成流量的负载内容
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadPattern {
+    /// 全零
+    #[default]
+    Zeros,
+    /// 逐字节递增（0x00–0xFF 循环）
+    Incrementing,
+    /// 随机字节
+    Random,
+    /// 开头为测试头（序号与计划发送时间），其余为零
+    Sequence,
+}
+
+impl fmt::Display for PayloadPattern {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            PayloadPattern::Zeros => write!(f, "Zeros"),
+            PayloadPattern::Incrementing => {
+                write!(f, "Incrementing")
+            }
+            PayloadPattern::Random => write!(f, "Random"),
+            PayloadPattern::Sequence => {
+                write!(f, "Sequence Header")
+            }
+        }
+    }
+}
+
+/// 合成流量发生器选项（数据格式为 Generator 时使用）
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct GeneratorOptions {
+    /// 包长分布
+    pub size: SizeDistribution,
+    /// 固定包长（字节）
+    pub packet_size: usize,
+    /// 均匀分布的最小包长（字节）
+    pub min_size: usize,
+    /// 均匀分布的最大包长（字节）
+    pub max_size: usize,
+    /// 负载内容
+    pub pattern: PayloadPattern,
+    /// 目标速率（Mbps，按 UDP 负载计算，0 表示不限速）
+    pub rate_mbps: f64,
+    /// 发送时长（秒，0 表示直到手动停止）
+    pub duration_secs: u64,
+    /// 随机包长与随机负载的种子
+    pub seed: u64,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            size: SizeDistribution::Fixed,
+            packet_size: 1024,
+            min_size: 64,
+            max_size: 1472,
+            pattern: PayloadPattern::Zeros,
+            rate_mbps: 100.0,
+            duration_secs: 10,
+            seed: 1,
+        }
+    }
+}
+
+/// 指标导出选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct MetricsOptions {
+    /// 是否提供 Prometheus `/metrics` 端点
+    pub enabled: bool,
+    /// HTTP 监听地址
+    pub bind: String,
+}
+
+impl Default for MetricsOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1:9464".to_string(),
+        }
+    }
+}
+
+/// 统计报告选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ReportOptions {
+    /// 是否在传输结束时写入统计报告
+    pub enabled: bool,
+    /// 时间序列的采样间隔（毫秒）
+    pub interval_ms: u64,
+    /// 报告目录（空表示写在数据集旁边）
+    pub directory: String,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_ms: 1000,
+            directory: String::new(),
+        }
+    }
+}
+
+/// 发送器高级选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct SenderOptions {
+    /// 单次系统调用批量发送的最大包数（1 表示逐包发送）
+    pub batch_size: usize,
+    /// 预读缓冲区容量（包数，0 表示不启用预读）
+    pub prefetch_packets: usize,
+    /// 预读缓冲区容量（字节数，0 表示不限制）
+    pub prefetch_bytes: usize,
+    /// 只重放来自该地址（`IP` 或 `IP:端口`）的数据包，空表示全部
+    pub source_filter: String,
+    /// 抓包文件过滤表达式（类似 BPF，如 `udp and dst port 9000`），空表示全部
+    pub capture_filter: String,
+    /// 合成流量发生器选项
+    pub generator: GeneratorOptions,
+    /// 测试头（序号）选项
+    pub test_header: TestHeaderOptions,
+    /// 往返时延测量选项（启用时自动插入带发送时间的测试头）
+    pub rtt: RttOptions,
+    /// 网络损伤模拟选项
+    pub impairment: ImpairmentOptions,
+    /// 模糊测试选项
+    pub fuzz: FuzzOptions,
+    /// 统计报告选项
+    pub report: ReportOptions,
+}
+
+impl Default for SenderOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 1,
+            prefetch_packets: 4096,
+            prefetch_bytes: 0,
+            source_filter: String::new(),
+            capture_filter: String::new(),
+            generator: GeneratorOptions::default(),
+            test_header: TestHeaderOptions::default(),
+            rtt: RttOptions::default(),
+            impairment: ImpairmentOptions::default(),
+            fuzz: FuzzOptions::default(),
+            report: ReportOptions::default(),
+        }
+    }
+}
+
+/// 接收器高级选项
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize,
+)]
+#[serde(default)]
+pub struct ReceiverOptions {
+    /// 单次系统调用批量接收的最大包数（1 表示逐包接收）
+    pub batch_size: usize,
+    /// 接收队列容量（包数）
+    pub queue_capacity: usize,
+    /// 接收队列溢出策略
+    pub overflow_policy: OverflowPolicy,
+    /// 通过 SO_REUSEPORT 在同一端口上打开的接收套接字数量
+    pub socket_count: usize,
+    /// 多套接字接收时按捕获时间重排序的等待窗口（毫秒）
+    pub reorder_window_ms: u64,
+    /// 接收时间戳来源
+    pub timestamp_source: TimestampSource,
+    /// 最大接收包数（0 表示不限制）
+    pub max_packets: u64,
+    /// 最大接收字节数（0 表示不限制）
+    pub max_bytes: u64,
+    /// 最大接收时长（秒，0 表示不限制）
+    pub max_duration_secs: u64,
+    /// 收到首包后无数据超过该时长即停止（秒，0 表示不限制）
+    pub idle_timeout_secs: u64,
+    /// 数据集轮转选项
+    pub rotation: RotationOptions,
+    /// 数据集写入器参数
+    pub writer: WriterOptions,
+    /// 接收过滤规则
+    pub filter: FilterOptions,
+    /// 附加监听端点（端口、端口范围或 `地址:端口`）
+    pub endpoints: Vec<String>,
+    /// 每个端点写入独立的数据集
+    pub dataset_per_endpoint: bool,
+    /// 序号检测选项
+    pub sequence: SequenceOptions,
+    /// 时延测量选项
+    pub latency: LatencyOptions,
+    /// 反射器选项
+    pub reflector: ReflectorOptions,
+    /// 统计报告选项
+    pub report: ReportOptions,
+}
+
+impl Default for ReceiverOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 1,
+            queue_capacity: 8192,
+            overflow_policy: OverflowPolicy::Block,
+            socket_count: 1,
+            reorder_window_ms: 20,
+            timestamp_source: TimestampSource::Kernel,
+            max_packets: 0,
+            max_bytes: 0,
+            max_duration_secs: 0,
+            idle_timeout_secs: 0,
+            rotation: RotationOptions::default(),
+            writer: WriterOptions::default(),
+            filter: FilterOptions::default(),
+            endpoints: Vec::new(),
+            dataset_per_endpoint: false,
+            sequence: SequenceOptions::default(),
+            latency: LatencyOptions::default(),
+            reflector: ReflectorOptions::default(),
+            report: ReportOptions::default(),
+        }
+    }
+}
@@ -16,6 +16,10 @@ pub enum DataFormat {
     Pcap,
     /// CSV数据格式
     Csv,
+    /// 合成流量（不需要数据集）
+    Generator,
+    /// libpcap / pcapng 抓包文件
+    Capture,
 }
 
 impl fmt::Display for DataFormat {
@@ -26,6 +30,8 @@ impl fmt::Display for DataFormat {
         match self {
             DataFormat::Pcap => write!(f, "PCAP"),
             DataFormat::Csv => write!(f, "CSV"),
+            DataFormat::Generator => write!(f, "Generator"),
+            DataFormat::Capture => write!(f, "PCAP File"),
         }
     }
 }
@@ -159,6 +165,10 @@ impl SenderAppConfig {
                     ));
                 }
             }
+            DataFormat::Generator => {}
+            DataFormat::Capture => {
+                validate_capture_file(&dataset_path)?;
+            }
         }
 
         Ok(Self {
@@ -191,12 +201,29 @@ impl SenderAppConfig {
                     ));
                 }
             }
+            DataFormat::Generator => {}
+            DataFormat::Capture => {
+                validate_capture_file(&self.dataset_path)?;
+            }
         }
 
         Ok(())
     }
 }
 
+/// 验证抓包文件路径（必须是已存在的文件）
+fn validate_capture_file(
+    path: &std::path::Path,
+) -> Result<()> {
+    if !path.is_file() {
+        return Err(crate::app::error::types::DataTransferError::validation(
+            "Capture File",
+            format!("Capture file does not exist: {}", path.display()),
+        ));
+    }
+    Ok(())
+}
+
 impl ReceiverAppConfig {
     /// 创建接收器配置
     pub fn new(
@@ -2,8 +2,39 @@
 //!
 //! 负责验证发送器和接收器的配置参数
 
-use crate::app::config::types::DataFormat;
+use std::net::SocketAddr;
+
+use crate::app::config::options::{
+    FuzzOptions, GeneratorOptions, ImpairmentOptions,
+    MetricsOptions, PayloadPattern, ReflectMode,
+    ReportOptions, RotationMode, RotationOptions,
+    SequenceMode, SequenceOptions, SizeDistribution,
+};
+use crate::app::config::types::{
+    DataFormat, NetworkConfig,
+};
 use crate::app::error::types::{AppError, Result};
+use crate::core::capture::filter::CaptureFilter;
+use crate::core::csv::parser::MAX_CSV_PACKET_INTERVAL_MS;
+use crate::core::dataset::rotation::validate_name_template;
+use crate::core::network::endpoint::resolve_endpoints;
+use crate::core::network::filter::PacketFilter;
+use crate::core::network::generator::MAX_GENERATOR_PACKET_SIZE;
+use crate::core::network::sys::MAX_BATCH_SIZE;
+use crate::core::network::types::MAX_SOCKET_COUNT;
+use crate::core::pipeline::fuzz::{
+    MAX_FUZZ_EXTEND, MAX_FUZZ_MUTATIONS,
+};
+use crate::core::pipeline::impairment::{
+    MAX_IMPAIRMENT_DELAY_MS, MAX_REORDER_WINDOW,
+};
+use crate::core::probe::header::HEADER_LEN;
+use crate::core::probe::reflector::MAX_PREFIX_BYTES;
+use crate::core::probe::rtt::MAX_ECHO_TIMEOUT_MS;
+use crate::core::probe::sequence::MAX_SEQUENCE_WINDOW;
+use crate::core::stats::report::{
+    MAX_REPORT_INTERVAL_MS, MIN_REPORT_INTERVAL_MS,
+};
 use crate::ui::config::{ReceiverConfig, SenderConfig};
 
 /// 配置验证器
@@ -82,6 +113,47 @@ impl ConfigValidator {
                         "File must have .csv extension",
                     ));
                 }
+
+                if config.csv_packet_interval
+                    > MAX_CSV_PACKET_INTERVAL_MS
+                {
+                    return Err(AppError::validation(
+                        "Packet Interval",
+                        format!(
+                            "Packet interval must not exceed {} ms",
+                            MAX_CSV_PACKET_INTERVAL_MS
+                        ),
+                    ));
+                }
+            }
+            DataFormat::Generator => {
+                Self::validate_generator(
+                    &config.options.generator,
+                )?;
+            }
+            DataFormat::Capture => {
+                if config.capture_file.is_empty() {
+                    return Err(AppError::validation(
+                        "Capture File",
+                        "Capture file path cannot be empty",
+                    ));
+                }
+
+                let capture_path = std::path::PathBuf::from(
+                    &config.capture_file,
+                );
+                if !capture_path.is_file() {
+                    return Err(AppError::validation(
+                        "Capture File",
+                        "Capture file does not exist",
+                    ));
+                }
+
+                let filter =
+                    config.options.capture_filter.trim();
+                if !filter.is_empty() {
+                    filter.parse::<CaptureFilter>()?;
+                }
             }
         }
 
@@ -92,6 +164,29 @@ impl ConfigValidator {
             ));
         }
 
+        Self::validate_batch_size(
+            config.options.batch_size,
+        )?;
+
+        if config.options.rtt.enabled
+            && config.options.rtt.timeout_ms
+                > MAX_ECHO_TIMEOUT_MS
+        {
+            return Err(AppError::validation(
+                "Echo Timeout",
+                format!(
+                    "Echo timeout must not exceed {} ms",
+                    MAX_ECHO_TIMEOUT_MS
+                ),
+            ));
+        }
+
+        Self::validate_impairment(
+            &config.options.impairment,
+        )?;
+        Self::validate_fuzz(&config.options.fuzz)?;
+        Self::validate_report(&config.options.report)?;
+
         Ok(())
     }
 
@@ -120,6 +215,368 @@ impl ConfigValidator {
             ));
         }
 
+        Self::validate_batch_size(
+            config.options.batch_size,
+        )?;
+
+        if config.options.queue_capacity == 0 {
+            return Err(AppError::validation(
+                "Queue Capacity",
+                "Queue capacity must be greater than 0",
+            ));
+        }
+
+        if config.options.socket_count == 0
+            || config.options.socket_count
+                > MAX_SOCKET_COUNT
+        {
+            return Err(AppError::validation(
+                "Socket Count",
+                format!(
+                    "Socket count must be between 1 and {}",
+                    MAX_SOCKET_COUNT
+                ),
+            ));
+        }
+
+        Self::validate_rotation(&config.options.rotation)?;
+        Self::validate_sequence(&config.options.sequence)?;
+        Self::validate_report(&config.options.report)?;
+        PacketFilter::from_options(&config.options.filter)?;
+
+        let reflector = &config.options.reflector;
+        if reflector.mode == ReflectMode::Prefix
+            && (reflector.prefix_bytes == 0
+                || reflector.prefix_bytes
+                    > MAX_PREFIX_BYTES)
+        {
+            return Err(AppError::validation(
+                "Prefix Bytes",
+                format!(
+                    "Prefix length must be between 1 and {}",
+                    MAX_PREFIX_BYTES
+                ),
+            ));
+        }
+
+        // 附加端点继承主端点的网络类型与接口
+        let primary = NetworkConfig::for_receiver(
+            config.address.clone(),
+            config.port,
+            config.network_type,
+            config.interface.clone(),
+        )?;
+        resolve_endpoints(
+            &primary,
+            &config.options.endpoints,
+        )?;
+
+        Ok(())
+    }
+
+    /// 验证数据集轮转选项
+    fn validate_rotation(
+        rotation: &RotationOptions,
+    ) -> Result<()> {
+        if rotation.mode == RotationMode::None {
+            return Ok(());
+        }
+
+        let needs_interval = matches!(
+            rotation.mode,
+            RotationMode::Minutes
+                | RotationMode::Packets
+                | RotationMode::Bytes
+        );
+        if needs_interval && rotation.interval == 0 {
+            return Err(AppError::validation(
+                "Rotation Interval",
+                "Rotation interval must be greater than 0",
+            ));
+        }
+
+        // 同一秒内可能发生多次轮转，序号保证名称唯一
+        if !rotation.name_template.contains("{seq}") {
+            return Err(AppError::validation(
+                "Name Template",
+                "Name template must contain {seq}",
+            ));
+        }
+        validate_name_template(&rotation.name_template)
+            .map_err(|e| {
+                AppError::validation("Name Template", e)
+            })?;
+
+        Ok(())
+    }
+
+    /// 验证序号检测选项
+    fn validate_sequence(
+        sequence: &SequenceOptions,
+    ) -> Result<()> {
+        if sequence.mode == SequenceMode::Off {
+            return Ok(());
+        }
+
+        if sequence.mode == SequenceMode::Field
+            && !matches!(sequence.width, 1 | 2 | 4 | 8)
+        {
+            return Err(AppError::validation(
+                "Sequence Width",
+                "Sequence width must be 1, 2, 4 or 8 bytes",
+            ));
+        }
+
+        if sequence.window == 0
+            || sequence.window > MAX_SEQUENCE_WINDOW
+        {
+            return Err(AppError::validation(
+                "Sequence Window",
+                format!(
+                    "Sequence window must be between 1 and {}",
+                    MAX_SEQUENCE_WINDOW
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 验证合成流量发生器选项
+    fn validate_generator(
+        generator: &GeneratorOptions,
+    ) -> Result<()> {
+        let sizes: &[(&str, usize)] = match generator.size {
+            SizeDistribution::Fixed => {
+                &[("Packet Size", generator.packet_size)]
+            }
+            SizeDistribution::Uniform => &[
+                ("Min Size", generator.min_size),
+                ("Max Size", generator.max_size),
+            ],
+            SizeDistribution::Imix => &[],
+        };
+        for (field, size) in sizes {
+            if !(1..=MAX_GENERATOR_PACKET_SIZE)
+                .contains(size)
+            {
+                return Err(AppError::validation(
+                    *field,
+                    format!(
+                        "Packet size must be between 1 and {} bytes",
+                        MAX_GENERATOR_PACKET_SIZE
+                    ),
+                ));
+            }
+        }
+
+        // 序号负载需容纳完整的测试头
+        if generator.pattern == PayloadPattern::Sequence {
+            if let Some((field, _)) = sizes
+                .iter()
+                .find(|(_, size)| *size < HEADER_LEN)
+            {
+                return Err(AppError::validation(
+                    *field,
+                    format!(
+                        "Sequence payloads need at least {} bytes",
+                        HEADER_LEN
+                    ),
+                ));
+            }
+        }
+
+        if generator.size == SizeDistribution::Uniform
+            && generator.min_size > generator.max_size
+        {
+            return Err(AppError::validation(
+                "Min Size",
+                "Minimum size must not exceed maximum size",
+            ));
+        }
+
+        if !generator.rate_mbps.is_finite()
+            || generator.rate_mbps < 0.0
+        {
+            return Err(AppError::validation(
+                "Rate",
+                "Rate must be a non-negative number",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 验证网络损伤模拟选项
+    fn validate_impairment(
+        impairment: &ImpairmentOptions,
+    ) -> Result<()> {
+        if !impairment.enabled {
+            return Ok(());
+        }
+
+        let burst = &impairment.burst_loss;
+        for (field, percent) in [
+            ("Drop", impairment.drop_percent),
+            ("Duplicate", impairment.duplicate_percent),
+            ("Reorder", impairment.reorder_percent),
+            ("Corrupt", impairment.corrupt_percent),
+            ("Enter Burst", burst.enter_bad_percent),
+            ("Exit Burst", burst.exit_bad_percent),
+            ("Good Loss", burst.good_loss_percent),
+            ("Burst Loss", burst.bad_loss_percent),
+        ] {
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(AppError::validation(
+                    field,
+                    "Probability must be between 0 and 100",
+                ));
+            }
+        }
+
+        if impairment.reorder_window == 0
+            || impairment.reorder_window
+                > MAX_REORDER_WINDOW
+        {
+            return Err(AppError::validation(
+                "Reorder Window",
+                format!(
+                    "Reorder window must be between 1 and {}",
+                    MAX_REORDER_WINDOW
+                ),
+            ));
+        }
+
+        for (field, value) in [
+            ("Delay", impairment.delay_ms),
+            ("Jitter", impairment.jitter_ms),
+        ] {
+            if value > MAX_IMPAIRMENT_DELAY_MS {
+                return Err(AppError::validation(
+                    field,
+                    format!(
+                        "{} must not exceed {} ms",
+                        field, MAX_IMPAIRMENT_DELAY_MS
+                    ),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 验证模糊测试选项
+    fn validate_fuzz(fuzz: &FuzzOptions) -> Result<()> {
+        if !fuzz.enabled {
+            return Ok(());
+        }
+
+        if !(fuzz.bit_flip
+            || fuzz.boundary
+            || fuzz.truncate
+            || fuzz.extend
+            || fuzz.splice)
+        {
+            return Err(AppError::validation(
+                "Mutators",
+                "At least one mutator must be enabled",
+            ));
+        }
+
+        if !(1..=MAX_FUZZ_MUTATIONS)
+            .contains(&fuzz.max_mutations)
+        {
+            return Err(AppError::validation(
+                "Max Mutations",
+                format!(
+                    "Mutations per packet must be between 1 and {}",
+                    MAX_FUZZ_MUTATIONS
+                ),
+            ));
+        }
+
+        if fuzz.max_extend > MAX_FUZZ_EXTEND {
+            return Err(AppError::validation(
+                "Max Extend",
+                format!(
+                    "Extension must not exceed {} bytes",
+                    MAX_FUZZ_EXTEND
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 验证指标导出选项
+    ///
+    /// 监听地址须为 `主机:端口` 形式，未启用时不检查。
+    pub fn validate_metrics(
+        metrics: &MetricsOptions,
+    ) -> Result<()> {
+        if !metrics.enabled
+            || metrics.bind.parse::<SocketAddr>().is_ok()
+        {
+            return Ok(());
+        }
+
+        let valid = metrics
+            .bind
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| {
+                !host.is_empty()
+                    && !host.contains(':')
+                    && port.parse::<u16>().is_ok()
+            });
+        if !valid {
+            return Err(AppError::validation(
+                "Metrics Bind",
+                format!(
+                    "Invalid metrics address: {} (expected host:port)",
+                    metrics.bind
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 验证统计报告选项
+    fn validate_report(
+        report: &ReportOptions,
+    ) -> Result<()> {
+        if report.enabled
+            && !(MIN_REPORT_INTERVAL_MS
+                ..=MAX_REPORT_INTERVAL_MS)
+                .contains(&report.interval_ms)
+        {
+            return Err(AppError::validation(
+                "Report Interval",
+                format!(
+                    "Report interval must be between {} and {} ms",
+                    MIN_REPORT_INTERVAL_MS,
+                    MAX_REPORT_INTERVAL_MS
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// 验证批量收发大小
+    fn validate_batch_size(
+        batch_size: usize,
+    ) -> Result<()> {
+        if batch_size == 0 || batch_size > MAX_BATCH_SIZE {
+            return Err(AppError::validation(
+                "Batch Size",
+                format!(
+                    "Batch size must be between 1 and {}",
+                    MAX_BATCH_SIZE
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -1,5 +1,6 @@
 //! 应用程序核心模块
 
+pub mod cli;
 pub mod config;
 pub mod error;
 pub mod logging;
@@ -0,0 +1,149 @@
+//! 数据集导出模块
+//!
+//! 把接收器写入的 pcapfile-io 数据集导出为 Wireshark 可直接打开的
+//! pcap / pcapng 文件。数据集只保存 UDP 负载，导出时按元数据文件中
+//! 记录的发送方地址与接收端点合成以太网、IP 与 UDP 头；没有元数据或
+//! 接收端点为通配地址时使用配置的默认地址。捕获时间保持不变。
+
+use std::net::SocketAddr;
+use std::path::Path;
+
+use pcapfile_io::{PcapReader, ReaderConfig};
+
+use crate::app::error::types::Result;
+use crate::core::capture::packet::{
+    udp_frame, LINKTYPE_ETHERNET,
+};
+use crate::core::capture::writer::{
+    CaptureFormat, CaptureWriter,
+};
+use crate::core::dataset::sidecar::{
+    PacketMeta, SidecarReader,
+};
+
+/// 导出选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions {
+    /// 输出文件格式
+    pub format: CaptureFormat,
+    /// 缺少元数据时使用的来源地址
+    pub source: SocketAddr,
+    /// 缺少元数据时使用的目的地址（接收端点为通配地址时只取其 IP）
+    pub destination: SocketAddr,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: CaptureFormat::Pcap,
+            source: SocketAddr::from((
+                [192, 0, 2, 1],
+                5000,
+            )),
+            destination: SocketAddr::from((
+                [192, 0, 2, 2],
+                8080,
+            )),
+        }
+    }
+}
+
+/// 导出结果
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ExportSummary {
+    /// 写出的数据包数
+    pub packets: u64,
+    /// 使用了元数据的数据包数
+    pub with_metadata: u64,
+    /// 负载超出 UDP 长度上限而跳过的数据包数
+    pub skipped: u64,
+}
+
+/// 导出数据集目录 `dataset_path` 到抓包文件 `output`
+pub fn export_dataset(
+    dataset_path: &Path,
+    output: &Path,
+    options: &ExportOptions,
+) -> Result<ExportSummary> {
+    let dataset_name = dataset_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("dataset");
+    let mut reader = PcapReader::new_with_config(
+        dataset_path.parent().unwrap_or(dataset_path),
+        dataset_name,
+        ReaderConfig::default(),
+    )?;
+    let mut sidecar = SidecarReader::open(dataset_path)?;
+    let mut writer = CaptureWriter::create(
+        output,
+        options.format,
+        LINKTYPE_ETHERNET,
+    )?;
+
+    let mut summary = ExportSummary::default();
+    while let Some(packet) = reader.read_packet()? {
+        // 元数据与数据包按相同顺序逐条对应
+        let meta = match &mut sidecar {
+            Some(sidecar) => sidecar
+                .next_record()?
+                .map(|record| record.meta),
+            None => None,
+        };
+        let (source, destination) =
+            addresses(meta.as_ref(), options);
+        let interface = meta
+            .as_ref()
+            .and_then(|meta| meta.interface.as_deref())
+            .unwrap_or("");
+
+        let Some(frame) = udp_frame(
+            source,
+            destination,
+            &packet.packet.data,
+        ) else {
+            summary.skipped += 1;
+            continue;
+        };
+        writer.write_frame(
+            interface,
+            packet.capture_time(),
+            &frame,
+        )?;
+        summary.packets += 1;
+        if meta.is_some() {
+            summary.with_metadata += 1;
+        }
+    }
+    writer.finish()?;
+
+    Ok(summary)
+}
+
+/// 根据元数据与默认值确定来源与目的地址
+fn addresses(
+    meta: Option<&PacketMeta>,
+    options: &ExportOptions,
+) -> (SocketAddr, SocketAddr) {
+    let Some(meta) = meta else {
+        return (options.source, options.destination);
+    };
+    let source = meta.source.unwrap_or(options.source);
+    // 早期数据集只记录了接收端口；监听通配地址时取默认目的 IP
+    let destination = match meta.endpoint {
+        Some(endpoint)
+            if !endpoint.ip().is_unspecified() =>
+        {
+            endpoint
+        }
+        Some(endpoint) => SocketAddr::new(
+            options.destination.ip(),
+            endpoint.port(),
+        ),
+        None => SocketAddr::new(
+            options.destination.ip(),
+            meta.local_port,
+        ),
+    };
+    (source, destination)
+}
@@ -0,0 +1,336 @@
+//! 抓包过滤模块
+//!
+//! 读取抓包文件时按类似 BPF 的表达式筛选 UDP 数据报：
+//!
+//! - `host 10.0.0.1`、`src host ...`、`dst host ...`：地址匹配；
+//! - `net 10.0.0.0/8`（也可加 `src`/`dst`）：地址属于网段；
+//! - `port 53`、`src port ...`、`dst port ...`：端口匹配；
+//! - `portrange 5000-5010`（也可加 `src`/`dst`）：端口在范围内；
+//! - `iface 1`：pcapng 接口序号；
+//! - `udp`：总为真，便于直接使用 tcpdump 的表达式。
+//!
+//! 条件之间用 `and`（`&&`）、`or`（`||`）、`not`（`!`）与括号组合，
+//! `not` 优先级最高，`and` 高于 `or`。
+
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::capture::packet::UdpDatagram;
+use crate::core::network::filter::Cidr;
+
+/// 地址方向
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Direction {
+    Source,
+    Destination,
+    Either,
+}
+
+/// 过滤表达式
+#[derive(Debug, Clone, PartialEq)]
+enum Expr {
+    Any,
+    Net(Direction, Cidr),
+    Port(Direction, RangeInclusive<u16>),
+    Interface(u32),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+/// 抓包过滤器
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureFilter {
+    expr: Expr,
+}
+
+impl CaptureFilter {
+    /// 判断接口 `interface` 上的数据报是否满足表达式
+    pub fn matches(
+        &self,
+        interface: u32,
+        datagram: &UdpDatagram<'_>,
+    ) -> bool {
+        self.expr.matches(interface, datagram)
+    }
+}
+
+impl FromStr for CaptureFilter {
+    type Err = DataTransferError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let tokens = tokenize(text);
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+        };
+        let expr = parser
+            .or()
+            .and_then(|expr| match parser.peek() {
+                None => Ok(expr),
+                Some(token) => Err(format!(
+                    "unexpected token: {token}"
+                )),
+            })
+            .map_err(|message| {
+                DataTransferError::validation(
+                    "Capture Filter",
+                    format!("{text}: {message}"),
+                )
+            })?;
+        Ok(Self { expr })
+    }
+}
+
+impl Expr {
+    fn matches(
+        &self,
+        interface: u32,
+        datagram: &UdpDatagram<'_>,
+    ) -> bool {
+        let either = |direction: Direction,
+                      check: &dyn Fn(
+            SocketAddr,
+        ) -> bool| {
+            match direction {
+                Direction::Source => check(datagram.source),
+                Direction::Destination => {
+                    check(datagram.destination)
+                }
+                Direction::Either => {
+                    check(datagram.source)
+                        || check(datagram.destination)
+                }
+            }
+        };
+        match self {
+            Expr::Any => true,
+            Expr::Net(direction, cidr) => {
+                either(*direction, &|addr| {
+                    cidr.contains(addr.ip())
+                })
+            }
+            Expr::Port(direction, range) => {
+                either(*direction, &|addr| {
+                    range.contains(&addr.port())
+                })
+            }
+            Expr::Interface(index) => interface == *index,
+            Expr::Not(expr) => {
+                !expr.matches(interface, datagram)
+            }
+            Expr::And(left, right) => {
+                left.matches(interface, datagram)
+                    && right.matches(interface, datagram)
+            }
+            Expr::Or(left, right) => {
+                left.matches(interface, datagram)
+                    || right.matches(interface, datagram)
+            }
+        }
+    }
+}
+
+/// 拆分记号，括号与 `!` 单独成为记号
+fn tokenize(text: &str) -> Vec<String> {
+    text.replace('(', " ( ")
+        .replace(')', " ) ")
+        .replace("&&", " and ")
+        .replace("||", " or ")
+        .replace('!', " not ")
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// 递归下降解析器
+struct Parser<'a> {
+    tokens: &'a [String],
+    position: usize,
+}
+
+type ParseResult<T> = std::result::Result<T, String>;
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.position).map(String::as_str)
+    }
+
+    fn next(&mut self) -> ParseResult<&str> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .ok_or_else(|| {
+                "unexpected end of expression".to_string()
+            })?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn or(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.and()?;
+        while self.peek() == Some("or") {
+            self.position += 1;
+            expr = Expr::Or(
+                Box::new(expr),
+                Box::new(self.and()?),
+            );
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> ParseResult<Expr> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some("and") {
+            self.position += 1;
+            expr = Expr::And(
+                Box::new(expr),
+                Box::new(self.unary()?),
+            );
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> ParseResult<Expr> {
+        match self.next()? {
+            "not" => Ok(Expr::Not(Box::new(self.unary()?))),
+            "(" => {
+                let expr = self.or()?;
+                match self.next()? {
+                    ")" => Ok(expr),
+                    token => Err(format!(
+                        "expected ')', found {token}"
+                    )),
+                }
+            }
+            "udp" | "ip" | "ip6" => Ok(Expr::Any),
+            "iface" => {
+                let index = self.next()?;
+                index.parse().map(Expr::Interface).map_err(
+                    |_| {
+                        format!(
+                            "invalid interface: {index}"
+                        )
+                    },
+                )
+            }
+            "src" => self.primitive(Direction::Source),
+            "dst" => self.primitive(Direction::Destination),
+            _ => {
+                self.position -= 1;
+                self.primitive(Direction::Either)
+            }
+        }
+    }
+
+    fn primitive(
+        &mut self,
+        direction: Direction,
+    ) -> ParseResult<Expr> {
+        let keyword = self.next()?.to_string();
+        let value = self.next()?;
+        match keyword.as_str() {
+            "host" => {
+                let addr: IpAddr =
+                    value.parse().map_err(|_| {
+                        format!("invalid host: {value}")
+                    })?;
+                Ok(Expr::Net(
+                    direction,
+                    addr.to_string().parse()?,
+                ))
+            }
+            "net" => {
+                Ok(Expr::Net(direction, value.parse()?))
+            }
+            "port" => {
+                let port = value.parse().map_err(|_| {
+                    format!("invalid port: {value}")
+                })?;
+                Ok(Expr::Port(direction, port..=port))
+            }
+            "portrange" => {
+                let range = value
+                    .split_once('-')
+                    .and_then(|(start, end)| {
+                        Some(
+                            start.parse().ok()?
+                                ..=end.parse().ok()?,
+                        )
+                    })
+                    .filter(|range| !range.is_empty())
+                    .ok_or_else(|| {
+                        format!(
+                            "invalid port range: {value}"
+                        )
+                    })?;
+                Ok(Expr::Port(direction, range))
+            }
+            other => {
+                Err(format!("unknown primitive: {other}"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn datagram(
+        source: &str,
+        destination: &str,
+    ) -> UdpDatagram<'static> {
+        UdpDatagram {
+            source: source.parse().unwrap(),
+            destination: destination.parse().unwrap(),
+            payload: &[],
+        }
+    }
+
+    #[test]
+    fn test_expressions() {
+        let packet =
+            datagram("10.0.0.1:5000", "239.1.1.1:9000");
+        let matches = |text: &str| {
+            text.parse::<CaptureFilter>()
+                .unwrap()
+                .matches(1, &packet)
+        };
+        assert!(matches("udp and dst port 9000"));
+        assert!(matches("host 10.0.0.1"));
+        assert!(!matches("dst host 10.0.0.1"));
+        assert!(matches(
+            "src net 10.0.0.0/8 && portrange 8000-9000"
+        ));
+        assert!(matches("not port 53"));
+        assert!(matches(
+            "port 53 or (iface 1 and !src port 1)"
+        ));
+        assert!(!matches("iface 0"));
+        assert!(!matches(
+            "port 5000 and not host 10.0.0.1"
+        ));
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for text in [
+            "",
+            "port",
+            "port 70000",
+            "host example",
+            "portrange 9-1",
+            "(port 1",
+            "port 1 port 2",
+            "foo 1",
+        ] {
+            assert!(
+                text.parse::<CaptureFilter>().is_err(),
+                "{text}"
+            );
+        }
+    }
+}
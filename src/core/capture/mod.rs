@@ -0,0 +1,11 @@
+//! 抓包文件模块
+//!
+//! 读取 tcpdump / Wireshark 生成的 libpcap 与 pcapng 文件，
+//! 从链路层帧中取出 UDP 负载，并按类似 BPF 的表达式过滤；
+//! 也可以把数据集导出为抓包文件。
+
+pub mod export;
+pub mod filter;
+pub mod packet;
+pub mod reader;
+pub mod writer;
@@ -0,0 +1,564 @@
+//! 链路层解析模块
+//!
+//! 从抓包帧中取出 UDP 负载。支持以太网（含 802.1Q/802.1ad VLAN 标签）、
+//! BSD 回环、Linux cooked（SLL 与 SLL2）以及裸 IPv4/IPv6 链路类型；
+//! IPv6 会跳过常见的扩展头。分片的数据报无法还原完整负载，直接跳过。
+//!
+//! 导出数据集时反向为 UDP 负载合成以太网、IP 与 UDP 头。
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// LINKTYPE_NULL（BSD 回环，主机字节序的协议族）
+pub const LINKTYPE_NULL: u16 = 0;
+
+/// LINKTYPE_ETHERNET
+pub const LINKTYPE_ETHERNET: u16 = 1;
+
+/// LINKTYPE_RAW（裸 IP）
+pub const LINKTYPE_RAW: u16 = 101;
+
+/// LINKTYPE_LOOP（OpenBSD 回环，网络字节序的协议族）
+const LINKTYPE_LOOP: u16 = 108;
+
+/// LINKTYPE_LINUX_SLL
+const LINKTYPE_LINUX_SLL: u16 = 113;
+
+/// LINKTYPE_IPV4
+const LINKTYPE_IPV4: u16 = 228;
+
+/// LINKTYPE_IPV6
+const LINKTYPE_IPV6: u16 = 229;
+
+/// LINKTYPE_LINUX_SLL2
+const LINKTYPE_LINUX_SLL2: u16 = 276;
+
+/// 以太网类型：IPv4
+pub const ETHERTYPE_IPV4: u16 = 0x0800;
+
+/// 以太网类型：IPv6
+pub const ETHERTYPE_IPV6: u16 = 0x86DD;
+
+/// IP 协议号：UDP
+pub const IPPROTO_UDP: u8 = 17;
+
+/// 合成帧的默认 TTL / 跳数限制
+const DEFAULT_TTL: u8 = 64;
+
+/// 合成帧的源 MAC 地址（本地管理地址）
+const SOURCE_MAC: [u8; 6] = [0x02, 0, 0, 0, 0, 0x01];
+
+/// 合成帧的单播目的 MAC 地址（本地管理地址）
+const DESTINATION_MAC: [u8; 6] = [0x02, 0, 0, 0, 0, 0x02];
+
+/// 从抓包帧中解析出的 UDP 数据报
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UdpDatagram<'a> {
+    /// 来源地址与端口
+    pub source: SocketAddr,
+    /// 目的地址与端口
+    pub destination: SocketAddr,
+    /// UDP 负载
+    pub payload: &'a [u8],
+}
+
+/// 解析帧，返回其中的 UDP 数据报（非 UDP、分片或不支持的链路类型返回 `None`）
+pub fn udp_datagram(
+    link_type: u16,
+    frame: &[u8],
+) -> Option<UdpDatagram<'_>> {
+    let (ethertype, packet) = match link_type {
+        LINKTYPE_ETHERNET => ethernet(frame)?,
+        LINKTYPE_NULL | LINKTYPE_LOOP => {
+            let family: [u8; 4] =
+                frame.get(..4)?.try_into().ok()?;
+            let family = if link_type == LINKTYPE_LOOP {
+                u32::from_be_bytes(family)
+            } else if family[0] != 0 {
+                u32::from_le_bytes(family)
+            } else {
+                u32::from_be_bytes(family)
+            };
+            // AF_INET 为 2；AF_INET6 因系统而异（10、24、28、30）
+            let ethertype = match family {
+                2 => ETHERTYPE_IPV4,
+                10 | 24 | 28 | 30 => ETHERTYPE_IPV6,
+                _ => return None,
+            };
+            (ethertype, &frame[4..])
+        }
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => {
+            let ethertype = match frame.first()? >> 4 {
+                4 => ETHERTYPE_IPV4,
+                6 => ETHERTYPE_IPV6,
+                _ => return None,
+            };
+            (ethertype, frame)
+        }
+        LINKTYPE_LINUX_SLL => {
+            (be16(frame, 14)?, frame.get(16..)?)
+        }
+        LINKTYPE_LINUX_SLL2 => {
+            (be16(frame, 0)?, frame.get(20..)?)
+        }
+        _ => return None,
+    };
+
+    match ethertype {
+        ETHERTYPE_IPV4 => ipv4(packet),
+        ETHERTYPE_IPV6 => ipv6(packet),
+        _ => None,
+    }
+}
+
+/// 为 UDP 负载合成以太网帧（LINKTYPE_ETHERNET）
+///
+/// 来源与目的地址族不同时，IPv4 地址转换为 IPv4 映射的 IPv6 地址。
+/// 组播与广播目的地址使用对应的目的 MAC 地址；负载超出 UDP
+/// 长度上限时返回 `None`。
+pub fn udp_frame(
+    source: SocketAddr,
+    destination: SocketAddr,
+    payload: &[u8],
+) -> Option<Vec<u8>> {
+    let (source_ip, destination_ip) =
+        match (source.ip(), destination.ip()) {
+            (
+                IpAddr::V6(source),
+                IpAddr::V4(destination),
+            ) => (
+                IpAddr::V6(source),
+                IpAddr::V6(destination.to_ipv6_mapped()),
+            ),
+            (
+                IpAddr::V4(source),
+                IpAddr::V6(destination),
+            ) => (
+                IpAddr::V6(source.to_ipv6_mapped()),
+                IpAddr::V6(destination),
+            ),
+            addresses => addresses,
+        };
+
+    let udp_len = u16::try_from(8 + payload.len()).ok()?;
+    let mut segment = Vec::with_capacity(udp_len as usize);
+    segment.extend_from_slice(&source.port().to_be_bytes());
+    segment.extend_from_slice(
+        &destination.port().to_be_bytes(),
+    );
+    segment.extend_from_slice(&udp_len.to_be_bytes());
+    segment.extend_from_slice(&[0, 0]);
+    segment.extend_from_slice(payload);
+
+    // 校验和覆盖伪首部：源地址、目的地址、协议号与 UDP 长度
+    let mut pseudo = Vec::with_capacity(40);
+    let (ethertype, mac, header) =
+        match (source_ip, destination_ip) {
+            (
+                IpAddr::V4(source_ip),
+                IpAddr::V4(destination_ip),
+            ) => {
+                let total_len =
+                    u16::try_from(20 + segment.len())
+                        .ok()?;
+                let mut header = vec![0x45, 0];
+                header.extend_from_slice(
+                    &total_len.to_be_bytes(),
+                );
+                // 标识为 0，置位 DF
+                header.extend_from_slice(&[0, 0, 0x40, 0]);
+                header.extend_from_slice(&[
+                    DEFAULT_TTL,
+                    IPPROTO_UDP,
+                    0,
+                    0,
+                ]);
+                header
+                    .extend_from_slice(&source_ip.octets());
+                header.extend_from_slice(
+                    &destination_ip.octets(),
+                );
+                let checksum =
+                    !ones_complement_sum(&header, 0);
+                header[10..12].copy_from_slice(
+                    &checksum.to_be_bytes(),
+                );
+
+                pseudo
+                    .extend_from_slice(&source_ip.octets());
+                pseudo.extend_from_slice(
+                    &destination_ip.octets(),
+                );
+                let mac = if destination_ip.is_broadcast() {
+                    [0xFF; 6]
+                } else if destination_ip.is_multicast() {
+                    let [_, b, c, d] =
+                        destination_ip.octets();
+                    [0x01, 0x00, 0x5E, b & 0x7F, c, d]
+                } else {
+                    DESTINATION_MAC
+                };
+                (ETHERTYPE_IPV4, mac, header)
+            }
+            (
+                IpAddr::V6(source_ip),
+                IpAddr::V6(destination_ip),
+            ) => {
+                let mut header = vec![0x60, 0, 0, 0];
+                header.extend_from_slice(
+                    &udp_len.to_be_bytes(),
+                );
+                header.extend_from_slice(&[
+                    IPPROTO_UDP,
+                    DEFAULT_TTL,
+                ]);
+                header
+                    .extend_from_slice(&source_ip.octets());
+                header.extend_from_slice(
+                    &destination_ip.octets(),
+                );
+
+                pseudo
+                    .extend_from_slice(&source_ip.octets());
+                pseudo.extend_from_slice(
+                    &destination_ip.octets(),
+                );
+                let mac = if destination_ip.is_multicast() {
+                    let octets = destination_ip.octets();
+                    [
+                        0x33, 0x33, octets[12], octets[13],
+                        octets[14], octets[15],
+                    ]
+                } else {
+                    DESTINATION_MAC
+                };
+                (ETHERTYPE_IPV6, mac, header)
+            }
+            _ => unreachable!(
+                "address families are unified above"
+            ),
+        };
+    pseudo.extend_from_slice(&[0, IPPROTO_UDP]);
+    pseudo.extend_from_slice(&udp_len.to_be_bytes());
+    // 计算结果为 0 时按 RFC 768 写为全 1
+    let checksum = match !ones_complement_sum(
+        &segment,
+        ones_complement_sum(&pseudo, 0),
+    ) {
+        0 => 0xFFFF,
+        checksum => checksum,
+    };
+    segment[6..8].copy_from_slice(&checksum.to_be_bytes());
+
+    let mut frame = Vec::with_capacity(
+        14 + header.len() + segment.len(),
+    );
+    frame.extend_from_slice(&mac);
+    frame.extend_from_slice(&SOURCE_MAC);
+    frame.extend_from_slice(&ethertype.to_be_bytes());
+    frame.extend(header);
+    frame.extend(segment);
+    Some(frame)
+}
+
+/// 16 位反码累加（用于 IP 与 UDP 校验和）
+fn ones_complement_sum(bytes: &[u8], initial: u16) -> u16 {
+    let mut sum = initial as u32;
+    for chunk in bytes.chunks(2) {
+        let word = match chunk {
+            [high, low] => {
+                u16::from_be_bytes([*high, *low])
+            }
+            [high] => u16::from_be_bytes([*high, 0]),
+            _ => 0,
+        };
+        sum += word as u32;
+        sum = (sum & 0xFFFF) + (sum >> 16);
+    }
+    sum as u16
+}
+
+/// 跳过以太网头与 VLAN 标签，返回以太网类型与三层数据
+fn ethernet(frame: &[u8]) -> Option<(u16, &[u8])> {
+    let mut offset = 12;
+    let mut ethertype = be16(frame, offset)?;
+    while matches!(ethertype, 0x8100 | 0x88A8 | 0x9100) {
+        offset += 4;
+        ethertype = be16(frame, offset)?;
+    }
+    Some((ethertype, frame.get(offset + 2..)?))
+}
+
+/// 解析 IPv4 头
+fn ipv4(packet: &[u8]) -> Option<UdpDatagram<'_>> {
+    let header_len = (*packet.first()? as usize & 0x0F) * 4;
+    if header_len < 20 || packet[0] >> 4 != 4 {
+        return None;
+    }
+    let total_len = be16(packet, 2)? as usize;
+    // 更多分片标志或非零分片偏移
+    if be16(packet, 6)? & 0x3FFF != 0
+        || *packet.get(9)? != IPPROTO_UDP
+    {
+        return None;
+    }
+    let source: [u8; 4] =
+        packet.get(12..16)?.try_into().ok()?;
+    let destination: [u8; 4] =
+        packet.get(16..20)?.try_into().ok()?;
+    // 头部长度超出已捕获数据时无法解析
+    if packet.len() < header_len {
+        return None;
+    }
+    // 以太网最小帧可能带填充，按 IP 总长度截取
+    let end = total_len.clamp(header_len, packet.len());
+    udp(
+        IpAddr::V4(Ipv4Addr::from(source)),
+        IpAddr::V4(Ipv4Addr::from(destination)),
+        packet.get(header_len..end)?,
+    )
+}
+
+/// 解析 IPv6 头与扩展头
+fn ipv6(packet: &[u8]) -> Option<UdpDatagram<'_>> {
+    if packet.first()? >> 4 != 6 {
+        return None;
+    }
+    let payload_len = be16(packet, 4)? as usize;
+    let source: [u8; 16] =
+        packet.get(8..24)?.try_into().ok()?;
+    let destination: [u8; 16] =
+        packet.get(24..40)?.try_into().ok()?;
+    let end = (40 + payload_len).min(packet.len());
+
+    let mut next_header = *packet.get(6)?;
+    let mut offset = 40;
+    loop {
+        match next_header {
+            IPPROTO_UDP => break,
+            // 逐跳选项、路由、目的选项
+            0 | 43 | 60 => {
+                next_header = *packet.get(offset)?;
+                offset +=
+                    (*packet.get(offset + 1)? as usize + 1)
+                        * 8;
+            }
+            // 分片头：只接受未分片的数据报
+            44 => {
+                if be16(packet, offset + 2)? & 0xFFF9 != 0 {
+                    return None;
+                }
+                next_header = *packet.get(offset)?;
+                offset += 8;
+            }
+            _ => return None,
+        }
+    }
+
+    udp(
+        IpAddr::V6(Ipv6Addr::from(source)),
+        IpAddr::V6(Ipv6Addr::from(destination)),
+        packet.get(offset..end)?,
+    )
+}
+
+/// 解析 UDP 头，负载按 UDP 长度字段截取（抓包被截断时取已捕获部分）
+fn udp<'a>(
+    source: IpAddr,
+    destination: IpAddr,
+    segment: &'a [u8],
+) -> Option<UdpDatagram<'a>> {
+    if segment.len() < 8 {
+        return None;
+    }
+    let length = be16(segment, 4)? as usize;
+    let end = length.clamp(8, segment.len());
+    Some(UdpDatagram {
+        source: SocketAddr::new(source, be16(segment, 0)?),
+        destination: SocketAddr::new(
+            destination,
+            be16(segment, 2)?,
+        ),
+        payload: &segment[8..end],
+    })
+}
+
+/// 读取网络字节序的 16 位整数
+fn be16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 以太网 + VLAN + IPv4 + UDP，末尾带以太网填充
+    fn vlan_frame() -> Vec<u8> {
+        let mut frame = vec![0u8; 12];
+        frame.extend_from_slice(&[0x81, 0x00, 0x00, 0x05]);
+        frame.extend_from_slice(
+            &ETHERTYPE_IPV4.to_be_bytes(),
+        );
+        let mut ip = vec![
+            0x45, 0, 0, 31, 0, 0, 0x40, 0, 64, 17, 0, 0,
+        ];
+        ip.extend_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+        ip.extend_from_slice(&5000u16.to_be_bytes());
+        ip.extend_from_slice(&9000u16.to_be_bytes());
+        ip.extend_from_slice(&11u16.to_be_bytes());
+        ip.extend_from_slice(&[0, 0]);
+        ip.extend_from_slice(b"abc");
+        frame.extend(ip);
+        frame.extend_from_slice(&[0; 6]);
+        frame
+    }
+
+    #[test]
+    fn test_ethernet_vlan_ipv4() {
+        let frame = vlan_frame();
+        let datagram =
+            udp_datagram(LINKTYPE_ETHERNET, &frame)
+                .unwrap();
+        assert_eq!(
+            datagram.source,
+            "10.0.0.1:5000".parse().unwrap()
+        );
+        assert_eq!(
+            datagram.destination,
+            "10.0.0.2:9000".parse().unwrap()
+        );
+        assert_eq!(datagram.payload, b"abc");
+
+        // 分片的数据报被跳过
+        let mut fragment = frame.clone();
+        fragment[18 + 6] = 0x20;
+        assert!(udp_datagram(LINKTYPE_ETHERNET, &fragment)
+            .is_none());
+    }
+
+    #[test]
+    fn test_truncated_frames() {
+        // IPv4 头部长度（IHL=15）超出已捕获数据
+        let mut frame = vlan_frame();
+        frame[18] = 0x4F;
+        assert!(udp_datagram(LINKTYPE_ETHERNET, &frame)
+            .is_none());
+
+        // UDP 头只捕获到 6、7 字节
+        let frame = vlan_frame();
+        for len in [6, 7] {
+            assert!(udp_datagram(
+                LINKTYPE_ETHERNET,
+                &frame[..18 + 20 + len]
+            )
+            .is_none());
+        }
+    }
+
+    #[test]
+    fn test_synthesized_frames() {
+        for (source, destination, mac) in [
+            (
+                "10.0.0.1:5000",
+                "239.1.2.3:9000",
+                [0x01, 0x00, 0x5E, 1, 2, 3],
+            ),
+            (
+                "10.0.0.1:5000",
+                "10.0.0.2:9000",
+                DESTINATION_MAC,
+            ),
+            (
+                "[::1]:5000",
+                "[ff02::1:2]:9000",
+                [0x33, 0x33, 0, 1, 0, 2],
+            ),
+        ] {
+            let source: SocketAddr =
+                source.parse().unwrap();
+            let destination: SocketAddr =
+                destination.parse().unwrap();
+            let frame =
+                udp_frame(source, destination, b"hello")
+                    .unwrap();
+            assert_eq!(frame[..6], mac);
+
+            let datagram =
+                udp_datagram(LINKTYPE_ETHERNET, &frame)
+                    .unwrap();
+            assert_eq!(datagram.source, source);
+            assert_eq!(datagram.destination, destination);
+            assert_eq!(datagram.payload, b"hello");
+
+            // 校验和正确时，含伪首部的反码和为全 1
+            let ip = &frame[14..];
+            let (pseudo, segment) = if source.is_ipv4() {
+                assert_eq!(
+                    ones_complement_sum(&ip[..20], 0),
+                    0xFFFF
+                );
+                let mut pseudo = ip[12..20].to_vec();
+                pseudo.extend_from_slice(&[
+                    0,
+                    IPPROTO_UDP,
+                    0,
+                    13,
+                ]);
+                (pseudo, &ip[20..])
+            } else {
+                let mut pseudo = ip[8..40].to_vec();
+                pseudo.extend_from_slice(&[
+                    0,
+                    IPPROTO_UDP,
+                    0,
+                    13,
+                ]);
+                (pseudo, &ip[40..])
+            };
+            assert_eq!(
+                ones_complement_sum(
+                    segment,
+                    ones_complement_sum(&pseudo, 0)
+                ),
+                0xFFFF
+            );
+        }
+    }
+
+    #[test]
+    fn test_raw_ipv6_with_extension_header() {
+        let mut packet = vec![0x60, 0, 0, 0, 0, 19, 0, 64];
+        packet.extend_from_slice(
+            &"::1".parse::<Ipv6Addr>().unwrap().octets(),
+        );
+        packet.extend_from_slice(
+            &"::2".parse::<Ipv6Addr>().unwrap().octets(),
+        );
+        // 目的选项扩展头，下一个头为 UDP
+        packet
+            .extend_from_slice(&[17, 0, 1, 4, 0, 0, 0, 0]);
+        packet.extend_from_slice(&53u16.to_be_bytes());
+        packet.extend_from_slice(&5353u16.to_be_bytes());
+        packet.extend_from_slice(&11u16.to_be_bytes());
+        packet.extend_from_slice(&[0, 0]);
+        packet.extend_from_slice(b"xyz");
+
+        let datagram =
+            udp_datagram(LINKTYPE_RAW, &packet).unwrap();
+        assert_eq!(
+            datagram.destination,
+            "[::2]:5353".parse().unwrap()
+        );
+        assert_eq!(datagram.payload, b"xyz");
+
+        let mut null = 24u32.to_le_bytes().to_vec();
+        null.extend_from_slice(&packet);
+        assert_eq!(
+            udp_datagram(LINKTYPE_NULL, &null)
+                .unwrap()
+                .payload,
+            b"xyz"
+        );
+    }
+}
@@ -0,0 +1,741 @@
+//! 抓包文件读取模块
+//!
+//! 读取 tcpdump、Wireshark 等工具保存的抓包文件：
+//!
+//! - 经典 pcap：微秒与纳秒两种时间戳魔数，大小端均可；
+//! - pcapng：支持多个节（SHB）与多个接口（IDB），按各接口的
+//!   `if_tsresol` 与 `if_tsoffset` 换算时间戳，读取增强包块（EPB）、
+//!   简单包块（SPB）与旧式包块，其余块跳过。
+
+use chrono::{DateTime, Utc};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::Path;
+
+use crate::app::error::types::{DataTransferError, Result};
+
+/// pcap 魔数（微秒时间戳）
+pub const PCAP_MAGIC_MICROS: u32 = 0xA1B2_C3D4;
+
+/// pcap 魔数（纳秒时间戳）
+pub const PCAP_MAGIC_NANOS: u32 = 0xA1B2_3C4D;
+
+/// pcapng 节头块类型
+pub const PCAPNG_SECTION_HEADER: u32 = 0x0A0D_0D0A;
+
+/// pcapng 字节序魔数
+pub const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+
+/// pcapng 接口描述块类型
+pub const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+
+/// pcapng 增强包块类型
+pub const PCAPNG_ENHANCED_PACKET: u32 = 6;
+
+/// pcapng 旧式包块类型
+const PCAPNG_OBSOLETE_PACKET: u32 = 2;
+
+/// pcapng 简单包块类型
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+
+/// 接口选项：时间戳精度
+pub const OPTION_TSRESOL: u16 = 9;
+
+/// 接口选项：时间戳偏移（秒）
+const OPTION_TSOFFSET: u16 = 14;
+
+/// 单个块或数据包的最大长度
+const MAX_RECORD_LEN: usize = 16 * 1024 * 1024;
+
+/// 抓包文件中的一帧
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// 接口序号（经典 pcap 总为 0）
+    pub interface: u32,
+    /// 链路层类型（LINKTYPE_*）
+    pub link_type: u16,
+    /// 捕获时间
+    pub timestamp: DateTime<Utc>,
+    /// 捕获的帧内容
+    pub data: Vec<u8>,
+}
+
+/// 时间戳单位
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resolution {
+    /// 10^-n 秒
+    Decimal(u32),
+    /// 2^-n 秒
+    Binary(u32),
+}
+
+/// pcapng 接口
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u16,
+    resolution: Resolution,
+    offset_secs: i64,
+}
+
+impl Interface {
+    /// 把时间戳单位数换算为 Unix 纪元以来的纳秒数
+    fn timestamp_ns(&self, units: u64) -> i128 {
+        let units = units as i128;
+        let ns = match self.resolution {
+            Resolution::Decimal(exp) if exp <= 9 => {
+                units * 10i128.pow(9 - exp)
+            }
+            Resolution::Decimal(exp) => {
+                units / 10i128.pow(exp - 9)
+            }
+            Resolution::Binary(exp) => {
+                (units * 1_000_000_000) >> exp
+            }
+        };
+        ns + self.offset_secs as i128 * 1_000_000_000
+    }
+}
+
+/// 文件格式与解析状态
+enum Format {
+    Pcap {
+        big_endian: bool,
+        nanos: bool,
+        link_type: u16,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// 抓包文件读取器
+pub struct CaptureReader<R> {
+    reader: R,
+    format: Format,
+    /// 上一帧的时间戳（简单包块没有时间戳，沿用该值）
+    last_timestamp_ns: i128,
+}
+
+impl CaptureReader<BufReader<File>> {
+    /// 打开抓包文件，按魔数识别 pcap 或 pcapng
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| {
+            DataTransferError::validation(
+                "Capture File",
+                format!(
+                    "Failed to open {}: {e}",
+                    path.display()
+                ),
+            )
+        })?;
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    /// 从任意数据流创建读取器
+    pub fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        reader
+            .read_exact(&mut magic)
+            .map_err(|_| malformed("file is too short"))?;
+
+        let format = if u32::from_be_bytes(magic)
+            == PCAPNG_SECTION_HEADER
+        {
+            let big_endian =
+                read_section_header(&mut reader)?;
+            Format::PcapNg {
+                big_endian,
+                interfaces: Vec::new(),
+            }
+        } else {
+            let (big_endian, nanos) = match (
+                u32::from_le_bytes(magic),
+                u32::from_be_bytes(magic),
+            ) {
+                (PCAP_MAGIC_MICROS, _) => (false, false),
+                (PCAP_MAGIC_NANOS, _) => (false, true),
+                (_, PCAP_MAGIC_MICROS) => (true, false),
+                (_, PCAP_MAGIC_NANOS) => (true, true),
+                _ => {
+                    return Err(
+                        DataTransferError::validation(
+                            "Capture File",
+                            "Not a pcap or pcapng file",
+                        ),
+                    )
+                }
+            };
+            let mut header = [0u8; 20];
+            reader.read_exact(&mut header).map_err(
+                |_| malformed("truncated file header"),
+            )?;
+            // 链路层类型字段的高位可能携带 FCS 信息
+            let link_type =
+                u32_at(&header, 16, big_endian) as u16;
+            Format::Pcap {
+                big_endian,
+                nanos,
+                link_type,
+            }
+        };
+
+        Ok(Self {
+            reader,
+            format,
+            last_timestamp_ns: 0,
+        })
+    }
+
+    /// 读取下一帧，文件结束时返回 `None`
+    pub fn next_frame(&mut self) -> Result<Option<Frame>> {
+        match self.format {
+            Format::Pcap {
+                big_endian,
+                nanos,
+                link_type,
+            } => {
+                self.next_pcap(big_endian, nanos, link_type)
+            }
+            Format::PcapNg { .. } => self.next_pcapng(),
+        }
+    }
+
+    /// 读取经典 pcap 的下一条记录
+    fn next_pcap(
+        &mut self,
+        big_endian: bool,
+        nanos: bool,
+        link_type: u16,
+    ) -> Result<Option<Frame>> {
+        let mut header = [0u8; 16];
+        if !read_or_eof(&mut self.reader, &mut header)? {
+            return Ok(None);
+        }
+        let secs = u32_at(&header, 0, big_endian) as i128;
+        let fraction =
+            u32_at(&header, 4, big_endian) as i128;
+        let captured =
+            u32_at(&header, 8, big_endian) as usize;
+        if captured > MAX_RECORD_LEN {
+            return Err(malformed(
+                "record length too large",
+            ));
+        }
+
+        let mut data = vec![0u8; captured];
+        self.reader
+            .read_exact(&mut data)
+            .map_err(|_| malformed("truncated record"))?;
+
+        let fraction_ns =
+            if nanos { fraction } else { fraction * 1000 };
+        let timestamp_ns =
+            secs * 1_000_000_000 + fraction_ns;
+        self.last_timestamp_ns = timestamp_ns;
+        Ok(Some(Frame {
+            interface: 0,
+            link_type,
+            timestamp: to_datetime(timestamp_ns),
+            data,
+        }))
+    }
+
+    /// 读取 pcapng 的块，直到得到下一帧
+    fn next_pcapng(&mut self) -> Result<Option<Frame>> {
+        loop {
+            let mut block_type = [0u8; 4];
+            if !read_or_eof(
+                &mut self.reader,
+                &mut block_type,
+            )? {
+                return Ok(None);
+            }
+
+            // 新的节可能改变字节序，并清空接口列表
+            if u32::from_be_bytes(block_type)
+                == PCAPNG_SECTION_HEADER
+            {
+                let big_endian =
+                    read_section_header(&mut self.reader)?;
+                self.format = Format::PcapNg {
+                    big_endian,
+                    interfaces: Vec::new(),
+                };
+                continue;
+            }
+
+            let Format::PcapNg {
+                big_endian,
+                ref mut interfaces,
+            } = self.format
+            else {
+                unreachable!("pcapng block in pcap file");
+            };
+            let block_type =
+                u32_at(&block_type, 0, big_endian);
+            let body = read_block_body(
+                &mut self.reader,
+                big_endian,
+            )?;
+
+            let (interface, units, captured, data_offset) =
+                match block_type {
+                    PCAPNG_INTERFACE_DESCRIPTION => {
+                        interfaces.push(parse_interface(
+                            &body, big_endian,
+                        )?);
+                        continue;
+                    }
+                    PCAPNG_ENHANCED_PACKET => {
+                        let fields = packet_fields(&body)?;
+                        (
+                            u32_at(fields, 0, big_endian),
+                            Some(timestamp_units(
+                                fields, big_endian,
+                            )),
+                            u32_at(fields, 12, big_endian)
+                                as usize,
+                            20,
+                        )
+                    }
+                    PCAPNG_OBSOLETE_PACKET => {
+                        let fields = packet_fields(&body)?;
+                        (
+                            u16_at(fields, 0, big_endian)
+                                as u32,
+                            Some(timestamp_units(
+                                fields, big_endian,
+                            )),
+                            u32_at(fields, 12, big_endian)
+                                as usize,
+                            20,
+                        )
+                    }
+                    PCAPNG_SIMPLE_PACKET => {
+                        if body.len() < 4 {
+                            return Err(malformed(
+                                "truncated simple packet block",
+                            ));
+                        }
+                        let original =
+                            u32_at(&body, 0, big_endian)
+                                as usize;
+                        (
+                            0,
+                            None,
+                            original.min(body.len() - 4),
+                            4,
+                        )
+                    }
+                    _ => continue,
+                };
+
+            let Some(description) =
+                interfaces.get(interface as usize).copied()
+            else {
+                return Err(malformed(
+                    "packet refers to an undefined interface",
+                ));
+            };
+            let data = body
+                .get(data_offset..data_offset + captured)
+                .ok_or_else(|| {
+                    malformed("packet data exceeds block")
+                })?
+                .to_vec();
+            if let Some(units) = units {
+                self.last_timestamp_ns =
+                    description.timestamp_ns(units);
+            }
+
+            return Ok(Some(Frame {
+                interface,
+                link_type: description.link_type,
+                timestamp: to_datetime(
+                    self.last_timestamp_ns,
+                ),
+                data,
+            }));
+        }
+    }
+}
+
+/// 读取节头块中块类型之后的部分，返回该节是否为大端序
+fn read_section_header(
+    reader: &mut impl Read,
+) -> Result<bool> {
+    let mut fields = [0u8; 8];
+    reader.read_exact(&mut fields).map_err(|_| {
+        malformed("truncated section header")
+    })?;
+    let big_endian = match u32::from_be_bytes(
+        fields[4..8].try_into().unwrap_or_default(),
+    ) {
+        PCAPNG_BYTE_ORDER_MAGIC => true,
+        magic
+            if magic.swap_bytes()
+                == PCAPNG_BYTE_ORDER_MAGIC =>
+        {
+            false
+        }
+        _ => {
+            return Err(malformed(
+                "invalid byte-order magic",
+            ))
+        }
+    };
+    let total = u32_at(&fields, 0, big_endian) as usize;
+    if !(28..=MAX_RECORD_LEN).contains(&total)
+        || !total.is_multiple_of(4)
+    {
+        return Err(malformed(
+            "invalid section header length",
+        ));
+    }
+    // 跳过版本号、节长度与选项，以及末尾的块长度
+    let mut rest = vec![0u8; total - 12];
+    reader.read_exact(&mut rest).map_err(|_| {
+        malformed("truncated section header")
+    })?;
+    Ok(big_endian)
+}
+
+/// 读取块长度、块内容与末尾的块长度，返回块内容
+fn read_block_body(
+    reader: &mut impl Read,
+    big_endian: bool,
+) -> Result<Vec<u8>> {
+    let mut length = [0u8; 4];
+    reader
+        .read_exact(&mut length)
+        .map_err(|_| malformed("truncated block"))?;
+    let total = u32_at(&length, 0, big_endian) as usize;
+    if !(12..=MAX_RECORD_LEN).contains(&total)
+        || !total.is_multiple_of(4)
+    {
+        return Err(malformed("invalid block length"));
+    }
+    let mut body = vec![0u8; total - 8];
+    reader
+        .read_exact(&mut body)
+        .map_err(|_| malformed("truncated block"))?;
+    body.truncate(total - 12);
+    Ok(body)
+}
+
+/// 解析接口描述块
+fn parse_interface(
+    body: &[u8],
+    big_endian: bool,
+) -> Result<Interface> {
+    if body.len() < 8 {
+        return Err(malformed(
+            "truncated interface description block",
+        ));
+    }
+    let mut interface = Interface {
+        link_type: u16_at(body, 0, big_endian),
+        resolution: Resolution::Decimal(6),
+        offset_secs: 0,
+    };
+
+    let mut offset = 8;
+    while offset + 4 <= body.len() {
+        let code = u16_at(body, offset, big_endian);
+        let len =
+            u16_at(body, offset + 2, big_endian) as usize;
+        let value = body
+            .get(offset + 4..offset + 4 + len)
+            .ok_or_else(|| malformed("truncated option"))?;
+        match (code, value) {
+            (0, _) => break,
+            (OPTION_TSRESOL, [resolution]) => {
+                let exp = (resolution & 0x7F) as u32;
+                interface.resolution =
+                    if resolution & 0x80 == 0 {
+                        Resolution::Decimal(exp)
+                    } else {
+                        Resolution::Binary(exp)
+                    };
+                let supported = match interface.resolution {
+                    Resolution::Decimal(exp) => exp <= 18,
+                    Resolution::Binary(exp) => exp <= 63,
+                };
+                if !supported {
+                    return Err(malformed(
+                        "unsupported timestamp resolution",
+                    ));
+                }
+            }
+            (OPTION_TSOFFSET, value)
+                if value.len() == 8 =>
+            {
+                let bytes =
+                    value.try_into().unwrap_or_default();
+                interface.offset_secs = if big_endian {
+                    i64::from_be_bytes(bytes)
+                } else {
+                    i64::from_le_bytes(bytes)
+                };
+            }
+            _ => {}
+        }
+        offset += 4 + len.div_ceil(4) * 4;
+    }
+    Ok(interface)
+}
+
+/// 增强包块与旧式包块的固定字段（至少 20 字节）
+fn packet_fields(body: &[u8]) -> Result<&[u8]> {
+    if body.len() < 20 {
+        return Err(malformed("truncated packet block"));
+    }
+    Ok(body)
+}
+
+/// 读取 64 位时间戳（高 32 位在前）
+fn timestamp_units(fields: &[u8], big_endian: bool) -> u64 {
+    (u32_at(fields, 4, big_endian) as u64) << 32
+        | u32_at(fields, 8, big_endian) as u64
+}
+
+/// 读取数据，开头即到达文件末尾时返回 `false`
+fn read_or_eof(
+    reader: &mut impl Read,
+    buf: &mut [u8],
+) -> Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(malformed("truncated file"))
+            }
+            Ok(read) => filled += read,
+            Err(e)
+                if e.kind() == ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(true)
+}
+
+/// 按字节序读取 32 位整数
+fn u32_at(
+    bytes: &[u8],
+    offset: usize,
+    big_endian: bool,
+) -> u32 {
+    let value: [u8; 4] = bytes[offset..offset + 4]
+        .try_into()
+        .unwrap_or_default();
+    if big_endian {
+        u32::from_be_bytes(value)
+    } else {
+        u32::from_le_bytes(value)
+    }
+}
+
+/// 按字节序读取 16 位整数
+fn u16_at(
+    bytes: &[u8],
+    offset: usize,
+    big_endian: bool,
+) -> u16 {
+    let value: [u8; 2] = bytes[offset..offset + 2]
+        .try_into()
+        .unwrap_or_default();
+    if big_endian {
+        u16::from_be_bytes(value)
+    } else {
+        u16::from_le_bytes(value)
+    }
+}
+
+/// 纳秒时间戳转为 UTC 时间，超出范围时取 Unix 纪元
+fn to_datetime(ns: i128) -> DateTime<Utc> {
+    i64::try_from(ns)
+        .map(DateTime::from_timestamp_nanos)
+        .unwrap_or_default()
+}
+
+/// 文件格式错误
+fn malformed(message: &str) -> DataTransferError {
+    DataTransferError::config(format!(
+        "Malformed capture file: {message}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// 构造一个 pcapng 块（小端序）
+    fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let padded = body.len().div_ceil(4) * 4;
+        let total = (padded + 12) as u32;
+        let mut out = Vec::new();
+        out.extend_from_slice(&block_type.to_le_bytes());
+        out.extend_from_slice(&total.to_le_bytes());
+        out.extend_from_slice(body);
+        out.resize(8 + padded, 0);
+        out.extend_from_slice(&total.to_le_bytes());
+        out
+    }
+
+    fn section_header() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(
+            &PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes(),
+        );
+        body.extend_from_slice(&1u16.to_le_bytes());
+        body.extend_from_slice(&0u16.to_le_bytes());
+        body.extend_from_slice(&(-1i64).to_le_bytes());
+        block(PCAPNG_SECTION_HEADER, &body)
+    }
+
+    fn interface(
+        link_type: u16,
+        tsresol: Option<u8>,
+    ) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&link_type.to_le_bytes());
+        body.extend_from_slice(&[0, 0]);
+        body.extend_from_slice(&65535u32.to_le_bytes());
+        if let Some(resolution) = tsresol {
+            body.extend_from_slice(
+                &OPTION_TSRESOL.to_le_bytes(),
+            );
+            body.extend_from_slice(&1u16.to_le_bytes());
+            body.extend_from_slice(&[resolution, 0, 0, 0]);
+            body.extend_from_slice(&[0; 4]);
+        }
+        block(PCAPNG_INTERFACE_DESCRIPTION, &body)
+    }
+
+    fn enhanced(
+        interface: u32,
+        units: u64,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(&interface.to_le_bytes());
+        body.extend_from_slice(
+            &((units >> 32) as u32).to_le_bytes(),
+        );
+        body.extend_from_slice(
+            &(units as u32).to_le_bytes(),
+        );
+        body.extend_from_slice(
+            &(data.len() as u32).to_le_bytes(),
+        );
+        body.extend_from_slice(
+            &(data.len() as u32).to_le_bytes(),
+        );
+        body.extend_from_slice(data);
+        block(PCAPNG_ENHANCED_PACKET, &body)
+    }
+
+    #[test]
+    fn test_pcap_byte_orders_and_resolutions() {
+        for (magic, big_endian, fraction, expected_ns) in [
+            (PCAP_MAGIC_MICROS, false, 250u32, 250_000),
+            (PCAP_MAGIC_NANOS, false, 250, 250),
+            (PCAP_MAGIC_MICROS, true, 250, 250_000),
+            (PCAP_MAGIC_NANOS, true, 250, 250),
+        ] {
+            let put = |out: &mut Vec<u8>, value: u32| {
+                out.extend_from_slice(&if big_endian {
+                    value.to_be_bytes()
+                } else {
+                    value.to_le_bytes()
+                });
+            };
+            let mut file = Vec::new();
+            put(&mut file, magic);
+            file.extend_from_slice(&[0; 12]);
+            put(&mut file, 65535);
+            put(&mut file, 1);
+            put(&mut file, 1_700_000_000);
+            put(&mut file, fraction);
+            put(&mut file, 3);
+            put(&mut file, 3);
+            file.extend_from_slice(b"abc");
+
+            let mut reader =
+                CaptureReader::new(Cursor::new(file))
+                    .unwrap();
+            let frame =
+                reader.next_frame().unwrap().unwrap();
+            assert_eq!(frame.link_type, 1);
+            assert_eq!(frame.data, b"abc");
+            assert_eq!(
+                frame.timestamp.timestamp_nanos_opt(),
+                Some(
+                    1_700_000_000_000_000_000 + expected_ns
+                )
+            );
+            assert!(reader.next_frame().unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn test_pcapng_interfaces_and_resolutions() {
+        let mut file = section_header();
+        file.extend(interface(1, None));
+        file.extend(interface(101, Some(9)));
+        file.extend(block(5, &[0; 8]));
+        file.extend(enhanced(
+            0,
+            1_700_000_000_000_001,
+            b"eth",
+        ));
+        file.extend(enhanced(
+            1,
+            1_700_000_000_000_000_002,
+            b"raw",
+        ));
+
+        let mut reader =
+            CaptureReader::new(Cursor::new(file)).unwrap();
+        let first = reader.next_frame().unwrap().unwrap();
+        assert_eq!(
+            (first.interface, first.link_type),
+            (0, 1)
+        );
+        assert_eq!(
+            first.timestamp.timestamp_nanos_opt(),
+            Some(1_700_000_000_000_001_000)
+        );
+        let second = reader.next_frame().unwrap().unwrap();
+        assert_eq!(
+            (second.interface, second.link_type),
+            (1, 101)
+        );
+        assert_eq!(second.data, b"raw");
+        assert_eq!(
+            second.timestamp.timestamp_nanos_opt(),
+            Some(1_700_000_000_000_000_002)
+        );
+        assert!(reader.next_frame().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_rejects_unknown_and_truncated_files() {
+        assert!(CaptureReader::new(Cursor::new(vec![
+            0;
+            24
+        ]))
+        .is_err());
+
+        let mut file = section_header();
+        file.extend(enhanced(0, 0, b"x"));
+        let mut reader =
+            CaptureReader::new(Cursor::new(file)).unwrap();
+        assert!(reader.next_frame().is_err());
+    }
+}
@@ -0,0 +1,354 @@
+//! 抓包文件写入模块
+//!
+//! 以小端序写出经典 pcap（纳秒时间戳魔数）或 pcapng 文件。
+//! pcapng 的每个接口对应一个接口描述块，时间戳精度为纳秒，
+//! 接口名写入 `if_name` 选项，便于在 Wireshark 中区分接收接口。
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::capture::reader::{
+    OPTION_TSRESOL, PCAPNG_BYTE_ORDER_MAGIC,
+    PCAPNG_ENHANCED_PACKET, PCAPNG_INTERFACE_DESCRIPTION,
+    PCAPNG_SECTION_HEADER, PCAP_MAGIC_NANOS,
+};
+
+/// 写入的最大捕获长度
+const SNAPLEN: u32 = 262_144;
+
+/// 接口选项：接口名
+const OPTION_NAME: u16 = 2;
+
+/// 抓包文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureFormat {
+    /// 经典 libpcap
+    Pcap,
+    /// pcapng
+    PcapNg,
+}
+
+impl fmt::Display for CaptureFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            CaptureFormat::Pcap => write!(f, "pcap"),
+            CaptureFormat::PcapNg => write!(f, "pcapng"),
+        }
+    }
+}
+
+impl FromStr for CaptureFormat {
+    type Err = DataTransferError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "pcap" => Ok(CaptureFormat::Pcap),
+            "pcapng" => Ok(CaptureFormat::PcapNg),
+            other => Err(DataTransferError::validation(
+                "Capture Format",
+                format!(
+                    "Unknown format: {other} (expected pcap or pcapng)"
+                ),
+            )),
+        }
+    }
+}
+
+/// 抓包文件写入器
+pub struct CaptureWriter<W: Write> {
+    writer: W,
+    format: CaptureFormat,
+    link_type: u16,
+    /// pcapng 中已写出接口描述块的接口名，按接口序号排列
+    interfaces: Vec<String>,
+}
+
+impl CaptureWriter<BufWriter<File>> {
+    /// 创建抓包文件
+    pub fn create(
+        path: &Path,
+        format: CaptureFormat,
+        link_type: u16,
+    ) -> Result<Self> {
+        let file = File::create(path)?;
+        Self::new(BufWriter::new(file), format, link_type)
+    }
+}
+
+impl<W: Write> CaptureWriter<W> {
+    /// 写出文件头（pcap 文件头或 pcapng 节头块）
+    pub fn new(
+        mut writer: W,
+        format: CaptureFormat,
+        link_type: u16,
+    ) -> Result<Self> {
+        match format {
+            CaptureFormat::Pcap => {
+                writer.write_all(
+                    &PCAP_MAGIC_NANOS.to_le_bytes(),
+                )?;
+                writer.write_all(&2u16.to_le_bytes())?;
+                writer.write_all(&4u16.to_le_bytes())?;
+                // 时区与时间精度字段固定为 0
+                writer.write_all(&[0; 8])?;
+                writer.write_all(&SNAPLEN.to_le_bytes())?;
+                writer.write_all(
+                    &(link_type as u32).to_le_bytes(),
+                )?;
+            }
+            CaptureFormat::PcapNg => {
+                let mut body = Vec::with_capacity(16);
+                body.extend_from_slice(
+                    &PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes(),
+                );
+                body.extend_from_slice(&1u16.to_le_bytes());
+                body.extend_from_slice(&0u16.to_le_bytes());
+                // 节长度未知
+                body.extend_from_slice(
+                    &(-1i64).to_le_bytes(),
+                );
+                write_block(
+                    &mut writer,
+                    PCAPNG_SECTION_HEADER,
+                    &body,
+                )?;
+            }
+        }
+
+        Ok(Self {
+            writer,
+            format,
+            link_type,
+            interfaces: Vec::new(),
+        })
+    }
+
+    /// 写入一帧，`interface` 为接收接口名（pcapng 按接口名分配接口序号）
+    pub fn write_frame(
+        &mut self,
+        interface: &str,
+        timestamp: DateTime<Utc>,
+        data: &[u8],
+    ) -> Result<()> {
+        let ns = timestamp.timestamp_nanos_opt().ok_or_else(
+            || {
+                DataTransferError::validation(
+                    "Timestamp",
+                    format!(
+                        "Timestamp out of range: {timestamp}"
+                    ),
+                )
+            },
+        )?;
+        let captured = data.len().min(SNAPLEN as usize);
+
+        match self.format {
+            CaptureFormat::Pcap => {
+                let secs = ns.div_euclid(1_000_000_000);
+                let nanos = ns.rem_euclid(1_000_000_000);
+                self.writer.write_all(
+                    &(secs as u32).to_le_bytes(),
+                )?;
+                self.writer.write_all(
+                    &(nanos as u32).to_le_bytes(),
+                )?;
+                self.writer.write_all(
+                    &(captured as u32).to_le_bytes(),
+                )?;
+                self.writer.write_all(
+                    &(data.len() as u32).to_le_bytes(),
+                )?;
+                self.writer.write_all(&data[..captured])?;
+            }
+            CaptureFormat::PcapNg => {
+                let index =
+                    self.interface_index(interface)?;
+                let units = ns as u64;
+                let mut body =
+                    Vec::with_capacity(20 + captured);
+                body.extend_from_slice(
+                    &index.to_le_bytes(),
+                );
+                body.extend_from_slice(
+                    &((units >> 32) as u32).to_le_bytes(),
+                );
+                body.extend_from_slice(
+                    &(units as u32).to_le_bytes(),
+                );
+                body.extend_from_slice(
+                    &(captured as u32).to_le_bytes(),
+                );
+                body.extend_from_slice(
+                    &(data.len() as u32).to_le_bytes(),
+                );
+                body.extend_from_slice(&data[..captured]);
+                write_block(
+                    &mut self.writer,
+                    PCAPNG_ENHANCED_PACKET,
+                    &body,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 刷新缓冲区并返回底层写入器
+    pub fn finish(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// 查找接口序号，首次出现的接口先写出接口描述块
+    fn interface_index(
+        &mut self,
+        name: &str,
+    ) -> Result<u32> {
+        if let Some(index) = self
+            .interfaces
+            .iter()
+            .position(|interface| interface == name)
+        {
+            return Ok(index as u32);
+        }
+
+        let mut body = Vec::new();
+        body.extend_from_slice(
+            &self.link_type.to_le_bytes(),
+        );
+        body.extend_from_slice(&[0, 0]);
+        body.extend_from_slice(&SNAPLEN.to_le_bytes());
+        push_option(&mut body, OPTION_TSRESOL, &[9]);
+        if !name.is_empty() {
+            push_option(
+                &mut body,
+                OPTION_NAME,
+                name.as_bytes(),
+            );
+        }
+        push_option(&mut body, 0, &[]);
+        write_block(
+            &mut self.writer,
+            PCAPNG_INTERFACE_DESCRIPTION,
+            &body,
+        )?;
+
+        self.interfaces.push(name.to_string());
+        Ok(self.interfaces.len() as u32 - 1)
+    }
+}
+
+/// 追加一个选项（值补齐到 4 字节边界）
+fn push_option(
+    body: &mut Vec<u8>,
+    code: u16,
+    value: &[u8],
+) {
+    body.extend_from_slice(&code.to_le_bytes());
+    body.extend_from_slice(
+        &(value.len() as u16).to_le_bytes(),
+    );
+    body.extend_from_slice(value);
+    body.resize(body.len().div_ceil(4) * 4, 0);
+}
+
+/// 写出一个 pcapng 块（内容补齐到 4 字节边界）
+fn write_block(
+    writer: &mut impl Write,
+    block_type: u32,
+    body: &[u8],
+) -> Result<()> {
+    let padded = body.len().div_ceil(4) * 4;
+    let total = (padded + 12) as u32;
+    writer.write_all(&block_type.to_le_bytes())?;
+    writer.write_all(&total.to_le_bytes())?;
+    writer.write_all(body)?;
+    writer.write_all(&[0; 3][..padded - body.len()])?;
+    writer.write_all(&total.to_le_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::capture::packet::LINKTYPE_ETHERNET;
+    use crate::core::capture::reader::CaptureReader;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_round_trip_through_reader() {
+        let first = DateTime::from_timestamp_nanos(
+            1_700_000_000_123_456_789,
+        );
+        let second = DateTime::from_timestamp_nanos(
+            1_700_000_001_000_000_001,
+        );
+
+        for format in
+            [CaptureFormat::Pcap, CaptureFormat::PcapNg]
+        {
+            let mut writer = CaptureWriter::new(
+                Vec::new(),
+                format,
+                LINKTYPE_ETHERNET,
+            )
+            .unwrap();
+            writer
+                .write_frame("eth0", first, b"first")
+                .unwrap();
+            writer
+                .write_frame("eth1", second, b"second!")
+                .unwrap();
+            writer
+                .write_frame("eth0", second, b"")
+                .unwrap();
+            let file = writer.finish().unwrap();
+
+            let mut reader =
+                CaptureReader::new(Cursor::new(file))
+                    .unwrap();
+            let mut frames = Vec::new();
+            while let Some(frame) =
+                reader.next_frame().unwrap()
+            {
+                assert_eq!(
+                    frame.link_type,
+                    LINKTYPE_ETHERNET
+                );
+                frames.push((
+                    frame.interface,
+                    frame.timestamp,
+                    frame.data,
+                ));
+            }
+            let interfaces = match format {
+                CaptureFormat::Pcap => [0, 0, 0],
+                CaptureFormat::PcapNg => [0, 1, 0],
+            };
+            assert_eq!(
+                frames,
+                vec![
+                    (
+                        interfaces[0],
+                        first,
+                        b"first".to_vec()
+                    ),
+                    (
+                        interfaces[1],
+                        second,
+                        b"second!".to_vec()
+                    ),
+                    (interfaces[2], second, Vec::new()),
+                ],
+                "{format}"
+            );
+        }
+    }
+}
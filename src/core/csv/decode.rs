@@ -0,0 +1,660 @@
+//! CSV 解码模块
+//!
+//! 按与 CSV 场景文件相同的表头（第一行列名、第二行类型）把数据集中的
+//! 数据包解析回字段值，写为 CSV 或 JSON Lines。长度与表头不符的数据包
+//! 标记为 `length_mismatch` 并保留原始十六进制内容。
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use pcapfile_io::{PcapReader, ReaderConfig};
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use crate::app::error::types::{AppError, Result};
+use crate::core::csv::parser::TIMESTAMP_TYPE;
+use crate::core::csv::types::CsvDataType;
+use crate::core::csv::CsvParser;
+use crate::utils::helpers::format_hex;
+
+/// 输出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeFormat {
+    /// CSV
+    Csv,
+    /// JSON Lines（每行一个 JSON 对象）
+    JsonLines,
+}
+
+impl fmt::Display for DecodeFormat {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            DecodeFormat::Csv => write!(f, "csv"),
+            DecodeFormat::JsonLines => write!(f, "jsonl"),
+        }
+    }
+}
+
+impl FromStr for DecodeFormat {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "csv" => Ok(DecodeFormat::Csv),
+            "jsonl" => Ok(DecodeFormat::JsonLines),
+            other => Err(AppError::validation(
+                "Decode Format",
+                format!(
+                    "Unknown format: {other} (expected csv or jsonl)"
+                ),
+            )),
+        }
+    }
+}
+
+/// 表头中的一列
+#[derive(Debug, Clone, PartialEq)]
+enum FieldKind {
+    /// 时间戳列，取数据包的捕获时间（不占数据包字节）
+    Timestamp,
+    /// 编码进数据包的字段
+    Value(CsvDataType),
+}
+
+/// 解码表头
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvSchema {
+    fields: Vec<(String, FieldKind)>,
+}
+
+/// 解码出的字段值
+#[derive(Debug, Clone, PartialEq)]
+enum FieldValue {
+    Signed(i64),
+    Unsigned(u64),
+    Float32(f32),
+    Float(f64),
+    Bool(bool),
+    Hex(Vec<u8>),
+    Timestamp(DateTime<Utc>),
+}
+
+impl CsvSchema {
+    /// 读取 CSV 文件的前两行作为表头（其余数据行忽略）
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| {
+            AppError::config(format!(
+                "Failed to open schema file: {e}"
+            ))
+        })?;
+        let mut lines = BufReader::new(file).lines();
+        let mut next_line = || -> Result<String> {
+            lines.next().transpose()?.ok_or_else(|| {
+                AppError::validation(
+                    "Schema",
+                    "File must have at least 2 lines (names and types)",
+                )
+            })
+        };
+        let names = next_line()?;
+        let types = next_line()?;
+        Self::parse(&names, &types)
+    }
+
+    /// 由列名行与类型行构造表头
+    pub fn parse(names: &str, types: &str) -> Result<Self> {
+        let names = CsvParser::parse_csv_line(names);
+        let types = CsvParser::parse_csv_line(types);
+        if names.len() != types.len() {
+            return Err(AppError::validation(
+                "Schema",
+                "Column names and data types count mismatch",
+            ));
+        }
+
+        // 字段按名称输出，重名会导致 JSON 中的值互相覆盖
+        if let Some((i, name)) = names
+            .iter()
+            .enumerate()
+            .find(|(i, name)| names[..*i].contains(name))
+        {
+            return Err(AppError::validation(
+                format!("Column {}", i + 1),
+                format!("Duplicate column name: {name}"),
+            ));
+        }
+
+        let fields = names
+            .into_iter()
+            .zip(types.iter())
+            .enumerate()
+            .map(|(i, (name, type_str))| {
+                if type_str == TIMESTAMP_TYPE {
+                    return Ok((
+                        name,
+                        FieldKind::Timestamp,
+                    ));
+                }
+                // 默认值表达式只影响编码，解码时只需类型与长度
+                CsvDataType::parse_type_and_default(
+                    type_str,
+                )
+                .map(|(data_type, _)| {
+                    (name, FieldKind::Value(data_type))
+                })
+                .map_err(|e| {
+                    AppError::validation(
+                        format!("Column {}", i + 1),
+                        e,
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let schema = Self { fields };
+
+        // CSV 输出附加的列不能与表头列重名；带时间戳列时不附加 `timestamp`
+        let reserved: &[&str] = if schema.has_timestamp() {
+            &["index", "status", "raw_hex"]
+        } else {
+            &["index", "timestamp", "status", "raw_hex"]
+        };
+        if let Some((i, (name, _))) =
+            schema.fields.iter().enumerate().find(
+                |(_, (name, _))| {
+                    reserved.contains(&name.as_str())
+                },
+            )
+        {
+            return Err(AppError::validation(
+                format!("Column {}", i + 1),
+                format!(
+                    "Column name {name} is reserved for decode output"
+                ),
+            ));
+        }
+        Ok(schema)
+    }
+
+    /// 数据包的预期长度
+    pub fn packet_len(&self) -> usize {
+        self.fields
+            .iter()
+            .map(|(_, kind)| match kind {
+                FieldKind::Timestamp => 0,
+                FieldKind::Value(data_type) => {
+                    data_type.size()
+                }
+            })
+            .sum()
+    }
+
+    /// 表头是否带有时间戳列
+    fn has_timestamp(&self) -> bool {
+        self.fields
+            .iter()
+            .any(|(_, kind)| *kind == FieldKind::Timestamp)
+    }
+
+    /// 按表头解析数据包，长度不符时返回 `None`
+    fn decode(
+        &self,
+        timestamp: DateTime<Utc>,
+        data: &[u8],
+    ) -> Option<Vec<FieldValue>> {
+        if data.len() != self.packet_len() {
+            return None;
+        }
+
+        let mut offset = 0;
+        let values = self
+            .fields
+            .iter()
+            .map(|(_, kind)| {
+                let data_type = match kind {
+                    FieldKind::Timestamp => {
+                        return FieldValue::Timestamp(
+                            timestamp,
+                        )
+                    }
+                    FieldKind::Value(data_type) => {
+                        data_type
+                    }
+                };
+                let bytes = &data
+                    [offset..offset + data_type.size()];
+                offset += bytes.len();
+                decode_value(data_type, bytes)
+            })
+            .collect();
+        Some(values)
+    }
+}
+
+/// 按小端序解析单个字段（与编码一致）
+fn decode_value(
+    data_type: &CsvDataType,
+    bytes: &[u8],
+) -> FieldValue {
+    let mut le = [0u8; 8];
+    le[..bytes.len().min(8)]
+        .copy_from_slice(&bytes[..bytes.len().min(8)]);
+    let unsigned = u64::from_le_bytes(le);
+    // 按字段宽度做符号扩展
+    let signed = |bits: u32| {
+        let shift = 64 - bits;
+        FieldValue::Signed(
+            ((unsigned << shift) as i64) >> shift,
+        )
+    };
+    match data_type {
+        CsvDataType::I8 => signed(8),
+        CsvDataType::I16 => signed(16),
+        CsvDataType::I32 => signed(32),
+        CsvDataType::I64 => signed(64),
+        CsvDataType::U8
+        | CsvDataType::U16
+        | CsvDataType::U32
+        | CsvDataType::U64 => {
+            FieldValue::Unsigned(unsigned)
+        }
+        CsvDataType::F32 => FieldValue::Float32(
+            f32::from_bits(unsigned as u32),
+        ),
+        CsvDataType::F64 => {
+            FieldValue::Float(f64::from_bits(unsigned))
+        }
+        CsvDataType::Bool => {
+            FieldValue::Bool(unsigned != 0)
+        }
+        CsvDataType::HexDynamic(_) => {
+            FieldValue::Hex(bytes.to_vec())
+        }
+    }
+}
+
+impl FieldValue {
+    /// CSV 单元格内容（可直接填回 CSV 场景文件）
+    fn to_cell(&self) -> String {
+        match self {
+            FieldValue::Signed(value) => value.to_string(),
+            FieldValue::Unsigned(value) => {
+                value.to_string()
+            }
+            FieldValue::Float32(value) => value.to_string(),
+            FieldValue::Float(value) => value.to_string(),
+            FieldValue::Bool(value) => value.to_string(),
+            FieldValue::Hex(bytes) => {
+                format!("0x{}", format_hex(bytes))
+            }
+            FieldValue::Timestamp(time) => {
+                format_timestamp(*time)
+            }
+        }
+    }
+
+    /// JSON 值（NaN 与无穷大写为字符串）
+    fn to_json(&self) -> Value {
+        match self {
+            FieldValue::Signed(value) => json!(value),
+            FieldValue::Unsigned(value) => json!(value),
+            FieldValue::Float32(value)
+                if value.is_finite() =>
+            {
+                json!(value)
+            }
+            FieldValue::Float(value)
+                if value.is_finite() =>
+            {
+                json!(value)
+            }
+            FieldValue::Bool(value) => json!(value),
+            other => json!(other.to_cell()),
+        }
+    }
+}
+
+/// 解码结果
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DecodeSummary {
+    /// 数据包总数
+    pub packets: u64,
+    /// 长度与表头不符的数据包数
+    pub mismatched: u64,
+}
+
+/// 按表头解码数据集 `dataset_path`，写入 `output`
+///
+/// CSV 输出的列为 `index`、`timestamp`（表头自带时间戳列时省略）、
+/// 表头各列、`status` 与 `raw_hex`；`raw_hex` 只在长度不符时填写。
+pub fn decode_dataset(
+    dataset_path: &Path,
+    schema: &CsvSchema,
+    output: &Path,
+    format: DecodeFormat,
+) -> Result<DecodeSummary> {
+    let dataset_name = dataset_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("dataset");
+    let mut reader = PcapReader::new_with_config(
+        dataset_path.parent().unwrap_or(dataset_path),
+        dataset_name,
+        ReaderConfig::default(),
+    )?;
+    let mut writer = BufWriter::new(File::create(output)?);
+
+    let timestamp_column = !schema.has_timestamp();
+    if format == DecodeFormat::Csv {
+        let mut header = vec!["index".to_string()];
+        if timestamp_column {
+            header.push("timestamp".to_string());
+        }
+        header.extend(
+            schema
+                .fields
+                .iter()
+                .map(|(name, _)| csv_field(name)),
+        );
+        header.push("status".to_string());
+        header.push("raw_hex".to_string());
+        writeln!(writer, "{}", header.join(","))?;
+    }
+
+    let mut summary = DecodeSummary::default();
+    while let Some(packet) = reader.read_packet()? {
+        let index = summary.packets;
+        let timestamp = packet.capture_time();
+        let data = &packet.packet.data;
+        let values = schema.decode(timestamp, data);
+        summary.packets += 1;
+        if values.is_none() {
+            summary.mismatched += 1;
+        }
+
+        match format {
+            DecodeFormat::Csv => {
+                let mut row = vec![index.to_string()];
+                if timestamp_column {
+                    row.push(format_timestamp(timestamp));
+                }
+                match &values {
+                    Some(values) => {
+                        row.extend(
+                            values
+                                .iter()
+                                .map(FieldValue::to_cell),
+                        );
+                        row.push("ok".to_string());
+                        row.push(String::new());
+                    }
+                    None => {
+                        row.extend(
+                            schema
+                                .fields
+                                .iter()
+                                .map(|_| String::new()),
+                        );
+                        row.push(
+                            "length_mismatch".to_string(),
+                        );
+                        row.push(format_hex(data));
+                    }
+                }
+                writeln!(writer, "{}", row.join(","))?;
+            }
+            DecodeFormat::JsonLines => {
+                let mut record = Map::new();
+                record.insert("index".into(), json!(index));
+                record.insert(
+                    "timestamp".into(),
+                    json!(format_timestamp(timestamp)),
+                );
+                match &values {
+                    Some(values) => {
+                        let fields = schema
+                            .fields
+                            .iter()
+                            .zip(values)
+                            .map(|((name, _), value)| {
+                                (
+                                    name.clone(),
+                                    value.to_json(),
+                                )
+                            })
+                            .collect::<Map<_, _>>();
+                        record.insert(
+                            "fields".into(),
+                            Value::Object(fields),
+                        );
+                        record.insert(
+                            "status".into(),
+                            json!("ok"),
+                        );
+                    }
+                    None => {
+                        record.insert(
+                            "status".into(),
+                            json!("length_mismatch"),
+                        );
+                        record.insert(
+                            "length".into(),
+                            json!(data.len()),
+                        );
+                        record.insert(
+                            "expected_length".into(),
+                            json!(schema.packet_len()),
+                        );
+                        record.insert(
+                            "raw_hex".into(),
+                            json!(format_hex(data)),
+                        );
+                    }
+                }
+                writeln!(
+                    writer,
+                    "{}",
+                    Value::Object(record)
+                )?;
+            }
+        }
+    }
+    writer.flush()?;
+
+    Ok(summary)
+}
+
+/// 纳秒精度的 RFC 3339 时间
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
+
+/// 含逗号或引号的 CSV 字段加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::dataset::compile::{
+        compile_csv, CompileTiming,
+    };
+    use pcapfile_io::{
+        DataPacket, PcapWriter, WriterConfig,
+    };
+
+    #[test]
+    fn test_decode_round_trips_encoded_rows() {
+        let names = "a,b,c,d,e,f,g";
+        let types = "i8,i16,u32,f32,f64,bool,hex_3";
+        let schema =
+            CsvSchema::parse(names, types).unwrap();
+        assert_eq!(
+            schema.packet_len(),
+            1 + 2 + 4 + 4 + 8 + 1 + 3
+        );
+        // 重名的列被拒绝
+        assert!(
+            CsvSchema::parse("a,b,a", "u8,u8,u8").is_err()
+        );
+        // 与输出附加列重名的列名被拒绝
+        assert!(
+            CsvSchema::parse("id,status", "u8,u8").is_err()
+        );
+        assert!(CsvSchema::parse("timestamp,id", "u64,u8")
+            .is_err());
+        // 带时间戳列时不附加 timestamp 列
+        assert!(CsvSchema::parse(
+            "time,timestamp",
+            "timestamp,u64"
+        )
+        .is_ok());
+
+        let dir = std::env::temp_dir().join(format!(
+            "pcap_transfer_decode_rows_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let csv = dir.join("rows.csv");
+        let row =
+            "-5,-300,4000000000,0.1,-0.1,true,0x0a0b0c";
+        std::fs::write(
+            &csv,
+            format!("{names}\n{types}\n{row}\n"),
+        )
+        .unwrap();
+        let packet = CsvParser::from_file(&csv)
+            .unwrap()
+            .generate_packet(0)
+            .unwrap();
+
+        let values = schema
+            .decode(DateTime::UNIX_EPOCH, &packet.data)
+            .unwrap();
+        let cells: Vec<String> = values
+            .iter()
+            .map(FieldValue::to_cell)
+            .collect();
+        assert_eq!(cells.join(","), row);
+        assert!(schema
+            .decode(DateTime::UNIX_EPOCH, &packet.data[1..])
+            .is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_decode_dataset_flags_length_mismatch() {
+        let dir = std::env::temp_dir().join(format!(
+            "pcap_transfer_decode_dataset_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 编译得到的数据集按同一表头解码
+        let csv = dir.join("scenario.csv");
+        std::fs::write(
+            &csv,
+            "time,\"id, main\",value\ntimestamp,u8,u16\n1700000000.5,1,258\n",
+        )
+        .unwrap();
+        let parser = CsvParser::from_file(&csv).unwrap();
+        compile_csv(
+            &parser,
+            &dir,
+            "compiled",
+            &CompileTiming::default(),
+        )
+        .unwrap();
+        let schema = CsvSchema::from_file(&csv).unwrap();
+
+        let output = dir.join("compiled.csv");
+        let summary = decode_dataset(
+            &dir.join("compiled"),
+            &schema,
+            &output,
+            DecodeFormat::Csv,
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            DecodeSummary {
+                packets: 1,
+                mismatched: 0
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(&output).unwrap(),
+            "index,time,\"id, main\",value,status,raw_hex\n\
+             0,2023-11-14T22:13:20.500000000Z,1,258,ok,\n"
+        );
+
+        // 长度不符的数据包保留原始内容
+        let mut writer = PcapWriter::new_with_config(
+            &dir,
+            "received",
+            WriterConfig::default(),
+        )
+        .unwrap();
+        for data in [vec![1, 2, 1], vec![0xAB]] {
+            writer
+                .write_packet(
+                    &DataPacket::from_datetime(
+                        DateTime::UNIX_EPOCH,
+                        data,
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let schema =
+            CsvSchema::parse("value,id", "u8,u16").unwrap();
+        let output = dir.join("received.jsonl");
+        let summary = decode_dataset(
+            &dir.join("received"),
+            &schema,
+            &output,
+            DecodeFormat::JsonLines,
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            DecodeSummary {
+                packets: 2,
+                mismatched: 1
+            }
+        );
+        let content =
+            std::fs::read_to_string(&output).unwrap();
+        // 键与字段按表头顺序输出
+        assert!(content.starts_with(
+            "{\"index\":0,\"timestamp\":\"1970-01-01T00:00:00.000000000Z\",\"fields\":{\"value\":1,\"id\":258},\"status\":\"ok\""
+        ));
+        let lines: Vec<Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["fields"]["id"], json!(258));
+        assert_eq!(lines[0]["status"], json!("ok"));
+        assert_eq!(
+            lines[1]["status"],
+            json!("length_mismatch")
+        );
+        assert_eq!(lines[1]["raw_hex"], json!("ab"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -11,6 +11,7 @@ use crate::app::error::types::{AppError, Result};
 use crate::core::csv::types::{
     parse_hex_string, CsvDataType,
 };
+use chrono::{DateTime, Utc};
 use rand::Rng;
 
 /// 默认值表达式枚举
@@ -452,6 +453,35 @@ pub fn parse_numeric_literal<T: std::str::FromStr>(
     })
 }
 
+/// 解析时间戳：RFC 3339（如 `2024-01-01T00:00:00.5Z`）或 Unix 纪元以来的秒数（最多 9 位小数）
+pub fn parse_timestamp(
+    raw: &str,
+) -> std::result::Result<DateTime<Utc>, String> {
+    let raw = raw.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    let invalid = || format!("Invalid timestamp '{raw}'");
+    let (secs, fraction) =
+        raw.split_once('.').unwrap_or((raw, ""));
+    if fraction.len() > 9
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let secs: i64 = secs.parse().map_err(|_| invalid())?;
+    let nanos: i64 = format!("{fraction:0<9}")
+        .parse()
+        .map_err(|_| invalid())?;
+    let nanos =
+        if raw.starts_with('-') { -nanos } else { nanos };
+    secs.checked_mul(1_000_000_000)
+        .and_then(|ns| ns.checked_add(nanos))
+        .map(DateTime::from_timestamp_nanos)
+        .ok_or_else(invalid)
+}
+
 /// 解析布尔字面量
 pub fn parse_bool_literal(raw: &str) -> Result<bool> {
     match raw.trim().to_lowercase().as_str() {
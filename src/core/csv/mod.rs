@@ -1,7 +1,8 @@
 //! CSV数据解析模块
 //!
-//! 负责解析CSV格式的数据文件并生成UDP数据包
+//! 负责解析CSV格式的数据文件并生成UDP数据包，也可按同一表头把数据包解码回字段值
 
+pub mod decode;
 pub mod expr;
 pub mod parser;
 pub mod types;
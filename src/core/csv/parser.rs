@@ -1,19 +1,26 @@
 //! CSV数据解析器
 
 use crate::app::error::types::{AppError, Result};
-use crate::core::csv::expr::parse_cell_value_by_type;
+use crate::core::csv::expr::{
+    parse_cell_value_by_type, parse_timestamp,
+};
 use crate::core::csv::types::{
     CsvColumn, CsvDataType, CsvPacket,
 };
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::Path;
 
+/// 类型行中标记时间戳列的关键字（该列不编码进数据包）
+pub const TIMESTAMP_TYPE: &str = "timestamp";
+
 /// CSV解析器
 pub struct CsvParser {
     columns: Vec<CsvColumn>,
     data_rows: Vec<Vec<String>>,
+    // 各行的时间戳（无时间戳列时为空）
+    timestamps: Vec<DateTime<Utc>>,
 }
 
 impl CsvParser {
@@ -59,6 +66,22 @@ impl CsvParser {
             ));
         }
 
+        // 时间戳列最多一个
+        let mut timestamp_columns = data_types
+            .iter()
+            .enumerate()
+            .filter(|(_, type_str)| {
+                *type_str == TIMESTAMP_TYPE
+            })
+            .map(|(i, _)| i);
+        let timestamp_column = timestamp_columns.next();
+        if timestamp_columns.next().is_some() {
+            return Err(AppError::validation(
+                "CSV",
+                "Only one timestamp column is allowed",
+            ));
+        }
+
         // 解析数据类型与默认表达式
         let mut columns = Vec::new();
         for (i, (_name, type_str)) in column_names
@@ -66,6 +89,9 @@ impl CsvParser {
             .zip(data_types.iter())
             .enumerate()
         {
+            if Some(i) == timestamp_column {
+                continue;
+            }
             let (data_type, default_expr) =
                 CsvDataType::parse_type_and_default(
                     type_str,
@@ -85,6 +111,7 @@ impl CsvParser {
 
         // 读取数据行
         let mut data_rows = Vec::new();
+        let mut timestamps = Vec::new();
         for (line_num, line) in lines.enumerate() {
             let line = line.map_err(|e| {
                 AppError::config(format!(
@@ -93,27 +120,46 @@ impl CsvParser {
                     e
                 ))
             })?;
-            let row = Self::parse_csv_line(&line);
+            let mut row = Self::parse_csv_line(&line);
 
-            if row.len() != columns.len() {
+            if row.len() != column_names.len() {
                 return Err(AppError::validation(
                     format!("CSV Line {}", line_num + 2),
                     format!(
                         "Expected {} columns, got {}",
-                        columns.len(),
+                        column_names.len(),
                         row.len()
                     ),
                 ));
             }
 
+            if let Some(index) = timestamp_column {
+                let cell = row.remove(index);
+                let timestamp = parse_timestamp(&cell)
+                    .map_err(|e| {
+                        AppError::validation(
+                            format!(
+                                "CSV Line {}",
+                                line_num + 2
+                            ),
+                            e,
+                        )
+                    })?;
+                timestamps.push(timestamp);
+            }
+
             data_rows.push(row);
         }
 
-        Ok(Self { columns, data_rows })
+        Ok(Self {
+            columns,
+            data_rows,
+            timestamps,
+        })
     }
 
     /// 解析CSV行
-    fn parse_csv_line(line: &str) -> Vec<String> {
+    pub fn parse_csv_line(line: &str) -> Vec<String> {
         let mut result = Vec::new();
         let mut current = String::new();
         let mut in_quotes = false;
@@ -148,6 +194,19 @@ impl CsvParser {
         result
     }
 
+    /// 各列的数据类型（按数据包中的字段顺序）
+    pub fn column_types(&self) -> Vec<CsvDataType> {
+        self.columns
+            .iter()
+            .map(|column| column.data_type.clone())
+            .collect()
+    }
+
+    /// 是否带有时间戳列
+    pub fn has_timestamps(&self) -> bool {
+        !self.timestamps.is_empty()
+    }
+
     /// 获取数据行数
     pub fn row_count(&self) -> usize {
         self.data_rows.len()
@@ -181,8 +240,12 @@ impl CsvParser {
             packet_data.extend_from_slice(&bytes);
         }
 
-        // 使用当前时间作为时间戳
-        let timestamp = Utc::now();
+        // 优先使用时间戳列，否则使用当前时间
+        let timestamp = self
+            .timestamps
+            .get(row_index)
+            .copied()
+            .unwrap_or_else(Utc::now);
 
         Ok(CsvPacket {
             data: packet_data,
@@ -218,3 +281,39 @@ impl CsvParser {
         Ok(data_type.default_value())
     }
 }
+
+/// 发送周期的上限（毫秒），更长的周期无法以纳秒推算各行时间
+pub const MAX_CSV_PACKET_INTERVAL_MS: u64 =
+    i64::MAX as u64 / 1_000_000;
+
+/// 按发送周期推算第 `row_index` 行（从 0 开始）的时间
+///
+/// 时间超出可表示范围时返回验证错误。
+pub fn row_time(
+    start: DateTime<Utc>,
+    interval: Duration,
+    row_index: usize,
+) -> Result<DateTime<Utc>> {
+    // 首行即起始时间，与周期无关
+    if row_index == 0 {
+        return Ok(start);
+    }
+    interval
+        .num_nanoseconds()
+        .zip(i64::try_from(row_index).ok())
+        .and_then(|(nanos, index)| nanos.checked_mul(index))
+        .and_then(|offset| {
+            start.checked_add_signed(Duration::nanoseconds(
+                offset,
+            ))
+        })
+        .ok_or_else(|| {
+            AppError::validation(
+                "Interval",
+                format!(
+                    "Time of CSV row {} is out of range",
+                    row_index + 1
+                ),
+            )
+        })
+}
@@ -115,6 +115,23 @@ impl CsvDataType {
         Ok((final_ty, default_expr))
     }
 
+    /// 编码后的字节数
+    pub fn size(&self) -> usize {
+        match self {
+            CsvDataType::I8
+            | CsvDataType::U8
+            | CsvDataType::Bool => 1,
+            CsvDataType::I16 | CsvDataType::U16 => 2,
+            CsvDataType::I32
+            | CsvDataType::U32
+            | CsvDataType::F32 => 4,
+            CsvDataType::I64
+            | CsvDataType::U64
+            | CsvDataType::F64 => 8,
+            CsvDataType::HexDynamic(size) => *size,
+        }
+    }
+
     /// 获取默认值
     pub fn default_value(&self) -> Vec<u8> {
         match self {
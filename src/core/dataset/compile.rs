@@ -0,0 +1,291 @@
+//! CSV 编译模块
+//!
+//! 离线把 CSV 场景文件编译为 pcapfile-io 数据集，便于版本管理、
+//! 精确重放与比对。每行的时间戳取自时间戳列；没有时间戳列时从
+//! 起始时间开始按固定间隔递增。
+
+use chrono::{DateTime, Duration, Utc};
+use pcapfile_io::{DataPacket, PcapWriter, WriterConfig};
+use std::path::Path;
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::csv::parser::row_time;
+use crate::core::csv::CsvParser;
+
+/// 无时间戳列时的计时方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompileTiming {
+    /// 第一行的时间戳
+    pub start: DateTime<Utc>,
+    /// 相邻两行的时间间隔
+    pub interval: Duration,
+}
+
+impl Default for CompileTiming {
+    fn default() -> Self {
+        // 固定的起始时间保证同一 CSV 每次编译得到相同的数据集
+        Self {
+            start: DateTime::UNIX_EPOCH,
+            interval: Duration::seconds(1),
+        }
+    }
+}
+
+/// 编译结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompileSummary {
+    /// 写入的数据包数
+    pub packets: u64,
+    /// 写入的负载字节数
+    pub bytes: u64,
+    /// 第一个与最后一个数据包的时间戳
+    pub span: Option<(DateTime<Utc>, DateTime<Utc>)>,
+}
+
+/// 把 `parser` 中的所有行写入 `output_dir` 下名为 `dataset_name` 的数据集
+///
+/// 时间戳列中的时间不能倒退；目标数据集已存在时返回错误。
+pub fn compile_csv(
+    parser: &CsvParser,
+    output_dir: &Path,
+    dataset_name: &str,
+    timing: &CompileTiming,
+) -> Result<CompileSummary> {
+    // 有时间戳列时不使用间隔
+    if !parser.has_timestamps()
+        && timing.interval <= Duration::zero()
+    {
+        return Err(DataTransferError::validation(
+            "Interval",
+            "Interval must be greater than 0",
+        ));
+    }
+    let dataset_path = output_dir.join(dataset_name);
+    if dataset_path.exists() {
+        return Err(DataTransferError::validation(
+            "Dataset",
+            format!(
+                "Dataset already exists: {}",
+                dataset_path.display()
+            ),
+        ));
+    }
+
+    std::fs::create_dir_all(output_dir)?;
+    let result = write_dataset(
+        parser,
+        output_dir,
+        dataset_name,
+        timing,
+    );
+    // 编译失败时不留下不完整的数据集
+    if result.is_err() {
+        let _ = std::fs::remove_dir_all(&dataset_path);
+    }
+    result
+}
+
+/// 逐行生成数据包并写入数据集
+fn write_dataset(
+    parser: &CsvParser,
+    output_dir: &Path,
+    dataset_name: &str,
+    timing: &CompileTiming,
+) -> Result<CompileSummary> {
+    let mut writer = PcapWriter::new_with_config(
+        output_dir,
+        dataset_name,
+        WriterConfig::default(),
+    )?;
+
+    let mut summary = CompileSummary {
+        packets: 0,
+        bytes: 0,
+        span: None,
+    };
+    for row_index in 0..parser.row_count() {
+        let packet = parser.generate_packet(row_index)?;
+        let timestamp = if parser.has_timestamps() {
+            packet.timestamp
+        } else {
+            row_time(
+                timing.start,
+                timing.interval,
+                row_index,
+            )?
+        };
+
+        if let Some((_, last)) = summary.span {
+            if timestamp < last {
+                return Err(DataTransferError::validation(
+                    format!("CSV Row {}", row_index + 1),
+                    format!(
+                        "Timestamp {} is earlier than the previous row ({})",
+                        timestamp.to_rfc3339(),
+                        last.to_rfc3339()
+                    ),
+                ));
+            }
+        }
+
+        summary.bytes += packet.data.len() as u64;
+        let packet = DataPacket::from_datetime(
+            timestamp,
+            packet.data,
+        )
+        .map_err(|e| {
+            DataTransferError::config(format!(
+                "Failed to create packet: {e}"
+            ))
+        })?;
+        writer.write_packet(&packet)?;
+        summary.packets += 1;
+        summary.span = Some((
+            summary
+                .span
+                .map_or(timestamp, |(first, _)| first),
+            timestamp,
+        ));
+    }
+    writer.finalize()?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pcapfile_io::{PcapReader, ReaderConfig};
+
+    fn temp_dir(name: &str) -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "pcap_transfer_compile_{name}_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn read_dataset(
+        dir: &Path,
+        name: &str,
+    ) -> Vec<(i64, Vec<u8>)> {
+        let mut reader = PcapReader::new_with_config(
+            dir,
+            name,
+            ReaderConfig::default(),
+        )
+        .unwrap();
+        let mut packets = Vec::new();
+        while let Some(packet) =
+            reader.read_packet().unwrap()
+        {
+            packets.push((
+                packet
+                    .capture_time()
+                    .timestamp_nanos_opt()
+                    .unwrap(),
+                packet.packet.data,
+            ));
+        }
+        packets
+    }
+
+    #[test]
+    fn test_compile_with_interval_and_timestamp_column() {
+        let dir = temp_dir("timing");
+
+        let csv = dir.join("interval.csv");
+        std::fs::write(
+            &csv,
+            "id,value\nu8,u16\n1,258\n2,3\n",
+        )
+        .unwrap();
+        let parser = CsvParser::from_file(&csv).unwrap();
+        let timing = CompileTiming {
+            start: DateTime::from_timestamp_nanos(
+                1_700_000_000_000_000_000,
+            ),
+            interval: Duration::milliseconds(250),
+        };
+        let summary =
+            compile_csv(&parser, &dir, "interval", &timing)
+                .unwrap();
+        assert_eq!(
+            (summary.packets, summary.bytes),
+            (2, 6)
+        );
+        assert_eq!(
+            read_dataset(&dir, "interval"),
+            vec![
+                (1_700_000_000_000_000_000, vec![1, 2, 1]),
+                (1_700_000_000_250_000_000, vec![2, 3, 0]),
+            ]
+        );
+        // 已存在的数据集不会被覆盖
+        assert!(compile_csv(
+            &parser, &dir, "interval", &timing
+        )
+        .is_err());
+
+        // 推算的时间超出范围时报错，不留下数据集
+        let overflow = CompileTiming {
+            interval: Duration::MAX,
+            ..timing
+        };
+        assert!(compile_csv(
+            &parser, &dir, "overflow", &overflow
+        )
+        .is_err());
+        assert!(!dir.join("overflow").exists());
+        // 首行时间与周期无关，不会溢出
+        assert_eq!(
+            row_time(timing.start, Duration::MAX, 0)
+                .unwrap(),
+            timing.start
+        );
+
+        // 时间戳列不编码进数据包
+        let csv = dir.join("column.csv");
+        std::fs::write(
+            &csv,
+            "time,id\ntimestamp,u8\n2023-11-14T22:13:20.000000001Z,1\n1700000000.5,2\n",
+        )
+        .unwrap();
+        let parser = CsvParser::from_file(&csv).unwrap();
+        compile_csv(
+            &parser,
+            &dir,
+            "column",
+            &CompileTiming::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            read_dataset(&dir, "column"),
+            vec![
+                (1_700_000_000_000_000_001, vec![1]),
+                (1_700_000_000_500_000_000, vec![2]),
+            ]
+        );
+
+        // 时间倒退的行被拒绝
+        let csv = dir.join("backwards.csv");
+        std::fs::write(
+            &csv,
+            "time,id\ntimestamp,u8\n1700000001,1\n1700000000,2\n",
+        )
+        .unwrap();
+        let parser = CsvParser::from_file(&csv).unwrap();
+        assert!(compile_csv(
+            &parser,
+            &dir,
+            "backwards",
+            &CompileTiming::default(),
+        )
+        .is_err());
+        assert!(!dir.join("backwards").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,8 @@
+//! 数据集模块
+//!
+//! 负责接收数据集的命名、轮转、写入以及数据包元数据的记录，
+//! 以及把 CSV 场景离线编译为数据集
+
+pub mod compile;
+pub mod rotation;
+pub mod sidecar;
@@ -0,0 +1,549 @@
+//! 数据集轮转模块
+//!
+//! 按时间、包数或字节数将长时间捕获拆分为多个数据集，
+//! 轮转后的数据集按名称模板命名。
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use pcapfile_io::{DataPacket, PcapWriter, WriterConfig};
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+use crate::app::config::options::{
+    RotationMode, RotationOptions, WriterOptions,
+};
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::dataset::sidecar::{
+    PacketMeta, SidecarWriter,
+};
+
+/// 根据写入器选项构造 pcap 写入配置
+pub fn writer_config(
+    options: &WriterOptions,
+) -> WriterConfig {
+    WriterConfig {
+        buffer_size: options.buffer_size,
+        index_cache_size: options.index_cache_size,
+        max_packets_per_file: options.max_packets_per_file,
+        auto_flush: options.auto_flush,
+        ..Default::default()
+    }
+}
+
+/// 时间占位符中的字段及对应的 strftime 格式
+const TIME_FIELDS: [(&str, &str); 6] = [
+    ("yyyy", "%Y"),
+    ("MM", "%m"),
+    ("dd", "%d"),
+    ("HH", "%H"),
+    ("mm", "%M"),
+    ("ss", "%S"),
+];
+
+/// 把时间占位符转换为 strftime 格式
+///
+/// 字段之外的字符原样保留（`%` 转义为 `%%`），出现字段以外的
+/// 字母时视为未知占位符。
+fn time_format(
+    pattern: &str,
+) -> std::result::Result<String, String> {
+    let mut format =
+        String::with_capacity(pattern.len() * 2);
+    let mut rest = pattern;
+    'scan: while let Some(ch) = rest.chars().next() {
+        for (field, spec) in TIME_FIELDS {
+            if let Some(tail) = rest.strip_prefix(field) {
+                format.push_str(spec);
+                rest = tail;
+                continue 'scan;
+            }
+        }
+        if ch.is_ascii_alphabetic() {
+            return Err(format!(
+                "Unknown placeholder {{{pattern}}}"
+            ));
+        }
+        if ch == '%' {
+            format.push('%');
+        }
+        format.push(ch);
+        rest = &rest[ch.len_utf8()..];
+    }
+
+    // 转义后不应再有非法格式项，这里再确认一次以免格式化时崩溃
+    if StrftimeItems::new(&format)
+        .any(|item| item == Item::Error)
+    {
+        return Err(format!(
+            "Invalid time placeholder {{{pattern}}}"
+        ));
+    }
+    Ok(format)
+}
+
+/// 把模板拆分为字面文本与占位符
+fn template_parts(
+    template: &str,
+) -> std::result::Result<Vec<(bool, &str)>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err(
+                "Unclosed '{' in name template".to_string()
+            );
+        };
+        parts.push((false, &rest[..start]));
+        parts.push((true, &rest[start + 1..start + end]));
+        rest = &rest[start + end + 1..];
+    }
+    parts.push((false, rest));
+    Ok(parts)
+}
+
+/// 检查名称模板中的全部占位符
+pub fn validate_name_template(
+    template: &str,
+) -> std::result::Result<(), String> {
+    for (placeholder, text) in template_parts(template)? {
+        if placeholder && !matches!(text, "name" | "seq") {
+            time_format(text)?;
+        }
+    }
+    Ok(())
+}
+
+/// 按模板生成数据集名称
+///
+/// 支持 `{name}`、`{seq}`（四位序号）以及由 `yyyy`、`MM`、`dd`、
+/// `HH`、`mm`、`ss` 组成的时间占位符（UTC），例如 `{yyyyMMdd_HHmmss}`。
+/// 无法识别的占位符与未闭合的 `{` 按原样保留。
+pub fn render_dataset_name(
+    template: &str,
+    name: &str,
+    time: DateTime<Utc>,
+    seq: u32,
+) -> String {
+    let Ok(parts) = template_parts(template) else {
+        return template.to_string();
+    };
+
+    let mut result = String::with_capacity(template.len());
+    for (placeholder, text) in parts {
+        match (placeholder, text) {
+            (false, text) => result.push_str(text),
+            (true, "name") => result.push_str(name),
+            (true, "seq") => {
+                result.push_str(&format!("{seq:04}"))
+            }
+            (true, pattern) => match time_format(pattern) {
+                Ok(format) => result.push_str(
+                    &time.format(&format).to_string(),
+                ),
+                Err(_) => {
+                    result.push('{');
+                    result.push_str(pattern);
+                    result.push('}');
+                }
+            },
+        }
+    }
+    result
+}
+
+/// 支持轮转的数据集写入器
+pub struct RotatingWriter {
+    output_path: PathBuf,
+    base_name: String,
+    config: WriterConfig,
+    rotation: RotationOptions,
+    writer: PcapWriter,
+    sidecar: SidecarWriter,
+    dataset_name: String,
+    seq: u32,
+    started_at: DateTime<Utc>,
+    packets: u64,
+    bytes: u64,
+}
+
+impl RotatingWriter {
+    /// 创建写入器并打开第一个数据集
+    pub fn new(
+        output_path: &Path,
+        base_name: &str,
+        writer: &WriterOptions,
+        rotation: RotationOptions,
+    ) -> Result<Self> {
+        let config = writer_config(writer);
+        config.validate().map_err(|e| {
+            DataTransferError::config(format!(
+                "Invalid writer options: {e}"
+            ))
+        })?;
+
+        let started_at = Utc::now();
+        let dataset_name = Self::dataset_name_for(
+            &rotation, base_name, started_at, 1,
+        );
+        let writer = PcapWriter::new_with_config(
+            output_path,
+            &dataset_name,
+            config.clone(),
+        )?;
+        let sidecar = SidecarWriter::create(
+            &output_path.join(&dataset_name),
+        )?;
+
+        Ok(Self {
+            output_path: output_path.to_path_buf(),
+            base_name: base_name.to_string(),
+            config,
+            rotation,
+            writer,
+            sidecar,
+            dataset_name,
+            seq: 1,
+            started_at,
+            packets: 0,
+            bytes: 0,
+        })
+    }
+
+    /// 当前数据集名称
+    pub fn dataset_name(&self) -> &str {
+        &self.dataset_name
+    }
+
+    /// 当前数据集序号（从 1 开始）
+    pub fn seq(&self) -> u32 {
+        self.seq
+    }
+
+    /// 写入数据包，必要时先轮转到新的数据集
+    ///
+    /// 返回 `true` 表示本次写入前发生了轮转。
+    pub fn write_packet(
+        &mut self,
+        packet: &DataPacket,
+        capture_time: DateTime<Utc>,
+        meta: &PacketMeta,
+    ) -> Result<bool> {
+        let rotated = self.should_rotate(
+            capture_time,
+            packet.packet_length() as u64,
+        );
+        if rotated {
+            self.rotate(capture_time)?;
+        }
+        // 轮转时刻按数据集首包的捕获时间计算：捕获时间可能来自网卡时钟，
+        // 不能与系统时钟混用
+        if self.packets == 0 {
+            self.started_at = capture_time;
+        }
+
+        self.writer.write_packet(packet)?;
+        self.sidecar
+            .append(packet.get_timestamp_ns(), meta)?;
+        self.packets += 1;
+        self.bytes += packet.packet_length() as u64;
+        Ok(rotated)
+    }
+
+    /// 完成当前数据集的写入
+    pub fn finalize(mut self) -> Result<()> {
+        self.writer.finalize()?;
+        self.sidecar.finish()?;
+        Ok(())
+    }
+
+    /// 无轮转时沿用原名称，否则按模板生成
+    fn dataset_name_for(
+        rotation: &RotationOptions,
+        base_name: &str,
+        time: DateTime<Utc>,
+        seq: u32,
+    ) -> String {
+        if rotation.mode == RotationMode::None {
+            base_name.to_string()
+        } else {
+            render_dataset_name(
+                &rotation.name_template,
+                base_name,
+                time,
+                seq,
+            )
+        }
+    }
+
+    /// 判断写入下一个数据包前是否需要轮转
+    fn should_rotate(
+        &self,
+        capture_time: DateTime<Utc>,
+        incoming_bytes: u64,
+    ) -> bool {
+        // 空数据集不轮转，避免产生空文件
+        if self.packets == 0 {
+            return false;
+        }
+
+        let interval = self.rotation.interval;
+        match self.rotation.mode {
+            RotationMode::None => false,
+            RotationMode::Minutes => {
+                interval > 0
+                    && capture_time - self.started_at
+                        >= Duration::minutes(
+                            interval as i64,
+                        )
+            }
+            RotationMode::Packets => {
+                interval > 0 && self.packets >= interval
+            }
+            RotationMode::Bytes => {
+                interval > 0
+                    && self.bytes + incoming_bytes
+                        > interval
+            }
+            RotationMode::Hourly => {
+                hour_start(capture_time)
+                    != hour_start(self.started_at)
+            }
+        }
+    }
+
+    /// 完成当前数据集并打开下一个
+    fn rotate(
+        &mut self,
+        capture_time: DateTime<Utc>,
+    ) -> Result<()> {
+        let seq = self.seq + 1;
+        let dataset_name = Self::dataset_name_for(
+            &self.rotation,
+            &self.base_name,
+            capture_time,
+            seq,
+        );
+        let next = PcapWriter::new_with_config(
+            &self.output_path,
+            &dataset_name,
+            self.config.clone(),
+        )?;
+        let next_sidecar = SidecarWriter::create(
+            &self.output_path.join(&dataset_name),
+        )?;
+
+        let mut previous =
+            std::mem::replace(&mut self.writer, next);
+        previous.finalize()?;
+        std::mem::replace(&mut self.sidecar, next_sidecar)
+            .finish()?;
+        info!(
+            "Rotated dataset {} -> {}",
+            self.dataset_name, dataset_name
+        );
+
+        self.dataset_name = dataset_name;
+        self.seq = seq;
+        self.started_at = capture_time;
+        self.packets = 0;
+        self.bytes = 0;
+        Ok(())
+    }
+}
+
+/// 时间所在整点
+fn hour_start(time: DateTime<Utc>) -> DateTime<Utc> {
+    time.with_minute(0)
+        .and_then(|t| t.with_second(0))
+        .and_then(|t| t.with_nanosecond(0))
+        .unwrap_or(time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_dataset_name() {
+        let time = Utc
+            .with_ymd_and_hms(2024, 3, 9, 14, 5, 7)
+            .unwrap();
+        assert_eq!(
+            render_dataset_name(
+                "{name}_{yyyyMMdd_HHmmss}_{seq}",
+                "capture",
+                time,
+                3
+            ),
+            "capture_20240309_140507_0003"
+        );
+        assert_eq!(
+            render_dataset_name(
+                "{name}-{HH}h",
+                "x",
+                time,
+                1
+            ),
+            "x-14h"
+        );
+    }
+
+    #[test]
+    fn test_name_template_escapes_literals() {
+        let time = Utc
+            .with_ymd_and_hms(2024, 3, 9, 14, 5, 7)
+            .unwrap();
+        // `%` 按字面输出，相邻字段分别替换
+        assert_eq!(
+            render_dataset_name(
+                "{name}_{100%}_{MMmm}_{seq}",
+                "x",
+                time,
+                2
+            ),
+            "x_100%_0305_0002"
+        );
+        assert!(validate_name_template(
+            "{name}_{100%}_{seq}"
+        )
+        .is_ok());
+
+        // 未知占位符与未闭合的括号原样保留，并在校验时报错
+        assert_eq!(
+            render_dataset_name(
+                "{name}_{foo}_{seq}",
+                "x",
+                time,
+                1
+            ),
+            "x_{foo}_0001"
+        );
+        assert!(validate_name_template(
+            "{name}_{foo}_{seq}"
+        )
+        .is_err());
+        assert_eq!(
+            render_dataset_name(
+                "{name}_{seq",
+                "x",
+                time,
+                1
+            ),
+            "{name}_{seq"
+        );
+        assert!(
+            validate_name_template("{name}_{seq").is_err()
+        );
+    }
+
+    #[test]
+    fn test_rotation_by_packets() {
+        let output = std::env::temp_dir().join(format!(
+            "pcap_transfer_rotation_{}",
+            std::process::id()
+        ));
+        let mut writer = RotatingWriter::new(
+            &output,
+            "capture",
+            &WriterOptions::default(),
+            RotationOptions {
+                mode: RotationMode::Packets,
+                interval: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let mut rotations = 0;
+        for i in 0u8..5 {
+            let time = Utc::now();
+            let packet =
+                DataPacket::from_datetime(time, vec![i; 4])
+                    .unwrap();
+            if writer
+                .write_packet(
+                    &packet,
+                    time,
+                    &PacketMeta::default(),
+                )
+                .unwrap()
+            {
+                rotations += 1;
+            }
+        }
+        assert_eq!(rotations, 2);
+        assert_eq!(writer.seq(), 3);
+        assert!(writer
+            .dataset_name()
+            .starts_with("capture_"));
+        writer.finalize().unwrap();
+
+        let datasets = std::fs::read_dir(&output)
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .count();
+        assert_eq!(datasets, 3);
+        let _ = std::fs::remove_dir_all(&output);
+    }
+
+    #[test]
+    fn test_minutes_rotation_uses_capture_clock() {
+        let output = std::env::temp_dir().join(format!(
+            "pcap_transfer_rotation_clock_{}",
+            std::process::id()
+        ));
+        // 捕获时钟领先或落后系统时钟时，按捕获时间的间隔轮转
+        for offset in
+            [Duration::hours(1), -Duration::days(1)]
+        {
+            let mut writer = RotatingWriter::new(
+                &output,
+                "capture",
+                &WriterOptions::default(),
+                RotationOptions {
+                    mode: RotationMode::Minutes,
+                    interval: 1,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let base = Utc::now() + offset;
+            let rotated: Vec<bool> = [0, 20, 40, 61]
+                .into_iter()
+                .map(|secs| {
+                    let time =
+                        base + Duration::seconds(secs);
+                    let packet = DataPacket::from_datetime(
+                        time,
+                        vec![0; 4],
+                    )
+                    .unwrap();
+                    writer
+                        .write_packet(
+                            &packet,
+                            time,
+                            &PacketMeta::default(),
+                        )
+                        .unwrap()
+                })
+                .collect();
+            assert_eq!(
+                rotated,
+                vec![false, false, false, true]
+            );
+            writer.finalize().unwrap();
+            let _ = std::fs::remove_dir_all(&output);
+        }
+    }
+
+    #[test]
+    fn test_hourly_rotation_boundary() {
+        let before = Utc
+            .with_ymd_and_hms(2024, 1, 1, 9, 59, 59)
+            .unwrap();
+        let after = before + Duration::seconds(2);
+        assert_ne!(hour_start(before), hour_start(after));
+    }
+}
@@ -0,0 +1,297 @@
+//! 数据包元数据旁路文件模块
+//!
+//! pcapfile-io 数据集只保存时间戳与负载，来源地址等信息写入
+//! 数据集目录下的 `packet_meta.csv`，每行对应数据集中的一个数据包，
+//! 顺序与数据集读取顺序一致。
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::app::error::types::{DataTransferError, Result};
+
+/// 元数据文件名
+pub const SIDECAR_FILE_NAME: &str = "packet_meta.csv";
+
+/// 元数据文件表头
+const SIDECAR_HEADER: &str =
+    "index,timestamp_ns,source,local_port,interface,endpoint";
+
+/// 接收时记录的数据包元数据
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PacketMeta {
+    /// 发送方地址
+    pub source: Option<SocketAddr>,
+    /// 接收端口
+    pub local_port: u16,
+    /// 接收网络接口
+    pub interface: Option<Arc<str>>,
+    /// 接收端点（监听地址与端口）
+    pub endpoint: Option<SocketAddr>,
+}
+
+/// 元数据文件中的一条记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct SidecarRecord {
+    /// 数据包在数据集中的序号（从 0 开始）
+    pub index: u64,
+    /// 数据包时间戳（纳秒）
+    pub timestamp_ns: u64,
+    /// 数据包元数据
+    pub meta: PacketMeta,
+}
+
+/// 元数据文件写入器
+pub struct SidecarWriter {
+    writer: BufWriter<File>,
+    next_index: u64,
+}
+
+impl SidecarWriter {
+    /// 在数据集目录中创建元数据文件
+    pub fn create(dataset_dir: &Path) -> Result<Self> {
+        let file = File::create(
+            dataset_dir.join(SIDECAR_FILE_NAME),
+        )?;
+        let mut writer = BufWriter::new(file);
+        writeln!(writer, "{SIDECAR_HEADER}")?;
+        Ok(Self {
+            writer,
+            next_index: 0,
+        })
+    }
+
+    /// 追加一条记录
+    pub fn append(
+        &mut self,
+        timestamp_ns: u64,
+        meta: &PacketMeta,
+    ) -> Result<()> {
+        let source = meta
+            .source
+            .map(|source| source.to_string())
+            .unwrap_or_default();
+        // 接口名中的逗号会破坏列格式
+        let interface = meta
+            .interface
+            .as_deref()
+            .unwrap_or("")
+            .replace(',', "_");
+        let endpoint = meta
+            .endpoint
+            .map(|endpoint| endpoint.to_string())
+            .unwrap_or_default();
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{}",
+            self.next_index,
+            timestamp_ns,
+            source,
+            meta.local_port,
+            interface,
+            endpoint
+        )?;
+        self.next_index += 1;
+        Ok(())
+    }
+
+    /// 刷新缓冲区
+    pub fn finish(mut self) -> Result<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// 元数据文件读取器
+pub struct SidecarReader {
+    lines: std::io::Lines<BufReader<File>>,
+}
+
+impl SidecarReader {
+    /// 打开数据集目录中的元数据文件，文件不存在时返回 `None`
+    pub fn open(
+        dataset_dir: &Path,
+    ) -> Result<Option<Self>> {
+        let path = dataset_dir.join(SIDECAR_FILE_NAME);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let mut lines =
+            BufReader::new(File::open(path)?).lines();
+        // 跳过表头
+        lines.next().transpose()?;
+        Ok(Some(Self { lines }))
+    }
+
+    /// 读取下一条记录
+    pub fn next_record(
+        &mut self,
+    ) -> Result<Option<SidecarRecord>> {
+        match self.lines.next() {
+            Some(line) => parse_record(&line?).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// 解析一行元数据记录
+fn parse_record(line: &str) -> Result<SidecarRecord> {
+    let invalid = || {
+        DataTransferError::validation(
+            SIDECAR_FILE_NAME,
+            format!("Invalid record: {line}"),
+        )
+    };
+
+    // 早期版本的元数据文件没有 endpoint 列
+    let fields: Vec<&str> = line.split(',').collect();
+    if fields.len() != 5 && fields.len() != 6 {
+        return Err(invalid());
+    }
+
+    let parse_addr = |field: Option<&&str>| match field {
+        Some(value) if !value.is_empty() => {
+            value.parse().map(Some).map_err(|_| invalid())
+        }
+        _ => Ok(None),
+    };
+    let source = parse_addr(fields.get(2))?;
+    let endpoint = parse_addr(fields.get(5))?;
+    let interface = (!fields[4].is_empty())
+        .then(|| Arc::from(fields[4]));
+
+    Ok(SidecarRecord {
+        index: fields[0].parse().map_err(|_| invalid())?,
+        timestamp_ns: fields[1]
+            .parse()
+            .map_err(|_| invalid())?,
+        meta: PacketMeta {
+            source,
+            local_port: fields[3]
+                .parse()
+                .map_err(|_| invalid())?,
+            interface,
+            endpoint,
+        },
+    })
+}
+
+/// 按发送方地址过滤数据包（`IP` 或 `IP:端口`）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SourceFilter {
+    /// 匹配该 IP 的任意端口
+    Ip(IpAddr),
+    /// 匹配该 IP 与端口
+    Addr(SocketAddr),
+}
+
+impl SourceFilter {
+    /// 判断元数据是否匹配
+    pub fn matches(&self, meta: &PacketMeta) -> bool {
+        match (self, meta.source) {
+            (SourceFilter::Ip(ip), Some(source)) => {
+                source.ip() == *ip
+            }
+            (SourceFilter::Addr(addr), Some(source)) => {
+                source == *addr
+            }
+            (_, None) => false,
+        }
+    }
+}
+
+impl FromStr for SourceFilter {
+    type Err = DataTransferError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        if let Ok(addr) = value.parse() {
+            return Ok(SourceFilter::Addr(addr));
+        }
+        value.parse().map(SourceFilter::Ip).map_err(|_| {
+            DataTransferError::validation(
+                "Source Filter",
+                format!("Invalid address: {value}"),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_round_trip() {
+        let dir = std::env::temp_dir().join(format!(
+            "pcap_transfer_sidecar_{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let first = PacketMeta {
+            source: Some("10.0.0.1:5000".parse().unwrap()),
+            local_port: 9000,
+            interface: Some(Arc::from("eth0")),
+            endpoint: Some(
+                "239.1.1.1:9000".parse().unwrap(),
+            ),
+        };
+        let second = PacketMeta {
+            source: None,
+            local_port: 9001,
+            interface: None,
+            endpoint: None,
+        };
+
+        let mut writer =
+            SidecarWriter::create(&dir).unwrap();
+        writer.append(100, &first).unwrap();
+        writer.append(200, &second).unwrap();
+        writer.finish().unwrap();
+
+        let mut reader =
+            SidecarReader::open(&dir).unwrap().unwrap();
+        let record = reader.next_record().unwrap().unwrap();
+        assert_eq!(record.index, 0);
+        assert_eq!(record.timestamp_ns, 100);
+        assert_eq!(record.meta, first);
+        let record = reader.next_record().unwrap().unwrap();
+        assert_eq!(record.index, 1);
+        assert_eq!(record.meta, second);
+        assert!(reader.next_record().unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_parse_record_without_endpoint() {
+        let record =
+            parse_record("3,42,10.0.0.1:5000,9000,")
+                .unwrap();
+        assert_eq!(record.index, 3);
+        assert_eq!(record.meta.local_port, 9000);
+        assert!(record.meta.endpoint.is_none());
+        assert!(parse_record("1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_source_filter() {
+        let meta = PacketMeta {
+            source: Some("10.0.0.1:5000".parse().unwrap()),
+            ..Default::default()
+        };
+        let ip: SourceFilter = "10.0.0.1".parse().unwrap();
+        let addr: SourceFilter =
+            "10.0.0.1:5001".parse().unwrap();
+        assert!(ip.matches(&meta));
+        assert!(!addr.matches(&meta));
+        assert!("not-an-ip"
+            .parse::<SourceFilter>()
+            .is_err());
+    }
+}
@@ -1,7 +1,11 @@
 //! 核心业务逻辑模块
 
+pub mod capture;
 pub mod csv;
+pub mod dataset;
 pub mod network;
+pub mod pipeline;
+pub mod probe;
 pub mod services;
 pub mod stats;
 pub mod timing;
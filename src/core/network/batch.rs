@@ -0,0 +1,340 @@
+//! 批量收发模块
+//!
+//! 在 Linux 上使用 sendmmsg/recvmmsg 减少系统调用次数，
+//! 其他平台或批量大小为 1 时退化为逐包收发。
+//! 需要内核时间戳等控制消息时，Linux 上始终使用 recvmmsg 接收。
+
+use std::io;
+use std::net::SocketAddr;
+use tokio::net::UdpSocket as TokioUdpSocket;
+use tracing::error;
+
+use crate::core::network::sys::RecvMeta;
+
+/// 批量发送结果
+#[derive(Debug, Default, Clone)]
+pub struct SendBatchResult {
+    /// 成功发送的数据报数量
+    pub sent: usize,
+    /// 成功发送的字节数
+    pub bytes: usize,
+    /// 发送失败的数据报在批次中的下标（升序）
+    pub failed: Vec<usize>,
+}
+
+impl SendBatchResult {
+    /// 发送失败的数据报数量
+    pub fn errors(&self) -> usize {
+        self.failed.len()
+    }
+
+    /// 按批次顺序返回成功发送的条目
+    ///
+    /// 失败的数据报不一定位于批次末尾，`items` 与发送的批次一一对应。
+    pub fn delivered<'a, T>(
+        &'a self,
+        items: &'a [T],
+    ) -> impl Iterator<Item = &'a T> + 'a {
+        items.iter().enumerate().filter_map(|(i, item)| {
+            self.failed
+                .binary_search(&i)
+                .is_err()
+                .then_some(item)
+        })
+    }
+}
+
+/// 批量发送一组数据报到同一目标地址
+///
+/// 单个数据报发送失败时记录错误并跳过，不会中断整批发送。
+pub async fn send_batch(
+    socket: &TokioUdpSocket,
+    target: SocketAddr,
+    payloads: &[&[u8]],
+) -> SendBatchResult {
+    #[cfg(target_os = "linux")]
+    if payloads.len() > 1 {
+        return send_batch_mmsg(socket, target, payloads)
+            .await;
+    }
+
+    let mut result = SendBatchResult::default();
+    for (index, payload) in payloads.iter().enumerate() {
+        match socket.send_to(payload, target).await {
+            Ok(bytes_sent) => {
+                result.sent += 1;
+                result.bytes += bytes_sent;
+            }
+            Err(e) => {
+                error!("Failed to send packet: {}", e);
+                result.failed.push(index);
+            }
+        }
+    }
+    result
+}
+
+/// 基于 sendmmsg 的批量发送实现
+#[cfg(target_os = "linux")]
+async fn send_batch_mmsg(
+    socket: &TokioUdpSocket,
+    target: SocketAddr,
+    payloads: &[&[u8]],
+) -> SendBatchResult {
+    use std::os::fd::AsRawFd;
+    use tokio::io::Interest;
+
+    let fd = socket.as_raw_fd();
+    let mut result = SendBatchResult::default();
+    let mut offset = 0;
+
+    while offset < payloads.len() {
+        if let Err(e) = socket.writable().await {
+            error!("Failed to wait for socket: {}", e);
+            result.failed.extend(offset..payloads.len());
+            break;
+        }
+
+        let remaining = &payloads[offset..];
+        match socket.try_io(Interest::WRITABLE, || {
+            crate::core::network::sys::sendmmsg(
+                fd, &target, remaining,
+            )
+        }) {
+            Ok(sent) => {
+                result.sent += sent;
+                result.bytes += remaining[..sent]
+                    .iter()
+                    .map(|payload| payload.len())
+                    .sum::<usize>();
+                offset += sent;
+            }
+            Err(e)
+                if e.kind()
+                    == io::ErrorKind::WouldBlock =>
+            {
+                continue;
+            }
+            Err(e) => {
+                // 首个数据报发送失败，跳过后继续发送剩余数据报
+                error!("Failed to send packet: {}", e);
+                result.failed.push(offset);
+                offset += 1;
+            }
+        }
+    }
+
+    result
+}
+
+/// 批量接收缓冲区
+pub struct RecvBatch {
+    buffers: Vec<Vec<u8>>,
+    metas: Vec<RecvMeta>,
+    // 控制消息缓冲区（仅在接收元数据时分配）
+    #[cfg(target_os = "linux")]
+    controls: Vec<crate::core::network::sys::ControlBuffer>,
+    len: usize,
+}
+
+impl RecvBatch {
+    /// 创建批量接收缓冲区
+    ///
+    /// # 参数
+    /// * `batch_size` - 单次最多接收的数据报数量
+    /// * `buffer_size` - 每个数据报缓冲区的大小
+    pub fn new(
+        batch_size: usize,
+        buffer_size: usize,
+    ) -> Self {
+        let batch_size = batch_size.max(1);
+        Self {
+            buffers: vec![
+                vec![0u8; buffer_size];
+                batch_size
+            ],
+            metas: vec![RecvMeta::default(); batch_size],
+            #[cfg(target_os = "linux")]
+            controls: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// 同时接收内核控制消息（接收时间戳、溢出计数）
+    ///
+    /// 仅 Linux 支持；启用后即使批量大小为 1 也通过 recvmmsg 接收。
+    pub fn with_metadata(mut self) -> Self {
+        #[cfg(target_os = "linux")]
+        {
+            self.controls =
+                vec![[0u64; 32]; self.buffers.len()];
+        }
+        self
+    }
+
+    /// 遍历本批接收到的数据报
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&[u8], RecvMeta)> {
+        self.buffers
+            .iter()
+            .zip(self.metas.iter())
+            .take(self.len)
+            .map(|(buffer, meta)| {
+                (&buffer[..meta.len], *meta)
+            })
+    }
+}
+
+/// 批量接收数据报，至少等待到一个数据报后返回
+///
+/// 该函数是取消安全的，可以在 `tokio::select!` 中使用。
+pub async fn recv_batch(
+    socket: &TokioUdpSocket,
+    batch: &mut RecvBatch,
+) -> io::Result<usize> {
+    batch.len = 0;
+
+    #[cfg(target_os = "linux")]
+    if batch.buffers.len() > 1 || !batch.controls.is_empty()
+    {
+        use std::os::fd::AsRawFd;
+        use tokio::io::Interest;
+
+        let fd = socket.as_raw_fd();
+        loop {
+            socket.readable().await?;
+            match socket.try_io(Interest::READABLE, || {
+                crate::core::network::sys::recvmmsg(
+                    fd,
+                    &mut batch.buffers,
+                    &mut batch.metas,
+                    &mut batch.controls,
+                )
+            }) {
+                Ok(received) => {
+                    batch.len = received;
+                    return Ok(received);
+                }
+                Err(e)
+                    if e.kind()
+                        == io::ErrorKind::WouldBlock =>
+                {
+                    continue;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    let (len, source) =
+        socket.recv_from(&mut batch.buffers[0]).await?;
+    batch.metas[0] = RecvMeta {
+        len,
+        source: Some(source),
+        ..Default::default()
+    };
+    batch.len = 1;
+    Ok(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_batch_round_trip() {
+        let receiver = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let sender = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let target = receiver.local_addr().unwrap();
+
+        let packets: Vec<Vec<u8>> = (0u8..8)
+            .map(|i| vec![i; 16 + i as usize])
+            .collect();
+        let payloads: Vec<&[u8]> =
+            packets.iter().map(|p| p.as_slice()).collect();
+        let result =
+            send_batch(&sender, target, &payloads).await;
+        assert_eq!(result.sent, 8);
+        assert_eq!(result.errors(), 0);
+
+        let mut batch = RecvBatch::new(4, 2048);
+        let mut received = Vec::new();
+        while received.len() < packets.len() {
+            recv_batch(&receiver, &mut batch)
+                .await
+                .unwrap();
+            for (data, meta) in batch.iter() {
+                assert_eq!(
+                    meta.source,
+                    Some(sender.local_addr().unwrap())
+                );
+                received.push(data.to_vec());
+            }
+        }
+        assert_eq!(received, packets);
+    }
+
+    #[tokio::test]
+    async fn test_failed_datagram_in_middle_of_batch() {
+        let receiver = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let sender = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let target = receiver.local_addr().unwrap();
+
+        // 超过 UDP 最大长度的数据报发送失败，其后的数据报照常发送
+        let oversized = vec![0u8; 70_000];
+        let payloads: Vec<&[u8]> =
+            vec![&[1; 8], &oversized, &[2; 8]];
+        let result =
+            send_batch(&sender, target, &payloads).await;
+        assert_eq!(result.sent, 2);
+        assert_eq!(result.failed, vec![1]);
+        let delivered: Vec<usize> = result
+            .delivered(&payloads)
+            .map(|payload| payload.len())
+            .collect();
+        assert_eq!(delivered, vec![8, 8]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_kernel_timestamps() {
+        use crate::core::network::sys::{
+            enable_rx_timestamps, RxTimestamping,
+        };
+        use std::os::fd::AsRawFd;
+
+        let receiver = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        enable_rx_timestamps(
+            receiver.as_raw_fd(),
+            RxTimestamping::Software,
+        )
+        .unwrap();
+        let sender = TokioUdpSocket::bind("127.0.0.1:0")
+            .await
+            .unwrap();
+        let target = receiver.local_addr().unwrap();
+
+        let before = chrono::Utc::now();
+        send_batch(&sender, target, &[b"ping"]).await;
+
+        let mut batch =
+            RecvBatch::new(1, 64).with_metadata();
+        recv_batch(&receiver, &mut batch).await.unwrap();
+        let (data, meta) = batch.iter().next().unwrap();
+        assert_eq!(data, b"ping");
+        let kernel_time = meta.kernel_time.unwrap();
+        assert!(kernel_time >= before);
+        assert!(kernel_time <= chrono::Utc::now());
+    }
+}
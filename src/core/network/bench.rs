@@ -0,0 +1,207 @@
+//! 吞吐量基准测试模块
+//!
+//! 在本机回环地址上对比逐包收发与批量收发的吞吐量
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket as TokioUdpSocket;
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::network::batch::{
+    recv_batch, send_batch, RecvBatch,
+};
+use crate::core::network::sys::MAX_BATCH_SIZE;
+
+/// 基准测试配置
+#[derive(Debug, Clone)]
+pub struct BenchmarkConfig {
+    /// 数据包大小（字节）
+    pub packet_size: usize,
+    /// 每轮发送的数据包数量
+    pub packet_count: usize,
+    /// 批量模式的批大小
+    pub batch_size: usize,
+}
+
+impl Default for BenchmarkConfig {
+    fn default() -> Self {
+        Self {
+            packet_size: 64,
+            packet_count: 200_000,
+            batch_size: 32,
+        }
+    }
+}
+
+/// 单轮基准测试结果
+#[derive(Debug, Clone)]
+pub struct BenchmarkResult {
+    /// 本轮使用的批大小（1 表示逐包收发）
+    pub batch_size: usize,
+    /// 数据包大小（字节）
+    pub packet_size: usize,
+    /// 成功发送的数据包数量
+    pub packets_sent: usize,
+    /// 接收端收到的数据包数量
+    pub packets_received: usize,
+    /// 发送失败的数据包数量
+    pub send_errors: usize,
+    /// 发送耗时
+    pub elapsed: Duration,
+}
+
+impl BenchmarkResult {
+    /// 发送速率（包/秒）
+    pub fn packets_per_second(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs > 0.0 {
+            self.packets_sent as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// 发送速率（Mbps）
+    pub fn megabits_per_second(&self) -> f64 {
+        self.packets_per_second()
+            * self.packet_size as f64
+            * 8.0
+            / 1_000_000.0
+    }
+
+    /// 回环丢包率（百分比）
+    pub fn loss_percent(&self) -> f64 {
+        if self.packets_sent == 0 {
+            return 0.0;
+        }
+        let lost = self
+            .packets_sent
+            .saturating_sub(self.packets_received);
+        lost as f64 * 100.0 / self.packets_sent as f64
+    }
+}
+
+/// 运行基准测试：先逐包收发，再以配置的批大小批量收发
+pub async fn run_benchmark(
+    config: &BenchmarkConfig,
+) -> Result<Vec<BenchmarkResult>> {
+    let mut results = vec![run_round(config, 1).await?];
+    let batch_size =
+        config.batch_size.clamp(1, MAX_BATCH_SIZE);
+    if batch_size > 1 {
+        results.push(run_round(config, batch_size).await?);
+    }
+    Ok(results)
+}
+
+/// 执行一轮回环收发测试
+async fn run_round(
+    config: &BenchmarkConfig,
+    batch_size: usize,
+) -> Result<BenchmarkResult> {
+    let loopback: SocketAddr =
+        "127.0.0.1:0".parse().unwrap();
+
+    let receiver = TokioUdpSocket::bind(loopback)
+        .await
+        .map_err(|e| {
+            DataTransferError::network(format!(
+                "Failed to bind benchmark receiver: {e}"
+            ))
+        })?;
+    if let Err(e) = socket2::SockRef::from(&receiver)
+        .set_recv_buffer_size(8 * 1024 * 1024)
+    {
+        tracing::warn!(
+            "Failed to set receive buffer size: {}",
+            e
+        );
+    }
+    let target = receiver.local_addr()?;
+
+    let sender = TokioUdpSocket::bind(loopback)
+        .await
+        .map_err(|e| {
+            DataTransferError::network(format!(
+                "Failed to bind benchmark sender: {e}"
+            ))
+        })?;
+
+    // 接收任务：发送结束后空闲 200ms 即退出
+    let sending_done = Arc::new(AtomicBool::new(false));
+    let receiver_done = Arc::clone(&sending_done);
+    let packet_size = config.packet_size;
+    let receive_task = tokio::spawn(async move {
+        let mut batch =
+            RecvBatch::new(batch_size, packet_size.max(1));
+        let mut received = 0usize;
+        loop {
+            match tokio::time::timeout(
+                Duration::from_millis(200),
+                recv_batch(&receiver, &mut batch),
+            )
+            .await
+            {
+                Ok(Ok(count)) => received += count,
+                Ok(Err(e)) => {
+                    tracing::warn!(
+                        "Benchmark receive error: {}",
+                        e
+                    );
+                    // 持续出错时不会触发空闲超时，发送结束后直接退出
+                    if receiver_done.load(Ordering::Acquire)
+                    {
+                        break;
+                    }
+                    tokio::task::yield_now().await;
+                }
+                Err(_) => {
+                    if receiver_done.load(Ordering::Acquire)
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+        received
+    });
+
+    let payload = vec![0xA5u8; config.packet_size];
+    let payloads: Vec<&[u8]> =
+        vec![payload.as_slice(); batch_size];
+
+    let mut packets_sent = 0;
+    let mut send_errors = 0;
+    let started = Instant::now();
+    while packets_sent + send_errors < config.packet_count {
+        let remaining = config.packet_count
+            - packets_sent
+            - send_errors;
+        let count = remaining.min(batch_size);
+        let result =
+            send_batch(&sender, target, &payloads[..count])
+                .await;
+        packets_sent += result.sent;
+        send_errors += result.errors();
+    }
+    let elapsed = started.elapsed();
+    sending_done.store(true, Ordering::Release);
+
+    let packets_received =
+        receive_task.await.map_err(|e| {
+            DataTransferError::network(format!(
+                "Benchmark receive task failed: {e}"
+            ))
+        })?;
+
+    Ok(BenchmarkResult {
+        batch_size,
+        packet_size: config.packet_size,
+        packets_sent,
+        packets_received,
+        send_errors,
+        elapsed,
+    })
+}
@@ -0,0 +1,190 @@
+//! 接收端点模块
+//!
+//! 解析接收器的附加监听端点。每条端点描述可以是：
+//!
+//! - `30002` 使用主监听地址的单个端口
+//! - `30001-30010` 使用主监听地址的连续端口
+//! - `239.1.1.2:30001` 指定地址与端口
+//! - `239.1.1.2:30001-30005` 指定地址与端口范围
+//!
+//! IPv6 地址需要写在方括号中，例如 `[ff02::1]:30001`。
+
+use std::net::{IpAddr, SocketAddr};
+
+use crate::app::config::types::NetworkConfig;
+use crate::app::error::types::{DataTransferError, Result};
+
+/// 单个接收会话允许的最大端点数量
+pub const MAX_ENDPOINT_COUNT: usize = 256;
+
+/// 根据主网络配置与附加端点描述生成全部接收端点
+///
+/// 主端点总是排在第一位，重复的端点只保留一个。
+pub fn resolve_endpoints(
+    primary: &NetworkConfig,
+    specs: &[String],
+) -> Result<Vec<NetworkConfig>> {
+    let mut addrs = vec![SocketAddr::new(
+        primary.address,
+        primary.port,
+    )];
+    for spec in specs
+        .iter()
+        .map(|spec| spec.trim())
+        .filter(|spec| !spec.is_empty())
+    {
+        let parsed =
+            parse_endpoint_spec(spec, primary.address)
+                .map_err(|message| {
+                    DataTransferError::validation(
+                        "Endpoints",
+                        format!("{spec}: {message}"),
+                    )
+                })?;
+        for addr in parsed {
+            if !addrs.contains(&addr) {
+                addrs.push(addr);
+            }
+            if addrs.len() > MAX_ENDPOINT_COUNT {
+                return Err(DataTransferError::validation(
+                    "Endpoints",
+                    format!(
+                        "At most {MAX_ENDPOINT_COUNT} endpoints are supported"
+                    ),
+                ));
+            }
+        }
+    }
+
+    addrs
+        .into_iter()
+        .map(|addr| {
+            NetworkConfig::for_receiver(
+                addr.ip().to_string(),
+                addr.port(),
+                primary.network_type,
+                primary.interface.clone(),
+            )
+        })
+        .collect()
+}
+
+/// 解析一条端点描述
+fn parse_endpoint_spec(
+    spec: &str,
+    default_address: IpAddr,
+) -> std::result::Result<Vec<SocketAddr>, String> {
+    let (address, ports) = match spec.rsplit_once(':') {
+        Some((address, ports)) => {
+            let address = address
+                .strip_prefix('[')
+                .and_then(|a| a.strip_suffix(']'))
+                .unwrap_or(address);
+            let address: IpAddr =
+                address.parse().map_err(|_| {
+                    format!("invalid address: {address}")
+                })?;
+            (address, ports)
+        }
+        None => (default_address, spec),
+    };
+
+    let parse_port = |value: &str| {
+        value
+            .parse::<u16>()
+            .ok()
+            .filter(|port| *port > 0)
+            .ok_or_else(|| format!("invalid port: {value}"))
+    };
+    let (start, end) = match ports.split_once('-') {
+        Some((start, end)) => {
+            (parse_port(start)?, parse_port(end)?)
+        }
+        None => {
+            let port = parse_port(ports)?;
+            (port, port)
+        }
+    };
+    if start > end {
+        return Err(format!("empty port range: {ports}"));
+    }
+
+    Ok((start..=end)
+        .map(|port| SocketAddr::new(address, port))
+        .collect())
+}
+
+/// 按端点生成数据集名称（地址中的分隔符替换为下划线）
+pub fn endpoint_dataset_name(
+    base_name: &str,
+    endpoint: &NetworkConfig,
+) -> String {
+    let address = endpoint
+        .address
+        .to_string()
+        .replace(['.', ':'], "_");
+    format!("{base_name}_{address}_{}", endpoint.port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::types::NetworkType;
+
+    fn primary() -> NetworkConfig {
+        NetworkConfig::for_receiver(
+            "127.0.0.1".to_string(),
+            30001,
+            NetworkType::Unicast,
+            None,
+        )
+        .unwrap()
+    }
+
+    fn addrs(endpoints: &[NetworkConfig]) -> Vec<String> {
+        endpoints
+            .iter()
+            .map(|e| format!("{}:{}", e.address, e.port))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_port_range_and_addresses() {
+        let endpoints = resolve_endpoints(
+            &primary(),
+            &[
+                "30001-30003".to_string(),
+                "127.0.0.2:40000".to_string(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            addrs(&endpoints),
+            [
+                "127.0.0.1:30001",
+                "127.0.0.1:30002",
+                "127.0.0.1:30003",
+                "127.0.0.2:40000",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_endpoint_specs() {
+        for spec in ["0", "30005-30001", "host:1", "x"] {
+            assert!(resolve_endpoints(
+                &primary(),
+                &[spec.to_string()]
+            )
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn test_endpoint_dataset_name() {
+        assert_eq!(
+            endpoint_dataset_name("capture", &primary()),
+            "capture_127_0_0_1_30001"
+        );
+    }
+}
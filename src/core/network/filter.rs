@@ -0,0 +1,425 @@
+//! 接收过滤模块
+//!
+//! 按来源地址、端口、负载长度与负载字节内容过滤接收到的数据包。
+//! 规则以文本形式保存在接收器配置中，每条规则一行：
+//!
+//! - `src 10.0.0.0/8` 来源地址属于该网段
+//! - `sport 5000-5010` 来源端口在范围内（也可写单个端口）
+//! - `dport 9000` 接收端口在范围内
+//! - `len 64-1500` 负载长度在范围内
+//! - `offset 0 == 0x5054` 指定偏移处的字节等于给定值（也支持 `!=`）
+//!
+//! 任意规则前加 `not` 表示取反（例如 `not src 192.168.1.0/24` 用于屏蔽网段），
+//! 多条规则按配置使用 AND（全部满足）或 OR（任一满足）组合。
+
+use std::net::{IpAddr, SocketAddr};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::app::config::options::{
+    FilterMode, FilterOptions,
+};
+use crate::app::error::types::{DataTransferError, Result};
+
+/// IP 网段
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cidr {
+    network: IpAddr,
+    prefix: u8,
+}
+
+impl Cidr {
+    /// 判断地址是否属于该网段
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        match (self.network, addr) {
+            (IpAddr::V4(network), IpAddr::V4(addr)) => {
+                let mask = u32::MAX
+                    .checked_shl(32 - self.prefix as u32)
+                    .unwrap_or(0);
+                u32::from(network) & mask
+                    == u32::from(addr) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(addr)) => {
+                let mask = u128::MAX
+                    .checked_shl(128 - self.prefix as u32)
+                    .unwrap_or(0);
+                u128::from(network) & mask
+                    == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Cidr {
+    type Err = String;
+
+    fn from_str(
+        value: &str,
+    ) -> std::result::Result<Self, String> {
+        let (address, prefix) = match value.split_once('/')
+        {
+            Some((address, prefix)) => {
+                (address, Some(prefix))
+            }
+            None => (value, None),
+        };
+        let network: IpAddr =
+            address.parse().map_err(|_| {
+                format!("invalid address: {address}")
+            })?;
+        let max_prefix =
+            if network.is_ipv4() { 32 } else { 128 };
+        let prefix = match prefix {
+            Some(prefix) => prefix
+                .parse::<u8>()
+                .ok()
+                .filter(|prefix| *prefix <= max_prefix)
+                .ok_or_else(|| {
+                    format!(
+                        "invalid prefix length: {prefix}"
+                    )
+                })?,
+            None => max_prefix,
+        };
+        Ok(Self { network, prefix })
+    }
+}
+
+/// 单条过滤条件
+#[derive(Debug, Clone, PartialEq)]
+enum Condition {
+    /// 来源地址属于网段
+    Source(Cidr),
+    /// 来源端口在范围内
+    SourcePort(RangeInclusive<u16>),
+    /// 接收端口在范围内
+    LocalPort(RangeInclusive<u16>),
+    /// 负载长度在范围内
+    Length(RangeInclusive<usize>),
+    /// 指定偏移处的字节与模式相同
+    Bytes { offset: usize, pattern: Vec<u8> },
+}
+
+/// 单条过滤规则（可取反）
+#[derive(Debug, Clone, PartialEq)]
+struct Rule {
+    negate: bool,
+    condition: Condition,
+}
+
+/// 接收过滤器
+#[derive(Debug, Clone, PartialEq)]
+pub struct PacketFilter {
+    mode: FilterMode,
+    rules: Vec<Rule>,
+}
+
+impl PacketFilter {
+    /// 根据配置构造过滤器
+    pub fn from_options(
+        options: &FilterOptions,
+    ) -> Result<Self> {
+        let rules = options
+            .rules
+            .iter()
+            .map(|rule| rule.trim())
+            .filter(|rule| !rule.is_empty())
+            .map(|rule| {
+                parse_rule(rule).map_err(|message| {
+                    DataTransferError::validation(
+                        "Receive Filter",
+                        format!("{rule}: {message}"),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            mode: options.mode,
+            rules,
+        })
+    }
+
+    /// 是否配置了任何规则
+    pub fn is_active(&self) -> bool {
+        !self.rules.is_empty()
+    }
+
+    /// 判断数据包是否应当被记录
+    pub fn matches(
+        &self,
+        data: &[u8],
+        source: Option<SocketAddr>,
+        local_port: u16,
+    ) -> bool {
+        if self.rules.is_empty() {
+            return true;
+        }
+
+        let check = |rule: &Rule| {
+            rule.negate
+                != rule
+                    .condition
+                    .matches(data, source, local_port)
+        };
+        match self.mode {
+            FilterMode::All => self.rules.iter().all(check),
+            FilterMode::Any => self.rules.iter().any(check),
+        }
+    }
+}
+
+impl Condition {
+    fn matches(
+        &self,
+        data: &[u8],
+        source: Option<SocketAddr>,
+        local_port: u16,
+    ) -> bool {
+        match self {
+            Condition::Source(cidr) => {
+                source.is_some_and(|source| {
+                    cidr.contains(source.ip())
+                })
+            }
+            Condition::SourcePort(range) => source
+                .is_some_and(|source| {
+                    range.contains(&source.port())
+                }),
+            Condition::LocalPort(range) => {
+                range.contains(&local_port)
+            }
+            Condition::Length(range) => {
+                range.contains(&data.len())
+            }
+            Condition::Bytes { offset, pattern } => offset
+                .checked_add(pattern.len())
+                .and_then(|end| data.get(*offset..end))
+                .is_some_and(|bytes| {
+                    bytes == pattern.as_slice()
+                }),
+        }
+    }
+}
+
+/// 解析一条规则
+fn parse_rule(
+    text: &str,
+) -> std::result::Result<Rule, String> {
+    let mut tokens: Vec<&str> =
+        text.split_whitespace().collect();
+    let negate = tokens.first() == Some(&"not");
+    if negate {
+        tokens.remove(0);
+    }
+
+    let condition = match tokens.as_slice() {
+        ["src", cidr] => Condition::Source(cidr.parse()?),
+        ["sport", range] => {
+            Condition::SourcePort(parse_range(range)?)
+        }
+        ["dport", range] => {
+            Condition::LocalPort(parse_range(range)?)
+        }
+        ["len", range] => {
+            Condition::Length(parse_range(range)?)
+        }
+        ["offset", offset, operator, value] => {
+            let offset = offset.parse().map_err(|_| {
+                format!("invalid offset: {offset}")
+            })?;
+            let pattern = parse_hex(value)?;
+            let condition =
+                Condition::Bytes { offset, pattern };
+            return match *operator {
+                "==" => Ok(Rule { negate, condition }),
+                "!=" => Ok(Rule {
+                    negate: !negate,
+                    condition,
+                }),
+                other => Err(format!(
+                    "unknown operator: {other}"
+                )),
+            };
+        }
+        _ => return Err("unrecognized rule".to_string()),
+    };
+
+    Ok(Rule { negate, condition })
+}
+
+/// 解析 `a-b` 或单个值形式的闭区间
+fn parse_range<T>(
+    text: &str,
+) -> std::result::Result<RangeInclusive<T>, String>
+where
+    T: FromStr + PartialOrd + Copy,
+{
+    let parse = |value: &str| {
+        value
+            .parse::<T>()
+            .map_err(|_| format!("invalid value: {value}"))
+    };
+    let (start, end) = match text.split_once('-') {
+        Some((start, end)) => (parse(start)?, parse(end)?),
+        None => {
+            let value = parse(text)?;
+            (value, value)
+        }
+    };
+    if start > end {
+        return Err(format!("empty range: {text}"));
+    }
+    Ok(start..=end)
+}
+
+/// 解析 `0x` 前缀的十六进制字节序列
+fn parse_hex(
+    text: &str,
+) -> std::result::Result<Vec<u8>, String> {
+    let digits = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .ok_or_else(|| {
+            format!("expected hex value: {text}")
+        })?;
+    // 先确认全部为 ASCII 十六进制数字，之后才能按字节切分
+    if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("invalid hex value: {text}"));
+    }
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return Err(format!(
+            "hex value must have an even number of digits: {text}"
+        ));
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&digits[i..i + 2], 16)
+                .map_err(|_| {
+                    format!("invalid hex value: {text}")
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(
+        mode: FilterMode,
+        rules: &[&str],
+    ) -> PacketFilter {
+        PacketFilter::from_options(&FilterOptions {
+            mode,
+            rules: rules
+                .iter()
+                .map(|r| r.to_string())
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    fn addr(value: &str) -> Option<SocketAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn test_cidr_contains() {
+        let cidr: Cidr = "10.1.0.0/16".parse().unwrap();
+        assert!(cidr.contains("10.1.2.3".parse().unwrap()));
+        assert!(!cidr.contains("10.2.0.1".parse().unwrap()));
+
+        let all: Cidr = "0.0.0.0/0".parse().unwrap();
+        assert!(
+            all.contains("192.168.1.1".parse().unwrap())
+        );
+        assert!("10.0.0.0/33".parse::<Cidr>().is_err());
+    }
+
+    #[test]
+    fn test_all_mode_with_blocklist() {
+        let filter = build(
+            FilterMode::All,
+            &[
+                "src 10.0.0.0/8",
+                "not src 10.1.0.0/16",
+                "len 4-8",
+            ],
+        );
+        assert!(filter.matches(
+            &[0; 4],
+            addr("10.2.0.1:1"),
+            0
+        ));
+        assert!(!filter.matches(
+            &[0; 4],
+            addr("10.1.0.1:1"),
+            0
+        ));
+        assert!(!filter.matches(
+            &[0; 9],
+            addr("10.2.0.1:1"),
+            0
+        ));
+        assert!(!filter.matches(&[0; 4], None, 0));
+    }
+
+    #[test]
+    fn test_any_mode_and_byte_pattern() {
+        let filter = build(
+            FilterMode::Any,
+            &["offset 0 == 0x5054", "sport 5000-5010"],
+        );
+        assert!(filter.matches(
+            &[0x50, 0x54, 0x00],
+            addr("1.1.1.1:1"),
+            0
+        ));
+        assert!(filter.matches(
+            &[0],
+            addr("1.1.1.1:5005"),
+            0
+        ));
+        assert!(!filter.matches(
+            &[0x50],
+            addr("1.1.1.1:1"),
+            0
+        ));
+
+        let filter = build(
+            FilterMode::All,
+            &["offset 1 != 0xff", "dport 9000"],
+        );
+        assert!(filter.matches(&[0, 0], None, 9000));
+        assert!(!filter.matches(&[0, 0xff], None, 9000));
+
+        // 偏移量过大时不匹配（而不是溢出）
+        let filter = build(
+            FilterMode::All,
+            &[&format!("offset {} == 0x50", usize::MAX)],
+        );
+        assert!(!filter.matches(&[0x50], None, 0));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        for rule in [
+            "src",
+            "len 9-1",
+            "offset 0 == 0x5",
+            "offset 0 == 0xé1",
+            "foo 1",
+        ] {
+            assert!(PacketFilter::from_options(
+                &FilterOptions {
+                    mode: FilterMode::All,
+                    rules: vec![rule.to_string()],
+                }
+            )
+            .is_err());
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! 合成流量发生器模块
+//!
+//! 不依赖数据集，按配置的包长分布与负载内容生成数据包，并按目标速率
+//! 推算每个数据包的计划发送时间。生成的数据包与数据集重放共用发送
+//! 循环，因此时序控制、批量发送、测试头与统计信息完全相同。
+
+use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::time::{Duration, Instant};
+
+use crate::app::config::options::{
+    GeneratorOptions, PayloadPattern, SizeDistribution,
+};
+use crate::app::error::types::Result;
+use crate::core::network::source::{
+    PacketSource, SourcePacket,
+};
+use crate::core::probe::header::TestHeader;
+
+/// 生成数据包的最大长度（UDP 最大负载）
+pub const MAX_GENERATOR_PACKET_SIZE: usize = 65507;
+
+/// 简单 IMIX 的 UDP 负载长度与权重
+///
+/// 对应 64、594、1518 字节（含 FCS）的以太网帧，比例为 7:4:1。
+const IMIX: [(usize, u32); 3] =
+    [(18, 7), (548, 4), (1472, 1)];
+
+/// 合成流量数据源
+pub struct GeneratorSource {
+    options: GeneratorOptions,
+    rng: StdRng,
+    /// 序号负载测试头中的数据流 ID
+    stream_id: u32,
+    /// 下一个数据包的序号
+    sequence: u64,
+    /// 首个数据包的计划发送时间
+    start_time: Option<DateTime<Utc>>,
+    /// 首个数据包的生成时刻（不限速时用于计算时长）
+    started: Option<Instant>,
+    /// 已调度的负载比特数
+    scheduled_bits: u64,
+}
+
+impl GeneratorSource {
+    /// 创建合成流量数据源
+    ///
+    /// 序号负载的测试头使用 `stream_id` 标识数据流。
+    pub fn new(
+        options: &GeneratorOptions,
+        stream_id: u32,
+    ) -> Self {
+        Self {
+            options: options.clone(),
+            rng: StdRng::seed_from_u64(options.seed),
+            stream_id,
+            sequence: 0,
+            start_time: None,
+            started: None,
+            scheduled_bits: 0,
+        }
+    }
+
+    /// 按包长分布选取下一个数据包的长度
+    fn next_size(&mut self) -> usize {
+        let options = &self.options;
+        let size = match options.size {
+            SizeDistribution::Fixed => options.packet_size,
+            SizeDistribution::Uniform => {
+                let low =
+                    options.min_size.min(options.max_size);
+                let high =
+                    options.min_size.max(options.max_size);
+                self.rng.gen_range(low..=high)
+            }
+            SizeDistribution::Imix => {
+                let total: u32 =
+                    IMIX.iter().map(|(_, w)| w).sum();
+                let mut pick = self.rng.gen_range(0..total);
+                IMIX.iter()
+                    .find(|(_, weight)| {
+                        let hit = pick < *weight;
+                        pick = pick.saturating_sub(*weight);
+                        hit
+                    })
+                    .map_or(IMIX[0].0, |(size, _)| *size)
+            }
+        };
+        size.clamp(1, MAX_GENERATOR_PACKET_SIZE)
+    }
+
+    /// 按负载内容填充数据包
+    fn fill(
+        &mut self,
+        data: &mut [u8],
+        send_time: DateTime<Utc>,
+    ) {
+        match self.options.pattern {
+            PayloadPattern::Zeros => {}
+            PayloadPattern::Incrementing => {
+                for (i, byte) in data.iter_mut().enumerate()
+                {
+                    *byte = i as u8;
+                }
+            }
+            PayloadPattern::Random => self.rng.fill(data),
+            PayloadPattern::Sequence => {
+                // 与发送器测试头格式相同，覆盖负载开头而不改变包长；
+                // 容不下发送时间的短包只写基本测试头
+                let mut header = TestHeader {
+                    stream_id: self.stream_id,
+                    seq: self.sequence,
+                    send_time_ns: send_time
+                        .timestamp_nanos_opt()
+                        .map(|ns| ns as u64),
+                    reflected_count: None,
+                };
+                if data.len() < header.encoded_len() {
+                    header.send_time_ns = None;
+                }
+                let mut encoded = Vec::with_capacity(
+                    header.encoded_len(),
+                );
+                header.encode(&mut encoded);
+                let len = data.len().min(encoded.len());
+                data[..len]
+                    .copy_from_slice(&encoded[..len]);
+            }
+        }
+    }
+}
+
+impl PacketSource for GeneratorSource {
+    fn next_packet(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        let start_time =
+            *self.start_time.get_or_insert_with(Utc::now);
+        let started =
+            *self.started.get_or_insert_with(Instant::now);
+
+        // 限速时按已调度的比特数推算发送偏移，不限速时全部立即发送
+        let rate = self.options.rate_mbps * 1e6;
+        let offset = if rate > 0.0 {
+            Duration::from_nanos(
+                (self.scheduled_bits as f64 * 1e9 / rate)
+                    .round() as u64,
+            )
+        } else {
+            started.elapsed()
+        };
+        if self.options.duration_secs > 0
+            && offset
+                >= Duration::from_secs(
+                    self.options.duration_secs,
+                )
+        {
+            return Ok(None);
+        }
+
+        // 不限速时所有数据包共用起始时间戳，测试头的发送时间取生成时刻
+        let (timestamp, send_time) = if rate > 0.0 {
+            let scheduled = start_time
+                + chrono::Duration::from_std(offset)
+                    .unwrap_or_default();
+            (scheduled, scheduled)
+        } else {
+            (start_time, Utc::now())
+        };
+
+        let size = self.next_size();
+        let mut data = vec![0u8; size];
+        self.fill(&mut data, send_time);
+        self.scheduled_bits += size as u64 * 8;
+        self.sequence += 1;
+
+        Ok(Some(SourcePacket { data, timestamp }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::probe::header::HEADER_LEN;
+
+    fn drain(
+        source: &mut GeneratorSource,
+    ) -> Vec<SourcePacket> {
+        let mut packets = Vec::new();
+        while let Some(packet) =
+            source.next_packet().unwrap()
+        {
+            packets.push(packet);
+        }
+        packets
+    }
+
+    #[test]
+    fn test_rate_schedules_timestamps() {
+        // 1250 字节 = 10000 比特，1 Mbps 下每 10 ms 一个数据包
+        let mut source = GeneratorSource::new(
+            &GeneratorOptions {
+                packet_size: 1250,
+                rate_mbps: 1.0,
+                duration_secs: 1,
+                pattern: PayloadPattern::Sequence,
+                ..Default::default()
+            },
+            7,
+        );
+        let packets = drain(&mut source);
+        assert_eq!(packets.len(), 100);
+
+        let start = packets[0].timestamp;
+        let gap = packets[42].timestamp - start;
+        assert_eq!(gap.num_milliseconds(), 420);
+
+        let data = &packets[42].data;
+        assert_eq!(data.len(), 1250);
+        let header = TestHeader::decode(data).unwrap();
+        assert_eq!(header.stream_id, 7);
+        assert_eq!(header.seq, 42);
+        assert_eq!(
+            header.send_time_ns,
+            packets[42]
+                .timestamp
+                .timestamp_nanos_opt()
+                .map(|ns| ns as u64)
+        );
+        let len = header.encoded_len();
+        assert!(data[len..].iter().all(|b| *b == 0));
+
+        // 容不下发送时间的短包只带基本测试头
+        let mut short = GeneratorSource::new(
+            &GeneratorOptions {
+                packet_size: HEADER_LEN,
+                rate_mbps: 1.0,
+                duration_secs: 1,
+                pattern: PayloadPattern::Sequence,
+                ..Default::default()
+            },
+            7,
+        );
+        let packet = short.next_packet().unwrap().unwrap();
+        let header =
+            TestHeader::decode(&packet.data).unwrap();
+        assert_eq!(header.seq, 0);
+        assert_eq!(header.send_time_ns, None);
+    }
+
+    #[test]
+    fn test_size_distributions_are_seeded() {
+        let options = GeneratorOptions {
+            size: SizeDistribution::Imix,
+            pattern: PayloadPattern::Random,
+            rate_mbps: 10.0,
+            duration_secs: 1,
+            ..Default::default()
+        };
+        let first =
+            drain(&mut GeneratorSource::new(&options, 0));
+        let second =
+            drain(&mut GeneratorSource::new(&options, 0));
+        assert_eq!(first.len(), second.len());
+        assert!(first
+            .iter()
+            .zip(&second)
+            .all(|(a, b)| a.data == b.data));
+        assert!(first.iter().all(|packet| IMIX
+            .iter()
+            .any(|(size, _)| *size == packet.data.len())));
+        let small = first
+            .iter()
+            .filter(|packet| packet.data.len() == IMIX[0].0)
+            .count();
+        assert!(small > first.len() / 2);
+
+        let mut uniform = GeneratorSource::new(
+            &GeneratorOptions {
+                size: SizeDistribution::Uniform,
+                min_size: 100,
+                max_size: 200,
+                pattern: PayloadPattern::Incrementing,
+                rate_mbps: 1.0,
+                duration_secs: 1,
+                ..Default::default()
+            },
+            0,
+        );
+        let packets = drain(&mut uniform);
+        assert!(packets.iter().all(|packet| {
+            (100..=200).contains(&packet.data.len())
+                && packet.data[1] == 1
+                && packet.data[99] == 99
+        }));
+    }
+}
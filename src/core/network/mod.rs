@@ -1,5 +1,14 @@
 //! 网络相关模块
 
+pub mod batch;
+pub mod bench;
+pub mod endpoint;
+pub mod filter;
+pub mod generator;
 pub mod receiver;
 pub mod sender;
+pub mod source;
+pub mod stop;
+pub mod sys;
+pub mod throughput;
 pub mod types;
@@ -1,18 +1,49 @@
 //! 接收器模块 - 处理数据包接收逻辑
 
+use crate::app::config::options::{
+    OverflowPolicy, ReceiverOptions, TimestampSource,
+};
 use crate::app::config::types::{
-    NetworkType, ReceiverAppConfig,
+    NetworkConfig, NetworkType, ReceiverAppConfig,
 };
 use crate::app::error::types::Result;
+use crate::core::dataset::rotation::RotatingWriter;
+use crate::core::dataset::sidecar::PacketMeta;
+use crate::core::network::batch::{recv_batch, RecvBatch};
+use crate::core::network::endpoint::{
+    endpoint_dataset_name, resolve_endpoints,
+};
+use crate::core::network::filter::PacketFilter;
 use crate::core::network::sender::TransferState;
-use crate::core::network::types::UdpSocketFactory;
+use crate::core::network::stop::{
+    ReceiveBudget, StopReason,
+};
+use crate::core::network::sys::MAX_BATCH_SIZE;
+use crate::core::network::types::{
+    enable_nic_timestamps, enable_receive_metadata,
+    UdpSocketFactory,
+};
+use crate::core::pipeline::writer::{
+    enqueue_packet, join_dataset_writer,
+    spawn_dataset_writer, PacketQueue, ReceivedPacket,
+};
+use crate::core::probe::latency::LatencyTracker;
+use crate::core::probe::reflector::Reflector;
+use crate::core::probe::sequence::SequenceTracker;
 use crate::core::stats::collector::TransferStats;
+use crate::core::stats::report::ReportRecorder;
 use chrono::Utc;
-use pcapfile_io::{DataPacket, PcapWriter, WriterConfig};
+use std::collections::HashMap;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
-use tokio::time::{sleep, Duration};
-use tracing::error;
+use tokio::net::UdpSocket;
+use tokio::sync::watch;
+use tokio::time::{interval, Duration};
+use tracing::{error, info, warn};
+
+/// UDP 数据报的最大长度
+const MAX_DATAGRAM_SIZE: usize = 65536;
 
 /// GUI专用的接收器函数，支持共享状态和统计信息
 #[allow(clippy::too_many_arguments)]
@@ -23,6 +54,7 @@ pub async fn run_receiver_with_gui_stats(
     port: u16,
     network_type: NetworkType,
     interface: Option<String>,
+    options: ReceiverOptions,
     stats: Arc<Mutex<TransferStats>>,
     transfer_state: Arc<Mutex<TransferState>>,
 ) -> Result<()> {
@@ -39,106 +71,493 @@ pub async fn run_receiver_with_gui_stats(
     // 验证配置
     config.validate()?;
 
-    // 创建UDP接收器
-    let socket =
-        UdpSocketFactory::create_receiver(&config.network)
-            .await?;
-
-    // 创建pcap写入器
-    let writer_config = WriterConfig {
-        index_cache_size: 1000, // 设置索引缓存大小
-        max_packets_per_file: 1000,
-        ..Default::default()
-    };
+    // 解析接收过滤规则
+    let filter = Arc::new(PacketFilter::from_options(
+        &options.filter,
+    )?);
 
-    let mut writer = PcapWriter::new_with_config(
-        &config.output_path,
-        &config.dataset_name,
-        writer_config,
+    // 解析全部接收端点（主端点在前）
+    let endpoints = resolve_endpoints(
+        &config.network,
+        &options.endpoints,
     )?;
+    let per_endpoint =
+        options.dataset_per_endpoint && endpoints.len() > 1;
+
+    // 为每个端点创建UDP接收器（多个套接字时启用 SO_REUSEPORT）
+    let mut bound = Vec::new();
+    // 各接口的网卡时间戳配置结果（每个接口只配置一次）
+    let mut nic_sources: HashMap<
+        Option<String>,
+        TimestampSource,
+    > = HashMap::new();
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        let sockets = UdpSocketFactory::create_receivers(
+            endpoint,
+            options.socket_count,
+        )
+        .await?;
+        let socket_count = sockets.len();
+        // 端点的时间戳来源取其各套接字中最弱的一个
+        let mut endpoint_source: Option<TimestampSource> =
+            None;
+        for (socket_index, socket) in
+            sockets.into_iter().enumerate()
+        {
+            let nic_source = *nic_sources
+                .entry(endpoint.interface.clone())
+                .or_insert_with(|| {
+                    enable_nic_timestamps(
+                        &socket,
+                        options.timestamp_source,
+                        endpoint.interface.as_deref(),
+                    )
+                });
+            let source = enable_receive_metadata(
+                &socket, nic_source,
+            );
+            endpoint_source = Some(
+                endpoint_source
+                    .map_or(source, |s| s.min(source)),
+            );
+            let label = channel_label(
+                endpoint,
+                endpoints.len(),
+                socket_index,
+                socket_count,
+            );
+            bound.push((index, socket, label));
+        }
+        if let Some(source) = endpoint_source {
+            info!(
+                "Receive timestamps on {}: {}",
+                SocketAddr::new(
+                    endpoint.address,
+                    endpoint.port
+                ),
+                source
+            );
+        }
+    }
+    info!(
+        "Receiving on {} endpoint(s) with {} socket(s)",
+        endpoints.len(),
+        bound.len()
+    );
+
+    // 所有套接字共享的序号跟踪器（未启用时为空）
+    let sequence =
+        SequenceTracker::from_options(&options.sequence)
+            .map(|tracker| Arc::new(Mutex::new(tracker)));
+    let latency =
+        LatencyTracker::from_options(&options.latency)
+            .map(|tracker| Arc::new(Mutex::new(tracker)));
+    let reflector =
+        Reflector::from_options(&options.reflector)
+            .map(Arc::new);
+
+    // 创建数据集写入器（按配置轮转，可按端点拆分）
+    let writers = if per_endpoint {
+        endpoints
+            .iter()
+            .map(|endpoint| {
+                RotatingWriter::new(
+                    &config.output_path,
+                    &endpoint_dataset_name(
+                        &config.dataset_name,
+                        endpoint,
+                    ),
+                    &options.writer,
+                    options.rotation.clone(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?
+    } else {
+        vec![RotatingWriter::new(
+            &config.output_path,
+            &config.dataset_name,
+            &options.writer,
+            options.rotation.clone(),
+        )?]
+    };
 
     // 获取配置中的缓冲区大小
     let buffer_size = config.buffer_size;
 
     // 重置并初始化统计信息
+    let track_channels = bound.len() > 1;
     if let Ok(mut stats_guard) = stats.lock() {
         *stats_guard = TransferStats::new(); // GUI不需要进度条
+        stats_guard
+            .set_queue_capacity(options.queue_capacity);
+        stats_guard.set_filter_active(filter.is_active());
+        if sequence.is_some() {
+            stats_guard.set_sequence(Default::default());
+        }
+        if latency.is_some() {
+            stats_guard.set_latency(Default::default());
+        }
+        if reflector.is_some() {
+            stats_guard.set_reflector_active();
+        }
+        stats_guard.set_dataset(
+            writers[0].dataset_name(),
+            writers[0].seq(),
+        );
+        if track_channels {
+            for (_, _, label) in &bound {
+                stats_guard.register_channel(label);
+            }
+        }
+    }
+
+    // 按间隔采样统计信息，结束时写入报告
+    let report = ReportRecorder::start(
+        &options.report,
+        &config.output_path,
+        "receiver",
+        config.dataset_name.clone(),
+        serde_json::json!({
+            "address": address,
+            "port": port,
+            "network_type": format!("{network_type:?}")
+                .to_lowercase(),
+            "interface": config.network.interface,
+            "output_path": config.output_path,
+            "dataset_name": config.dataset_name,
+            "options": options,
+        }),
+        Arc::clone(&stats),
+    );
+
+    // 接收循环与写入线程之间的有界队列（每个数据集一个写入线程）
+    let mut queues = Vec::with_capacity(writers.len());
+    let mut writer_handles =
+        Vec::with_capacity(writers.len());
+    for (index, writer) in writers.into_iter().enumerate() {
+        // 单套接字时到达顺序即捕获顺序，无需等待重排序
+        let feeding = bound
+            .iter()
+            .filter(|(endpoint, _, _)| {
+                !per_endpoint || *endpoint == index
+            })
+            .count();
+        let reorder_window = if feeding > 1 {
+            Duration::from_millis(options.reorder_window_ms)
+        } else {
+            Duration::ZERO
+        };
+        let queue = Arc::new(PacketQueue::new(
+            options.queue_capacity,
+            0,
+        ));
+        writer_handles.push(spawn_dataset_writer(
+            writer,
+            Arc::clone(&queue),
+            Arc::clone(&stats),
+            reorder_window,
+        )?);
+        queues.push(queue);
+    }
+
+    // 批量接收缓冲区（批量模式下每个槽位只需容纳最大UDP数据报）
+    let batch_size =
+        options.batch_size.clamp(1, MAX_BATCH_SIZE);
+    let slot_size = if batch_size > 1 {
+        buffer_size.min(MAX_DATAGRAM_SIZE)
+    } else {
+        buffer_size
+    };
+
+    // 每个数据包记录的接收接口
+    let interface: Option<Arc<str>> =
+        config.network.interface.as_deref().map(Arc::from);
+
+    // 所有接收任务共享的停止条件配额
+    let budget = Arc::new(ReceiveBudget::new(&options));
+
+    // 每个套接字由独立的任务接收
+    let (stop_tx, stop_rx) = watch::channel(false);
+    let tasks: Vec<_> = bound
+        .into_iter()
+        .map(|(index, socket, label)| {
+            let endpoint = &endpoints[index];
+            let queue = if per_endpoint {
+                &queues[index]
+            } else {
+                &queues[0]
+            };
+            tokio::spawn(run_socket_loop(
+                SocketLoop {
+                    socket,
+                    label: track_channels.then_some(label),
+                    batch: RecvBatch::new(
+                        batch_size, slot_size,
+                    )
+                    .with_metadata(),
+                    last_kernel_drops: 0,
+                    endpoint: SocketAddr::new(
+                        endpoint.address,
+                        endpoint.port,
+                    ),
+                    interface: interface.clone(),
+                    filter: Arc::clone(&filter),
+                    sequence: sequence.clone(),
+                    latency: latency.clone(),
+                    reflector: reflector.clone(),
+                    overflow_policy: options
+                        .overflow_policy,
+                },
+                Arc::clone(queue),
+                Arc::clone(&budget),
+                Arc::clone(&stats),
+                stop_rx.clone(),
+            ))
+        })
+        .collect();
+
+    // 停止状态与停止条件检查 - 每100ms检查一次，配额用尽时立即停止
+    let mut stop_check =
+        interval(Duration::from_millis(100));
+    let stop_reason = loop {
+        tokio::select! {
+            _ = stop_check.tick() => {
+                if let Ok(state) = transfer_state.lock() {
+                    if matches!(*state, TransferState::Idle) {
+                        break StopReason::User;
+                    }
+                }
+                budget.check_timers();
+            }
+            _ = budget.exhausted() => {}
+        }
+        if let Some(reason) = budget.stop_reason() {
+            break reason;
+        }
+    };
+    info!("Receiver stopping: {}", stop_reason);
+
+    // 通知所有接收任务停止并等待其退出
+    let _ = stop_tx.send(true);
+    for task in tasks {
+        if let Err(e) = task.await {
+            error!("Receive task failed: {}", e);
+        }
     }
 
-    let mut buffer = vec![0u8; buffer_size];
+    // 关闭队列并等待写入线程完成剩余写入
+    for queue in &queues {
+        queue.close();
+    }
+    for handle in writer_handles {
+        join_dataset_writer(handle).await?;
+    }
 
-    // 统计信息
+    // 完成统计信息
+    if let Ok(mut stats_guard) = stats.lock() {
+        stats_guard
+            .set_stop_reason(stop_reason.to_string());
+        stats_guard.finish();
+        info!(
+            "Receiver summary: {}",
+            stats_guard.summary()
+        );
+    }
+    if let Some(report) = report {
+        if let Err(e) = report.finish().await {
+            error!("Failed to write report: {}", e);
+        }
+    }
+
+    // 更新传输状态为完成
+    if let Ok(mut state) = transfer_state.lock() {
+        *state = TransferState::Completed;
+    }
+
+    Ok(())
+}
+
+/// 生成接收通道名称
+///
+/// 多端点时以端点地址命名，同一端点有多个套接字时附加套接字序号。
+fn channel_label(
+    endpoint: &NetworkConfig,
+    endpoint_count: usize,
+    socket_index: usize,
+    socket_count: usize,
+) -> String {
+    if endpoint_count == 1 {
+        return format!("Socket {}", socket_index + 1);
+    }
+    let addr =
+        SocketAddr::new(endpoint.address, endpoint.port);
+    if socket_count > 1 {
+        format!("{} #{}", addr, socket_index + 1)
+    } else {
+        addr.to_string()
+    }
+}
+
+/// 单个接收套接字的任务状态
+struct SocketLoop {
+    socket: UdpSocket,
+    /// 通道名称（多套接字时用于分通道统计）
+    label: Option<String>,
+    batch: RecvBatch,
+    /// 上次读取到的 SO_RXQ_OVFL 累计值
+    last_kernel_drops: u32,
+    /// 接收端点（监听地址与端口）
+    endpoint: SocketAddr,
+    /// 接收网络接口
+    interface: Option<Arc<str>>,
+    /// 接收过滤器
+    filter: Arc<PacketFilter>,
+    /// 序号跟踪器
+    sequence: Option<Arc<Mutex<SequenceTracker>>>,
+    /// 时延跟踪器
+    latency: Option<Arc<Mutex<LatencyTracker>>>,
+    /// 反射器（启用时把数据包回送给发送方）
+    reflector: Option<Arc<Reflector>>,
+    overflow_policy: OverflowPolicy,
+}
+
+/// 单个套接字的接收循环，直到收到停止信号
+async fn run_socket_loop(
+    mut state: SocketLoop,
+    queue: Arc<PacketQueue>,
+    budget: Arc<ReceiveBudget>,
+    stats: Arc<Mutex<TransferStats>>,
+    mut stop: watch::Receiver<bool>,
+) {
     // 接收循环 - 使用 tokio::select! 来同时监听数据包接收和停止信号
     loop {
         tokio::select! {
             // 接收数据包分支
-            recv_result = socket.recv_from(&mut buffer) => {
+            recv_result = recv_batch(&state.socket, &mut state.batch) => {
                 match recv_result {
-            Ok((bytes_received, _source_addr)) => {
-                // 创建数据包
-                let packet_data = buffer[..bytes_received].to_vec();
-                let capture_time = Utc::now();
-
-                match DataPacket::from_datetime(capture_time, packet_data) {
-                    Ok(packet) => {
-                        // 写入数据包
-                        if let Err(e) = writer.write_packet(&packet) {
-                            error!("Failed to write packet: {}", e);
-
-                            // 立即更新错误统计
-                            if let Ok(mut stats_guard) = stats.lock() {
-                                stats_guard.add_error();
+                    Ok(_) => {
+                        // 未取得内核时间戳时退回用户态时间
+                        let user_time = Utc::now();
+                        let mut packets = 0;
+                        let mut bytes = 0;
+                        let mut dropped = 0;
+                        let mut kernel_drops = 0;
+                        let mut received = 0;
+                        let mut filtered = 0;
+                        let mut over_budget = 0;
+                        let mut reflected = 0;
+                        let mut errors = 0;
+
+                        for (data, meta) in state.batch.iter() {
+                            received += 1;
+                            tracing::trace!("Received {} bytes from {:?}", meta.len, meta.source);
+
+                            // SO_RXQ_OVFL 报告的是累计值，只统计增量
+                            if let Some(total) = meta.kernel_drops {
+                                kernel_drops += u64::from(total.wrapping_sub(state.last_kernel_drops));
+                                state.last_kernel_drops = total;
+                            }
+
+                            // 未通过过滤规则的数据包只计数，不写入
+                            if !state.filter.matches(data, meta.source, state.endpoint.port()) {
+                                filtered += 1;
+                                continue;
+                            }
+
+                            // 超出停止条件配额的数据包不再写入
+                            if !budget.admit(data.len()) {
+                                over_budget += 1;
+                                continue;
+                            }
+                            packets += 1;
+                            bytes += data.len() as u64;
+
+                            let capture_time = meta.kernel_time.unwrap_or(user_time);
+                            if let (Some(reflector), Some(source)) = (&state.reflector, meta.source) {
+                                let echo = reflector.reflect(source, data);
+                                match state.socket.send_to(&echo, source).await {
+                                    Ok(_) => reflected += 1,
+                                    Err(e) => {
+                                        warn!("Failed to reflect packet to {}: {}", source, e);
+                                        errors += 1;
+                                    }
+                                }
                             }
-                        } else {
-                            // 立即更新统计信息
-                            if let Ok(mut stats_guard) = stats.lock() {
-                                stats_guard.update_with_timestamp(bytes_received, capture_time);
+                            if let Some(tracker) = &state.sequence {
+                                if let Ok(mut tracker) = tracker.lock() {
+                                    tracker.observe(meta.source, data);
+                                }
                             }
+                            if let Some(tracker) = &state.latency {
+                                if let Ok(mut tracker) = tracker.lock() {
+                                    tracker.observe(meta.source, data, capture_time);
+                                }
+                            }
+
+                            let packet = ReceivedPacket {
+                                data: data.to_vec(),
+                                capture_time,
+                                meta: PacketMeta {
+                                    source: meta.source,
+                                    local_port: state.endpoint.port(),
+                                    interface: state.interface.clone(),
+                                    endpoint: Some(state.endpoint),
+                                },
+                            };
+                            dropped += enqueue_packet(&queue, packet, state.overflow_policy).await;
                         }
-                    }
-                    Err(e) => {
-                        error!("Failed to create packet: {}", e);
 
-                        // 立即更新错误统计
+                        // 被过滤的流量不计入空闲超时判断
+                        if filtered < received {
+                            budget.record_activity();
+                        }
+
+                        // 每批次只获取一次统计锁
+                        let depth = queue.depth();
                         if let Ok(mut stats_guard) = stats.lock() {
-                            stats_guard.add_error();
+                            stats_guard.add_queue_drops(dropped);
+                            stats_guard.add_kernel_drops(kernel_drops);
+                            stats_guard.add_filtered(filtered);
+                            stats_guard.add_over_budget(over_budget);
+                            stats_guard.add_reflected(reflected);
+                            stats_guard.add_errors(errors);
+                            if let Some(tracker) = &state.sequence {
+                                if let Ok(tracker) = tracker.lock() {
+                                    stats_guard.set_sequence(tracker.stats());
+                                }
+                            }
+                            if let Some(tracker) = &state.latency {
+                                if let Ok(tracker) = tracker.lock() {
+                                    stats_guard.set_latency(tracker.stats());
+                                }
+                            }
+                            stats_guard.update_queue_depth(depth);
+                            if let Some(label) = &state.label {
+                                let channel = stats_guard.channel_mut(label);
+                                channel.packets += packets;
+                                channel.bytes += bytes;
+                                channel.drops += dropped;
+                                channel.kernel_drops += kernel_drops;
+                                channel.filtered += filtered;
+                                channel.errors += errors;
+                            }
                         }
                     }
-                }
-                    }
                     Err(e) => {
                         error!("Failed to receive packet: {}", e);
 
                         // 立即更新错误统计
                         if let Ok(mut stats_guard) = stats.lock() {
                             stats_guard.add_error();
+                            if let Some(label) = &state.label {
+                                stats_guard.channel_mut(label).errors += 1;
+                            }
                         }
                     }
                 }
             },
-            // 定期检查停止状态分支 - 每100ms检查一次
-            _ = sleep(Duration::from_millis(100)) => {
-                if let Ok(state) = transfer_state.lock() {
-                    if matches!(*state, TransferState::Idle) {
-                        break;
-                    }
-                }
-            }
+            // 停止信号分支
+            _ = stop.changed() => break,
         }
     }
-
-    // 完成写入
-    writer.finalize()?;
-
-    // 完成统计信息
-    if let Ok(mut stats_guard) = stats.lock() {
-        stats_guard.finish();
-    }
-
-    // 更新传输状态为完成
-    if let Ok(mut state) = transfer_state.lock() {
-        *state = TransferState::Completed;
-    }
-
-    Ok(())
 }
@@ -1,17 +1,39 @@
 //! 发送器模块 - 处理数据包发送逻辑
 
+use crate::app::config::options::SenderOptions;
 use crate::app::config::types::{
     DataFormat, NetworkType, SenderAppConfig,
 };
 use crate::app::error::types::Result;
 use crate::core::csv::CsvParser;
+use crate::core::network::batch::send_batch;
+use crate::core::network::generator::GeneratorSource;
+use crate::core::network::source::{
+    CaptureFileSource, CsvSource, PacketSource,
+    PcapDatasetSource, SourcePacket,
+};
+use crate::core::network::sys::MAX_BATCH_SIZE;
 use crate::core::network::types::UdpSocketFactory;
+use crate::core::pipeline::fuzz::Fuzzer;
+use crate::core::pipeline::impairment::Impairer;
+use crate::core::pipeline::prefetch::{
+    FeedPoll, PacketFeed, PrefetchBuffer,
+};
+use crate::core::probe::header::HeaderStamper;
+use crate::core::probe::rtt::RttTracker;
 use crate::core::stats::collector::TransferStats;
+use crate::core::stats::report::{
+    dataset_stem, ReportRecorder,
+};
+use crate::core::stats::window::PacketSizes;
 use crate::core::timing::utils::TimingController;
-use pcapfile_io::{PcapReader, ReaderConfig};
-use std::path::PathBuf;
+use chrono::Utc;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use tracing::error;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+use tokio::sync::watch;
 
 /// 传输状态枚举
 #[derive(Debug, Clone)]
@@ -32,6 +54,7 @@ pub async fn run_sender_with_gui_stats(
     interface: Option<String>,
     data_format: DataFormat,
     csv_packet_interval: u64, // CSV发送周期（毫秒）
+    options: SenderOptions,
     stats: Arc<Mutex<TransferStats>>,
     transfer_state: Arc<Mutex<TransferState>>,
 ) -> Result<()> {
@@ -49,9 +72,10 @@ pub async fn run_sender_with_gui_stats(
     config.validate()?;
 
     // 创建UDP发送器
-    let socket =
+    let socket = Arc::new(
         UdpSocketFactory::create_sender(&config.network)
-            .await?;
+            .await?,
+    );
 
     // 初始化时序控制器（始终启用精确时序控制）
     let mut timing_controller = TimingController::new();
@@ -61,188 +85,355 @@ pub async fn run_sender_with_gui_stats(
         *stats_guard = TransferStats::new(); // GUI不需要进度条
     }
 
-    // 预计算目标地址以避免重复字符串格式化
-    let target_addr = format!(
-        "{}:{}",
-        config.network.address, config.network.port
+    // 按间隔采样统计信息，结束时写入数据集旁边或报告目录
+    let report = ReportRecorder::start(
+        &options.report,
+        config
+            .dataset_path
+            .parent()
+            .unwrap_or(Path::new(".")),
+        "sender",
+        dataset_stem(&config.dataset_path),
+        serde_json::json!({
+            "address": address,
+            "port": port,
+            "network_type": format!("{network_type:?}")
+                .to_lowercase(),
+            "interface": config.network.interface,
+            "dataset_path": config.dataset_path,
+            "data_format": config.data_format.to_string(),
+            "csv_packet_interval_ms": csv_packet_interval,
+            "options": options,
+        }),
+        Arc::clone(&stats),
+    );
+
+    // 预计算目标地址
+    let target_addr = SocketAddr::new(
+        config.network.address,
+        config.network.port,
     );
 
+    // 根据数据格式选择数据源（CSV 数据源同时提供字段类型）
+    let mut columns = Vec::new();
+    let source: Box<dyn PacketSource> = match config
+        .data_format
+    {
+        DataFormat::Pcap => {
+            let source_filter =
+                match options.source_filter.trim() {
+                    "" => None,
+                    filter => Some(filter.parse()?),
+                };
+            Box::new(PcapDatasetSource::open(
+                &config.dataset_path,
+                source_filter,
+            )?)
+        }
+        DataFormat::Csv => {
+            let csv_parser =
+                CsvParser::from_file(&config.dataset_path)?;
+            tracing::info!("CSV file loaded: {} rows, packet interval: {}ms", csv_parser.row_count(), csv_packet_interval);
+            columns = csv_parser.column_types();
+            Box::new(CsvSource::new(
+                csv_parser,
+                csv_packet_interval,
+            ))
+        }
+        DataFormat::Generator => {
+            let generator = &options.generator;
+            tracing::info!(
+                "Generating {} traffic: {} payload at {} Mbps for {}s",
+                generator.size,
+                generator.pattern,
+                generator.rate_mbps,
+                generator.duration_secs
+            );
+            Box::new(GeneratorSource::new(
+                generator,
+                options.test_header.stream_id,
+            ))
+        }
+        DataFormat::Capture => {
+            let capture_filter =
+                match options.capture_filter.trim() {
+                    "" => None,
+                    filter => Some(filter.parse()?),
+                };
+            Box::new(CaptureFileSource::open(
+                &config.dataset_path,
+                capture_filter,
+            )?)
+        }
+    };
+
+    // 启用预读时由后台线程提前读取数据包
+    let mut feed = if options.prefetch_packets > 0 {
+        PacketFeed::Prefetch(PrefetchBuffer::spawn(
+            source,
+            options.prefetch_packets,
+            options.prefetch_bytes,
+            Arc::clone(&stats),
+        )?)
+    } else {
+        PacketFeed::Direct(source)
+    };
+
+    let batch_size =
+        options.batch_size.clamp(1, MAX_BATCH_SIZE);
+    let mut batch: Vec<SourcePacket> =
+        Vec::with_capacity(batch_size);
+    let mut pending: Option<SourcePacket> = None;
+
+    // 启用测试头时为每个数据包插入序号，测量往返时延时必须带发送时间
+    let rtt_enabled = options.rtt.enabled;
+    let mut stamper = (options.test_header.enabled
+        || rtt_enabled)
+        .then(|| {
+            let stamper = HeaderStamper::new(
+                options.test_header.stream_id,
+            );
+            if options.test_header.timestamp || rtt_enabled
+            {
+                stamper.with_timestamps()
+            } else {
+                stamper
+            }
+        });
+
+    // 启用模糊测试时在插入测试头之前变异数据包
+    let mut fuzzer = Fuzzer::from_options(
+        &options.fuzz,
+        &columns,
+        config
+            .dataset_path
+            .parent()
+            .unwrap_or(Path::new(".")),
+        &dataset_stem(&config.dataset_path),
+    )?;
+
+    // 启用损伤模拟时在测试头之后、发送之前施加损伤
+    let mut impairer =
+        Impairer::from_options(&options.impairment);
+    let mut impaired: Vec<SourcePacket> = Vec::new();
+    if let (Some(impairer), Ok(mut stats_guard)) =
+        (&impairer, stats.lock())
+    {
+        stats_guard.set_impairment(impairer.stats());
+    }
+
+    // 测量往返时延时在后台接收反射器回送的数据包
+    let (echo_stop_tx, echo_stop_rx) =
+        watch::channel(false);
+    let rtt = rtt_enabled.then(|| {
+        let tracker = Arc::new(Mutex::new(
+            RttTracker::new(options.test_header.stream_id),
+        ));
+        let listener = tokio::spawn(run_echo_listener(
+            Arc::clone(&socket),
+            Arc::clone(&tracker),
+            echo_stop_rx,
+        ));
+        (tracker, listener)
+    });
+    let mut stopped_by_user = false;
+
     // 基于时间的停止状态检查
     let mut last_stop_check = std::time::Instant::now();
     let stop_check_interval =
         std::time::Duration::from_millis(100);
 
-    // 根据数据格式选择不同的处理方式
-    match config.data_format {
-        DataFormat::Pcap => {
-            // PCAP数据集处理
-            let dataset_path = &config.dataset_path;
-            let dataset_name = dataset_path
-                .file_name()
-                .and_then(|name| name.to_str())
-                .unwrap_or("dataset");
-
-            let mut reader = PcapReader::new_with_config(
-                dataset_path
-                    .parent()
-                    .unwrap_or(dataset_path),
-                dataset_name,
-                ReaderConfig::default(),
-            )?;
-
-            // 获取数据集信息
-            let _dataset_info =
-                reader.get_dataset_info()?;
-
-            // 读取并发送数据包
-            while let Some(packet) = reader.read_packet()? {
-                // 每100ms检查一次停止状态
-                if last_stop_check.elapsed()
-                    >= stop_check_interval
-                {
-                    if let Ok(state) = transfer_state.lock()
-                    {
-                        if matches!(
-                            *state,
-                            TransferState::Idle
-                        ) {
-                            tracing::info!("Sender received stop signal, breaking loop");
-                            break;
-                        }
-                    }
-                    last_stop_check =
-                        std::time::Instant::now();
+    loop {
+        // 每100ms检查一次停止状态
+        if last_stop_check.elapsed() >= stop_check_interval
+        {
+            if let Ok(state) = transfer_state.lock() {
+                if matches!(*state, TransferState::Idle) {
+                    tracing::info!("Sender received stop signal, breaking loop");
+                    stopped_by_user = true;
+                    break;
                 }
+            }
+            last_stop_check = std::time::Instant::now();
+        }
 
-                let packet_data = &packet.packet.data;
-                let packet_time = packet.capture_time();
+        let first = match pending.take() {
+            Some(packet) => packet,
+            None => match feed.next().await? {
+                Some(packet) => packet,
+                None => break,
+            },
+        };
 
-                // 时序控制（精确重放）
+        // 时序控制（精确重放），等待期间发出到期的延迟数据包
+        loop {
+            let Some(release) = impairer
+                .as_ref()
+                .and_then(Impairer::next_release)
+            else {
                 timing_controller
-                    .wait_for_packet_time(packet_time)
+                    .wait_for_packet_time(first.timestamp)
                     .await;
-
-                // 发送数据包
-                match socket
-                    .send_to(packet_data, &target_addr)
-                    .await
-                {
-                    Ok(bytes_sent) => {
-                        // 立即更新统计信息
-                        if let Ok(mut stats_guard) =
-                            stats.lock()
-                        {
-                            stats_guard
-                                .update_with_timestamp(
-                                    bytes_sent,
-                                    packet_time,
-                                );
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to send packet: {}",
-                            e
-                        );
-
-                        // 立即更新错误统计
-                        if let Ok(mut stats_guard) =
-                            stats.lock()
-                        {
-                            stats_guard.add_error();
-                        }
+                break;
+            };
+            tokio::select! {
+                _ = timing_controller.wait_for_packet_time(first.timestamp) => break,
+                _ = tokio::time::sleep_until(release.into()) => {
+                    if let Some(impairer) = &mut impairer {
+                        impaired.clear();
+                        impairer.release_due(Instant::now(), &mut impaired);
+                        send_packets(&socket, target_addr, &impaired, &stats, |_| {}).await;
                     }
                 }
             }
         }
-        DataFormat::Csv => {
-            // CSV数据处理
-            let csv_parser =
-                CsvParser::from_file(&config.dataset_path)?;
-            let row_count = csv_parser.row_count();
-
-            tracing::info!("CSV file loaded: {} rows, packet interval: {}ms", row_count, csv_packet_interval);
 
-            // 发送CSV数据包
-            for row_index in 0..row_count {
-                // 每100ms检查一次停止状态
-                if last_stop_check.elapsed()
-                    >= stop_check_interval
+        // 将发送时间已过的后续数据包合并到同一批次
+        batch.clear();
+        batch.push(first);
+        while batch.len() < batch_size {
+            match feed.poll_next()? {
+                FeedPoll::Packet(packet)
+                    if timing_controller
+                        .is_due(packet.timestamp) =>
                 {
-                    if let Ok(state) = transfer_state.lock()
-                    {
-                        if matches!(
-                            *state,
-                            TransferState::Idle
-                        ) {
-                            tracing::info!("Sender received stop signal, breaking loop");
-                            break;
-                        }
-                    }
-                    last_stop_check =
-                        std::time::Instant::now();
+                    batch.push(packet);
                 }
+                FeedPoll::Packet(packet) => {
+                    pending = Some(packet);
+                    break;
+                }
+                // 预读缓冲区暂时为空时不等待，先发送已合并的数据包
+                FeedPoll::Pending | FeedPoll::End => break,
+            }
+        }
 
-                // 生成数据包
-                let csv_packet = csv_parser
-                    .generate_packet(row_index)?;
-                let packet_data = &csv_packet.data;
-                let packet_time = csv_packet.timestamp;
-
-                tracing::info!(
-                    "Sending row {}: {} bytes",
-                    row_index,
-                    packet_data.len()
+        // 变异数据包、插入测试头并施加网络损伤
+        if let Some(fuzzer) = &mut fuzzer {
+            for packet in &mut batch {
+                fuzzer.mutate(packet)?;
+            }
+        }
+        if let Some(stamper) = &mut stamper {
+            for packet in &mut batch {
+                packet.data = stamper.stamp(&packet.data);
+            }
+        }
+        if let Some(impairer) = &mut impairer {
+            let now = Instant::now();
+            impaired.clear();
+            for packet in batch.drain(..) {
+                impairer.process(
+                    packet,
+                    now,
+                    &mut impaired,
                 );
+            }
+            impairer.release_due(now, &mut impaired);
+            std::mem::swap(&mut batch, &mut impaired);
+        }
 
-                // 发送数据包
-                match socket
-                    .send_to(packet_data, &target_addr)
-                    .await
+        // 发送数据包
+        send_packets(
+            &socket,
+            target_addr,
+            &batch,
+            &stats,
+            |stats_guard| {
+                if let (Some((tracker, _)), Some(stamper)) =
+                    (&rtt, &stamper)
                 {
-                    Ok(bytes_sent) => {
-                        // 立即更新统计信息
-                        if let Ok(mut stats_guard) =
-                            stats.lock()
-                        {
-                            stats_guard
-                                .update_with_timestamp(
-                                    bytes_sent,
-                                    packet_time,
-                                );
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            "Failed to send packet: {}",
-                            e
+                    if let Ok(tracker) = tracker.lock() {
+                        stats_guard.set_rtt(
+                            tracker
+                                .stats(stamper.stamped()),
                         );
-
-                        // 立即更新错误统计
-                        if let Ok(mut stats_guard) =
-                            stats.lock()
-                        {
-                            stats_guard.add_error();
-                        }
                     }
                 }
-
-                // CSV数据发送间隔控制（除了最后一行）
-                if row_index < row_count - 1 {
-                    tokio::time::sleep(
-                        tokio::time::Duration::from_millis(
-                            csv_packet_interval,
-                        ),
-                    )
-                    .await;
+                if let Some(impairer) = &impairer {
+                    stats_guard
+                        .set_impairment(impairer.stats());
+                }
+                if let Some(fuzzer) = &fuzzer {
+                    stats_guard
+                        .set_fuzzed(fuzzer.mutated());
                 }
+            },
+        )
+        .await;
+    }
+
+    // 数据源结束后发出乱序推迟与延迟中的数据包
+    if let Some(impairer) = &mut impairer {
+        impaired.clear();
+        impairer.flush_held(Instant::now(), &mut impaired);
+        while !stopped_by_user {
+            send_packets(
+                &socket,
+                target_addr,
+                &impaired,
+                &stats,
+                |_| {},
+            )
+            .await;
+            let Some(release) = impairer.next_release()
+            else {
+                break;
+            };
+            tokio::time::sleep_until(release.into()).await;
+            impaired.clear();
+            impairer
+                .release_due(Instant::now(), &mut impaired);
+            if let Ok(state) = transfer_state.lock() {
+                stopped_by_user =
+                    matches!(*state, TransferState::Idle);
             }
         }
+        if let Ok(mut stats_guard) = stats.lock() {
+            stats_guard.set_impairment(impairer.stats());
+        }
+    }
+
+    if let Some(fuzzer) = fuzzer {
+        fuzzer.finish()?;
+    }
+
+    // 等待最后一批数据包的回送后停止接收
+    if let Some((tracker, listener)) = rtt {
+        if !stopped_by_user {
+            tokio::time::sleep(Duration::from_millis(
+                options.rtt.timeout_ms,
+            ))
+            .await;
+        }
+        let _ = echo_stop_tx.send(true);
+        let _ = listener.await;
+
+        let sent = stamper
+            .as_ref()
+            .map_or(0, HeaderStamper::stamped);
+        if let (Ok(tracker), Ok(mut stats_guard)) =
+            (tracker.lock(), stats.lock())
+        {
+            stats_guard.set_rtt(tracker.stats(sent));
+        }
     }
 
     // 标记统计信息完成并更新传输状态为完成
     if let Ok(mut stats_guard) = stats.lock() {
         stats_guard.finish();
+        tracing::info!(
+            "Sender summary: {}",
+            stats_guard.summary()
+        );
+    }
+    if let Some(report) = report {
+        if let Err(e) = report.finish().await {
+            tracing::error!(
+                "Failed to write report: {}",
+                e
+            );
+        }
     }
     if let Ok(mut state) = transfer_state.lock() {
         *state = TransferState::Completed;
@@ -250,3 +441,74 @@ pub async fn run_sender_with_gui_stats(
 
     Ok(())
 }
+
+/// 发送一批数据包并更新统计信息
+///
+/// 每批次只获取一次统计锁，`update` 在同一次加锁中更新其他统计。
+async fn send_packets(
+    socket: &UdpSocket,
+    target: SocketAddr,
+    batch: &[SourcePacket],
+    stats: &Mutex<TransferStats>,
+    update: impl FnOnce(&mut TransferStats),
+) {
+    let payloads: Vec<&[u8]> = batch
+        .iter()
+        .map(|packet| packet.data.as_slice())
+        .collect();
+    let result =
+        send_batch(socket, target, &payloads).await;
+
+    // 在加锁前汇总已发送数据包的包长与时间范围
+    // （失败的数据包可能位于批次中间，损伤重排后时间戳也未必有序）
+    let mut sizes = PacketSizes::default();
+    let mut span = None;
+    for packet in result.delivered(batch) {
+        sizes.record(packet.data.len());
+        let (first, last) = span.get_or_insert((
+            packet.timestamp,
+            packet.timestamp,
+        ));
+        *first = (*first).min(packet.timestamp);
+        *last = (*last).max(packet.timestamp);
+    }
+
+    if let Ok(mut stats_guard) = stats.lock() {
+        stats_guard.record_packet_sizes(&sizes);
+        if let Some((first, last)) = span {
+            stats_guard.update_batch(
+                result.sent,
+                result.bytes as u64,
+                first,
+                last,
+            );
+        }
+        stats_guard.add_errors(result.errors());
+        update(&mut stats_guard);
+    }
+}
+
+/// 接收反射器回送的数据包并记录往返时延，直到收到停止信号
+async fn run_echo_listener(
+    socket: Arc<UdpSocket>,
+    tracker: Arc<Mutex<RttTracker>>,
+    mut stop: watch::Receiver<bool>,
+) {
+    let mut buffer = vec![0u8; 65536];
+    loop {
+        tokio::select! {
+            result = socket.recv_from(&mut buffer) => match result {
+                Ok((len, _)) => {
+                    let receive_time = Utc::now();
+                    if let Ok(mut tracker) = tracker.lock() {
+                        tracker.observe(&buffer[..len], receive_time);
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to receive echo: {}", e);
+                }
+            },
+            _ = stop.changed() => break,
+        }
+    }
+}
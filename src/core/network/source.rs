@@ -0,0 +1,256 @@
+//! 数据源模块 - 为发送循环提供带时间戳的数据包
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::capture::filter::CaptureFilter;
+use crate::core::capture::packet::udp_datagram;
+use crate::core::capture::reader::CaptureReader;
+use crate::core::csv::parser::row_time;
+use crate::core::csv::CsvParser;
+use crate::core::dataset::sidecar::{
+    SidecarReader, SourceFilter,
+};
+use chrono::{DateTime, Utc};
+use pcapfile_io::{PcapReader, ReaderConfig};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+/// 待发送的数据包
+#[derive(Debug, Clone)]
+pub struct SourcePacket {
+    /// 数据包内容
+    pub data: Vec<u8>,
+    /// 计划发送时间（用于时序控制）
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 数据包来源
+pub trait PacketSource: Send {
+    /// 读取下一个数据包，返回 `None` 表示数据源已结束
+    fn next_packet(
+        &mut self,
+    ) -> Result<Option<SourcePacket>>;
+}
+
+/// PCAP 数据集数据源
+pub struct PcapDatasetSource {
+    reader: PcapReader,
+    // 按来源地址过滤时使用的元数据读取器
+    filter: Option<(SourceFilter, SidecarReader)>,
+}
+
+impl PcapDatasetSource {
+    /// 打开数据集目录
+    ///
+    /// 指定 `source_filter` 时只重放来自该地址的数据包，
+    /// 需要数据集带有接收时记录的元数据文件。
+    pub fn open(
+        dataset_path: &Path,
+        source_filter: Option<SourceFilter>,
+    ) -> Result<Self> {
+        let dataset_name = dataset_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or("dataset");
+
+        let mut reader = PcapReader::new_with_config(
+            dataset_path.parent().unwrap_or(dataset_path),
+            dataset_name,
+            ReaderConfig::default(),
+        )?;
+
+        // 获取数据集信息
+        let _dataset_info = reader.get_dataset_info()?;
+
+        let filter = match source_filter {
+            Some(source_filter) => {
+                let sidecar =
+                    SidecarReader::open(dataset_path)?
+                        .ok_or_else(|| {
+                            DataTransferError::validation(
+                        "Source Filter",
+                        "Dataset has no packet metadata",
+                    )
+                        })?;
+                Some((source_filter, sidecar))
+            }
+            None => None,
+        };
+
+        Ok(Self { reader, filter })
+    }
+}
+
+impl PacketSource for PcapDatasetSource {
+    fn next_packet(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        while let Some(packet) =
+            self.reader.read_packet()?
+        {
+            // 元数据与数据包按相同顺序逐条对应
+            if let Some((source_filter, sidecar)) =
+                &mut self.filter
+            {
+                let matched = sidecar
+                    .next_record()?
+                    .is_some_and(|record| {
+                        source_filter.matches(&record.meta)
+                    });
+                if !matched {
+                    continue;
+                }
+            }
+
+            let timestamp = packet.capture_time();
+            return Ok(Some(SourcePacket {
+                data: packet.packet.data,
+                timestamp,
+            }));
+        }
+        Ok(None)
+    }
+}
+
+/// CSV 数据源，按时间戳列或固定周期为每行生成计划发送时间
+pub struct CsvSource {
+    parser: CsvParser,
+    row_index: usize,
+    start_time: Option<DateTime<Utc>>,
+    interval: chrono::Duration,
+}
+
+impl CsvSource {
+    /// 创建 CSV 数据源
+    ///
+    /// # 参数
+    /// * `parser` - 已加载的 CSV 解析器
+    /// * `packet_interval_ms` - 发送周期（毫秒）
+    pub fn new(
+        parser: CsvParser,
+        packet_interval_ms: u64,
+    ) -> Self {
+        Self {
+            parser,
+            row_index: 0,
+            start_time: None,
+            // 配置验证已限制周期上限；超出范围的周期在推算第二行时间时报错
+            interval: i64::try_from(packet_interval_ms)
+                .ok()
+                .and_then(
+                    chrono::Duration::try_milliseconds,
+                )
+                .unwrap_or(chrono::Duration::MAX),
+        }
+    }
+}
+
+impl PacketSource for CsvSource {
+    fn next_packet(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        if self.row_index >= self.parser.row_count() {
+            return Ok(None);
+        }
+
+        // 生成数据包
+        let csv_packet =
+            self.parser.generate_packet(self.row_index)?;
+        // 带时间戳列时按列中的时间重放；否则以首行生成时间为基准，
+        // 按发送周期推算后续行的发送时间
+        let timestamp = if self.parser.has_timestamps() {
+            csv_packet.timestamp
+        } else {
+            let start_time = *self
+                .start_time
+                .get_or_insert(csv_packet.timestamp);
+            row_time(
+                start_time,
+                self.interval,
+                self.row_index,
+            )?
+        };
+
+        tracing::info!(
+            "Sending row {}: {} bytes",
+            self.row_index,
+            csv_packet.data.len()
+        );
+
+        self.row_index += 1;
+        Ok(Some(SourcePacket {
+            data: csv_packet.data,
+            timestamp,
+        }))
+    }
+}
+
+/// 抓包文件数据源，重放 pcap / pcapng 文件中 UDP 数据报的负载
+pub struct CaptureFileSource {
+    reader: CaptureReader<BufReader<File>>,
+    filter: Option<CaptureFilter>,
+    frames: u64,
+    non_udp: u64,
+    filtered: u64,
+    finished: bool,
+}
+
+impl CaptureFileSource {
+    /// 打开抓包文件
+    ///
+    /// 指定 `filter` 时只重放满足表达式的数据报，
+    /// 非 UDP、分片或链路类型不支持的帧总是被跳过。
+    pub fn open(
+        path: &Path,
+        filter: Option<CaptureFilter>,
+    ) -> Result<Self> {
+        Ok(Self {
+            reader: CaptureReader::open(path)?,
+            filter,
+            frames: 0,
+            non_udp: 0,
+            filtered: 0,
+            finished: false,
+        })
+    }
+}
+
+impl PacketSource for CaptureFileSource {
+    fn next_packet(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        while let Some(frame) = self.reader.next_frame()? {
+            self.frames += 1;
+            let Some(datagram) =
+                udp_datagram(frame.link_type, &frame.data)
+            else {
+                self.non_udp += 1;
+                continue;
+            };
+            if let Some(filter) = &self.filter {
+                if !filter
+                    .matches(frame.interface, &datagram)
+                {
+                    self.filtered += 1;
+                    continue;
+                }
+            }
+
+            return Ok(Some(SourcePacket {
+                data: datagram.payload.to_vec(),
+                timestamp: frame.timestamp,
+            }));
+        }
+
+        if !self.finished {
+            self.finished = true;
+            tracing::info!(
+                "Capture file finished: {} frames, {} not UDP, {} filtered out",
+                self.frames,
+                self.non_udp,
+                self.filtered
+            );
+        }
+        Ok(None)
+    }
+}
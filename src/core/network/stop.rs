@@ -0,0 +1,290 @@
+//! 接收停止条件模块
+//!
+//! 在多个接收任务之间共享包数与字节数配额，并检查时长和空闲超时，
+//! 任一条件触发时记录停止原因并通知接收器结束。
+
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use crate::app::config::options::ReceiverOptions;
+
+/// 接收停止原因
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    /// 用户手动停止
+    User,
+    /// 达到最大包数
+    MaxPackets(u64),
+    /// 达到最大字节数
+    MaxBytes(u64),
+    /// 达到最大接收时长
+    MaxDuration(Duration),
+    /// 收到首包后超过空闲时间没有新数据
+    IdleTimeout(Duration),
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            StopReason::User => {
+                write!(f, "Stopped by user")
+            }
+            StopReason::MaxPackets(limit) => {
+                write!(f, "Packet limit reached ({limit})")
+            }
+            StopReason::MaxBytes(limit) => {
+                write!(f, "Byte limit reached ({limit})")
+            }
+            StopReason::MaxDuration(limit) => write!(
+                f,
+                "Duration limit reached ({}s)",
+                limit.as_secs()
+            ),
+            StopReason::IdleTimeout(limit) => write!(
+                f,
+                "No traffic for {}s",
+                limit.as_secs()
+            ),
+        }
+    }
+}
+
+/// 接收配额，由所有接收任务共享
+pub struct ReceiveBudget {
+    max_packets: u64,
+    max_bytes: u64,
+    max_duration: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    packets: AtomicU64,
+    bytes: AtomicU64,
+    started: Instant,
+    last_packet: Mutex<Option<Instant>>,
+    reason: Mutex<Option<StopReason>>,
+    exhausted: Notify,
+}
+
+impl ReceiveBudget {
+    /// 根据接收器选项创建配额（0 表示不限制）
+    pub fn new(options: &ReceiverOptions) -> Self {
+        let seconds = |value: u64| {
+            (value > 0).then(|| Duration::from_secs(value))
+        };
+        Self {
+            max_packets: options.max_packets,
+            max_bytes: options.max_bytes,
+            max_duration: seconds(
+                options.max_duration_secs,
+            ),
+            idle_timeout: seconds(
+                options.idle_timeout_secs,
+            ),
+            packets: AtomicU64::new(0),
+            bytes: AtomicU64::new(0),
+            started: Instant::now(),
+            last_packet: Mutex::new(None),
+            reason: Mutex::new(None),
+            exhausted: Notify::new(),
+        }
+    }
+
+    /// 为一个数据包申请配额
+    ///
+    /// 超出包数或字节数限制时返回 `false`，该数据包不应写入，
+    /// 也不占用配额。
+    pub fn admit(&self, len: usize) -> bool {
+        // 只在配额足够时预留，被拒绝的数据包不计入
+        let Ok(packets) = self.packets.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |packets| {
+                (self.max_packets == 0
+                    || packets < self.max_packets)
+                    .then_some(packets + 1)
+            },
+        ) else {
+            self.trigger(StopReason::MaxPackets(
+                self.max_packets,
+            ));
+            return false;
+        };
+
+        let len = len as u64;
+        let reserved = self.bytes.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |bytes| {
+                let total = bytes.saturating_add(len);
+                (self.max_bytes == 0
+                    || total <= self.max_bytes)
+                    .then_some(total)
+            },
+        );
+        if reserved.is_err() {
+            // 归还已预留的包数配额
+            self.packets.fetch_sub(1, Ordering::Relaxed);
+            self.trigger(StopReason::MaxBytes(
+                self.max_bytes,
+            ));
+            return false;
+        }
+
+        // 恰好用完配额时立即停止，无需等待下一个数据包
+        if self.max_packets > 0
+            && packets + 1 == self.max_packets
+        {
+            self.trigger(StopReason::MaxPackets(
+                self.max_packets,
+            ));
+        }
+        true
+    }
+
+    /// 记录收到数据（用于空闲超时判断）
+    pub fn record_activity(&self) {
+        if let Ok(mut last) = self.last_packet.lock() {
+            *last = Some(Instant::now());
+        }
+    }
+
+    /// 检查时长与空闲超时条件
+    pub fn check_timers(&self) {
+        if let Some(limit) = self.max_duration {
+            if self.started.elapsed() >= limit {
+                self.trigger(StopReason::MaxDuration(
+                    limit,
+                ));
+            }
+        }
+
+        if let Some(limit) = self.idle_timeout {
+            let idle = self
+                .last_packet
+                .lock()
+                .ok()
+                .and_then(|last| *last)
+                .map(|last| last.elapsed() >= limit)
+                .unwrap_or(false);
+            if idle {
+                self.trigger(StopReason::IdleTimeout(
+                    limit,
+                ));
+            }
+        }
+    }
+
+    /// 记录停止原因（只保留最先触发的原因）
+    pub fn trigger(&self, reason: StopReason) {
+        if let Ok(mut slot) = self.reason.lock() {
+            if slot.is_none() {
+                *slot = Some(reason);
+                self.exhausted.notify_waiters();
+            }
+        }
+    }
+
+    /// 已触发的停止原因
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.reason
+            .lock()
+            .ok()
+            .and_then(|slot| slot.clone())
+    }
+
+    /// 等待任一配额条件触发
+    pub async fn exhausted(&self) {
+        let notified = self.exhausted.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.stop_reason().is_some() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budget(
+        max_packets: u64,
+        max_bytes: u64,
+    ) -> ReceiveBudget {
+        ReceiveBudget::new(&ReceiverOptions {
+            max_packets,
+            max_bytes,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_packet_limit() {
+        let budget = budget(2, 0);
+        assert!(budget.admit(10));
+        assert!(budget.stop_reason().is_none());
+        assert!(budget.admit(10));
+        assert_eq!(
+            budget.stop_reason(),
+            Some(StopReason::MaxPackets(2))
+        );
+        assert!(!budget.admit(10));
+    }
+
+    #[test]
+    fn test_byte_limit_rejects_crossing_packet() {
+        let budget = budget(0, 25);
+        assert!(budget.admit(10));
+        assert!(budget.admit(10));
+        assert!(!budget.admit(10));
+        assert_eq!(
+            budget.stop_reason(),
+            Some(StopReason::MaxBytes(25))
+        );
+    }
+
+    #[test]
+    fn test_rejected_packet_keeps_budget() {
+        // 因字节数被拒绝的数据包不占用包数配额
+        let budget = budget(2, 15);
+        assert!(budget.admit(10));
+        assert!(!budget.admit(10));
+        assert!(budget.admit(5));
+        assert_eq!(
+            budget.packets.load(Ordering::Relaxed),
+            2
+        );
+        assert_eq!(
+            budget.bytes.load(Ordering::Relaxed),
+            15
+        );
+        assert!(!budget.admit(0));
+    }
+
+    #[test]
+    fn test_idle_timeout_starts_after_first_packet() {
+        let budget = ReceiveBudget::new(&ReceiverOptions {
+            idle_timeout_secs: 1,
+            ..Default::default()
+        });
+
+        // 尚未收到数据包时不触发
+        budget.check_timers();
+        assert!(budget.stop_reason().is_none());
+
+        *budget.last_packet.lock().unwrap() =
+            Some(Instant::now() - Duration::from_secs(2));
+        budget.check_timers();
+        assert_eq!(
+            budget.stop_reason(),
+            Some(StopReason::IdleTimeout(
+                Duration::from_secs(1)
+            ))
+        );
+    }
+}
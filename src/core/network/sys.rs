@@ -0,0 +1,365 @@
+//! 平台相关的套接字系统调用封装
+//!
+//! Linux 下通过 sendmmsg/recvmmsg 实现单次系统调用收发多个数据报
+
+use chrono::{DateTime, Utc};
+use std::io;
+use std::net::SocketAddr;
+
+#[cfg(target_os = "linux")]
+use socket2::SockAddr;
+#[cfg(target_os = "linux")]
+use std::os::fd::RawFd;
+
+/// 单批最大数据报数量（对应内核 UIO_MAXIOV 限制）
+pub const MAX_BATCH_SIZE: usize = 1024;
+
+/// 每个数据报的控制消息缓冲区（按 8 字节对齐）
+#[cfg(target_os = "linux")]
+pub type ControlBuffer = [u64; 32];
+
+/// 批量接收时单个数据报的元信息
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RecvMeta {
+    /// 数据报长度
+    pub len: usize,
+    /// 来源地址
+    pub source: Option<SocketAddr>,
+    /// 内核（或网卡硬件）记录的接收时间
+    pub kernel_time: Option<DateTime<Utc>>,
+    /// SO_RXQ_OVFL 报告的套接字累计溢出丢包数
+    pub kernel_drops: Option<u32>,
+}
+
+/// 接收时间戳来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxTimestamping {
+    /// 使用 SO_TIMESTAMPNS 获取内核软件时间戳
+    Software,
+    /// 使用 SO_TIMESTAMPING 获取网卡硬件时间戳（网卡需先由
+    /// [`enable_nic_rx_timestamps`] 开启）
+    Hardware,
+}
+
+/// 启用接收时间戳
+#[cfg(target_os = "linux")]
+pub fn enable_rx_timestamps(
+    fd: RawFd,
+    mode: RxTimestamping,
+) -> io::Result<()> {
+    match mode {
+        RxTimestamping::Software => {
+            set_socket_option(fd, libc::SO_TIMESTAMPNS, 1)
+        }
+        RxTimestamping::Hardware => set_socket_option(
+            fd,
+            libc::SO_TIMESTAMPING,
+            (libc::SOF_TIMESTAMPING_RX_HARDWARE
+                | libc::SOF_TIMESTAMPING_RAW_HARDWARE
+                | libc::SOF_TIMESTAMPING_RX_SOFTWARE
+                | libc::SOF_TIMESTAMPING_SOFTWARE)
+                as libc::c_int,
+        ),
+    }
+}
+
+/// 通过 SIOCSHWTSTAMP 让网卡为全部接收数据包记录硬件时间戳
+///
+/// 需要 CAP_NET_ADMIN 权限且驱动支持硬件时间戳；未配置网卡时
+/// SO_TIMESTAMPING 只会得到软件时间戳。
+#[cfg(target_os = "linux")]
+pub fn enable_nic_rx_timestamps(
+    fd: RawFd,
+    interface: &str,
+) -> io::Result<()> {
+    let name = interface.as_bytes();
+    if name.is_empty() || name.len() >= libc::IFNAMSIZ {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("Invalid interface name: {interface}"),
+        ));
+    }
+
+    let mut config = libc::hwtstamp_config {
+        flags: 0,
+        tx_type: libc::HWTSTAMP_TX_OFF as libc::c_int,
+        rx_filter: libc::HWTSTAMP_FILTER_ALL as libc::c_int,
+    };
+    // SAFETY: ifreq 为纯数据结构，全零是合法的初始值
+    let mut request: libc::ifreq =
+        unsafe { std::mem::zeroed() };
+    for (dst, src) in request.ifr_name.iter_mut().zip(name)
+    {
+        *dst = *src as libc::c_char;
+    }
+    request.ifr_ifru.ifru_data = &mut config
+        as *mut libc::hwtstamp_config
+        as *mut libc::c_char;
+
+    // SAFETY: request 与其指向的 config 在调用期间有效
+    let result = unsafe {
+        libc::ioctl(
+            fd,
+            libc::SIOCSHWTSTAMP as _,
+            &mut request as *mut libc::ifreq,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    // 驱动可能只支持部分过滤方式，此时不会为普通 UDP 包打时间戳
+    if config.rx_filter
+        != libc::HWTSTAMP_FILTER_ALL as libc::c_int
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!(
+                "NIC only supports receive filter {}",
+                config.rx_filter
+            ),
+        ));
+    }
+    Ok(())
+}
+
+/// 启用 SO_RXQ_OVFL，在控制消息中报告套接字溢出丢包数
+#[cfg(target_os = "linux")]
+pub fn enable_rxq_ovfl(fd: RawFd) -> io::Result<()> {
+    set_socket_option(fd, libc::SO_RXQ_OVFL, 1)
+}
+
+/// 设置 SOL_SOCKET 层的整数套接字选项
+#[cfg(target_os = "linux")]
+fn set_socket_option(
+    fd: RawFd,
+    option: libc::c_int,
+    value: libc::c_int,
+) -> io::Result<()> {
+    // SAFETY: value 在调用期间有效，长度与类型一致
+    let result = unsafe {
+        libc::setsockopt(
+            fd,
+            libc::SOL_SOCKET,
+            option,
+            &value as *const libc::c_int
+                as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>()
+                as libc::socklen_t,
+        )
+    };
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
+/// 将 timespec 转换为 UTC 时间，全零表示未提供
+#[cfg(target_os = "linux")]
+fn timespec_to_datetime(
+    ts: &libc::timespec,
+) -> Option<DateTime<Utc>> {
+    if ts.tv_sec == 0 && ts.tv_nsec == 0 {
+        return None;
+    }
+    DateTime::from_timestamp(ts.tv_sec, ts.tv_nsec as u32)
+}
+
+/// 解析控制消息中的接收时间戳与溢出计数
+#[cfg(target_os = "linux")]
+fn parse_control_messages(
+    header: &libc::msghdr,
+    meta: &mut RecvMeta,
+) {
+    // SAFETY: 控制消息缓冲区由内核按 cmsg 格式填充，
+    // CMSG_* 宏保证遍历不超出 msg_controllen
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(header);
+        while !cmsg.is_null() {
+            let level = (*cmsg).cmsg_level;
+            let kind = (*cmsg).cmsg_type;
+            let data = libc::CMSG_DATA(cmsg);
+
+            if level == libc::SOL_SOCKET {
+                if kind == libc::SCM_TIMESTAMPNS {
+                    let ts = std::ptr::read_unaligned(
+                        data as *const libc::timespec,
+                    );
+                    meta.kernel_time =
+                        timespec_to_datetime(&ts);
+                } else if kind == libc::SCM_TIMESTAMPING {
+                    // [0] 软件时间戳，[2] 原始硬件时间戳
+                    let ts = std::ptr::read_unaligned(
+                        data as *const [libc::timespec; 3],
+                    );
+                    meta.kernel_time =
+                        timespec_to_datetime(&ts[2])
+                            .or_else(|| {
+                                timespec_to_datetime(&ts[0])
+                            });
+                } else if kind == libc::SO_RXQ_OVFL {
+                    meta.kernel_drops =
+                        Some(std::ptr::read_unaligned(
+                            data as *const u32,
+                        ));
+                }
+            }
+
+            cmsg = libc::CMSG_NXTHDR(header, cmsg);
+        }
+    }
+}
+
+/// 使用 sendmmsg 向同一目标发送多个数据报，返回实际发送的数据报数量
+#[cfg(target_os = "linux")]
+pub fn sendmmsg(
+    fd: RawFd,
+    target: &SocketAddr,
+    payloads: &[&[u8]],
+) -> io::Result<usize> {
+    if payloads.is_empty() {
+        return Ok(0);
+    }
+
+    let target = SockAddr::from(*target);
+    let mut iovecs: Vec<libc::iovec> = payloads
+        .iter()
+        .map(|payload| libc::iovec {
+            iov_base: payload.as_ptr() as *mut libc::c_void,
+            iov_len: payload.len(),
+        })
+        .collect();
+
+    let mut messages: Vec<libc::mmsghdr> = iovecs
+        .iter_mut()
+        .map(|iovec| {
+            // SAFETY: mmsghdr 为纯 C 结构体，全零是合法的初始值
+            let mut message: libc::mmsghdr =
+                unsafe { std::mem::zeroed() };
+            message.msg_hdr.msg_name =
+                target.as_ptr() as *mut libc::c_void;
+            message.msg_hdr.msg_namelen = target.len();
+            message.msg_hdr.msg_iov = iovec;
+            message.msg_hdr.msg_iovlen = 1;
+            message
+        })
+        .collect();
+
+    // SAFETY: messages 中的指针在调用期间均指向有效内存
+    let sent = unsafe {
+        libc::sendmmsg(
+            fd,
+            messages.as_mut_ptr(),
+            messages.len() as libc::c_uint,
+            0,
+        )
+    };
+
+    if sent < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(sent as usize)
+    }
+}
+
+/// 使用 recvmmsg 非阻塞地接收多个数据报，返回实际接收的数据报数量
+///
+/// 提供 `controls` 时同时接收控制消息（时间戳、溢出计数）。
+#[cfg(target_os = "linux")]
+pub fn recvmmsg(
+    fd: RawFd,
+    buffers: &mut [Vec<u8>],
+    metas: &mut [RecvMeta],
+    controls: &mut [ControlBuffer],
+) -> io::Result<usize> {
+    let count = buffers.len().min(metas.len());
+    if count == 0 {
+        return Ok(0);
+    }
+
+    // SAFETY: sockaddr_storage 为纯 C 结构体，全零是合法的初始值
+    let mut addresses: Vec<libc::sockaddr_storage> =
+        vec![unsafe { std::mem::zeroed() }; count];
+    let mut iovecs: Vec<libc::iovec> = buffers[..count]
+        .iter_mut()
+        .map(|buffer| libc::iovec {
+            iov_base: buffer.as_mut_ptr()
+                as *mut libc::c_void,
+            iov_len: buffer.len(),
+        })
+        .collect();
+
+    let mut messages: Vec<libc::mmsghdr> =
+        iovecs
+            .iter_mut()
+            .zip(addresses.iter_mut())
+            .map(|(iovec, address)| {
+                // SAFETY: 同上
+                let mut message: libc::mmsghdr =
+                    unsafe { std::mem::zeroed() };
+                message.msg_hdr.msg_name = address
+                    as *mut libc::sockaddr_storage
+                    as *mut libc::c_void;
+                message.msg_hdr.msg_namelen =
+                    std::mem::size_of::<
+                        libc::sockaddr_storage,
+                    >()
+                        as libc::socklen_t;
+                message.msg_hdr.msg_iov = iovec;
+                message.msg_hdr.msg_iovlen = 1;
+                message
+            })
+            .collect();
+
+    for (message, control) in
+        messages.iter_mut().zip(controls.iter_mut())
+    {
+        message.msg_hdr.msg_control =
+            control.as_mut_ptr() as *mut libc::c_void;
+        message.msg_hdr.msg_controllen =
+            std::mem::size_of::<ControlBuffer>() as _;
+    }
+
+    // SAFETY: messages 中的指针在调用期间均指向有效内存
+    let received = unsafe {
+        libc::recvmmsg(
+            fd,
+            messages.as_mut_ptr(),
+            count as libc::c_uint,
+            libc::MSG_DONTWAIT,
+            std::ptr::null_mut(),
+        )
+    };
+
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let received = received as usize;
+    for (index, message) in
+        messages.iter().take(received).enumerate()
+    {
+        // SAFETY: 内核已写入 msg_namelen 字节的地址数据
+        let source = unsafe {
+            SockAddr::new(
+                addresses[index],
+                message.msg_hdr.msg_namelen,
+            )
+        }
+        .as_socket();
+
+        metas[index] = RecvMeta {
+            len: message.msg_len as usize,
+            source,
+            ..Default::default()
+        };
+        if message.msg_hdr.msg_controllen > 0 {
+            parse_control_messages(
+                &message.msg_hdr,
+                &mut metas[index],
+            );
+        }
+    }
+
+    Ok(received)
+}
@@ -0,0 +1,633 @@
+//! 吞吐量测试模块（RFC 2544 风格）
+//!
+//! 在给定包长下以不同速率发送带测试头的数据包，统计本机接收端收到或
+//! 反射器回送的包数得到丢包率，按步进或二分查找丢包率不超过阈值的
+//! 最高速率。每次试验使用独立的数据流 ID，迟到的数据包不会计入下一次
+//! 试验。多个包长依次测试，结果组成一张扫频表。
+
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket as TokioUdpSocket;
+use tokio::sync::watch;
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::network::batch::{
+    recv_batch, send_batch, RecvBatch,
+};
+use crate::core::network::generator::MAX_GENERATOR_PACKET_SIZE;
+use crate::core::network::sys::MAX_BATCH_SIZE;
+use crate::core::probe::header::{
+    HeaderStamper, TestHeader, HEADER_LEN,
+};
+
+/// 试验数据流 ID 的起始值
+const STREAM_ID_BASE: u32 = 0x2544_0000;
+
+/// 实际速率低于目标速率的该比例时判定试验失败
+const MIN_OFFERED_RATIO: f64 = 0.99;
+
+/// 接收缓冲区大小（容纳最大负载与反射计数）
+const RECV_BUFFER_SIZE: usize = 65536;
+
+/// 速率查找方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// 从最低速率按步长递增，直到出现超过阈值的丢包
+    Step,
+    /// 先试最高速率，失败后在最低与最高速率之间二分
+    Binary,
+}
+
+impl FromStr for SearchMode {
+    type Err = DataTransferError;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "step" => Ok(SearchMode::Step),
+            "binary" => Ok(SearchMode::Binary),
+            other => Err(DataTransferError::validation(
+                "--search",
+                format!("Unknown search mode: {other}"),
+            )),
+        }
+    }
+}
+
+/// 吞吐量测试配置
+#[derive(Debug, Clone)]
+pub struct ThroughputConfig {
+    /// 反射器地址，`None` 时在本机回环地址上启动接收端
+    pub reflector: Option<SocketAddr>,
+    /// 依次测试的 UDP 负载长度（字节，含 16 字节测试头）
+    pub packet_sizes: Vec<usize>,
+    /// 速率查找方式
+    pub search: SearchMode,
+    /// 最低速率（Mbps，按 UDP 负载计算）
+    pub min_rate_mbps: f64,
+    /// 最高速率（Mbps）
+    pub max_rate_mbps: f64,
+    /// 步进查找的步长（Mbps）
+    pub step_mbps: f64,
+    /// 二分查找的精度（Mbps）
+    pub resolution_mbps: f64,
+    /// 每次试验的发送时长
+    pub trial_duration: Duration,
+    /// 发送结束后等待迟到数据包的时长
+    pub settle_time: Duration,
+    /// 判定通过的最大丢包率（百分比）
+    pub loss_threshold_percent: f64,
+    /// 单次系统调用最多发送的包数
+    pub batch_size: usize,
+}
+
+impl Default for ThroughputConfig {
+    fn default() -> Self {
+        Self {
+            reflector: None,
+            packet_sizes: vec![64, 512, 1472],
+            search: SearchMode::Binary,
+            min_rate_mbps: 10.0,
+            max_rate_mbps: 1000.0,
+            step_mbps: 100.0,
+            resolution_mbps: 10.0,
+            trial_duration: Duration::from_secs(2),
+            settle_time: Duration::from_millis(500),
+            loss_threshold_percent: 0.0,
+            batch_size: 32,
+        }
+    }
+}
+
+impl ThroughputConfig {
+    /// 检查包长、速率与阈值
+    fn validate(&self) -> Result<()> {
+        if self.packet_sizes.is_empty() {
+            return Err(DataTransferError::validation(
+                "--sizes",
+                "At least one packet size is required",
+            ));
+        }
+        if let Some(size) =
+            self.packet_sizes.iter().find(|size| {
+                !(HEADER_LEN..=MAX_GENERATOR_PACKET_SIZE)
+                    .contains(*size)
+            })
+        {
+            return Err(DataTransferError::validation(
+                "--sizes",
+                format!(
+                    "Packet size {} must be between {} and {} bytes",
+                    size,
+                    HEADER_LEN,
+                    MAX_GENERATOR_PACKET_SIZE
+                ),
+            ));
+        }
+        if !(self.min_rate_mbps > 0.0
+            && self.min_rate_mbps <= self.max_rate_mbps
+            && self.max_rate_mbps.is_finite())
+        {
+            return Err(DataTransferError::validation(
+                "--min-rate",
+                "Rates must satisfy 0 < min <= max",
+            ));
+        }
+        let increment = match self.search {
+            SearchMode::Step => ("--step", self.step_mbps),
+            SearchMode::Binary => {
+                ("--resolution", self.resolution_mbps)
+            }
+        };
+        if increment.1.is_nan() || increment.1 <= 0.0 {
+            return Err(DataTransferError::validation(
+                increment.0,
+                "Value must be greater than 0",
+            ));
+        }
+        if !(0.0..=100.0)
+            .contains(&self.loss_threshold_percent)
+        {
+            return Err(DataTransferError::validation(
+                "--loss",
+                "Loss threshold must be between 0 and 100",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 单次试验结果
+#[derive(Debug, Clone, Copy)]
+pub struct Trial {
+    /// 目标速率（Mbps）
+    pub rate_mbps: f64,
+    /// 实际发出的速率（Mbps，发送端跟不上时低于目标速率）
+    pub offered_mbps: f64,
+    /// 成功发送的数据包数量
+    pub sent: u64,
+    /// 收到（或回送）的数据包数量
+    pub received: u64,
+    /// 发送失败的数据包数量
+    pub errors: u64,
+    /// 实际速率达到目标且丢包率不超过阈值
+    pub passed: bool,
+}
+
+impl Trial {
+    /// 丢包率（百分比）
+    pub fn loss_percent(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        self.sent.saturating_sub(self.received) as f64
+            * 100.0
+            / self.sent as f64
+    }
+}
+
+/// 单个包长的测试结果
+#[derive(Debug, Clone)]
+pub struct SizeResult {
+    /// UDP 负载长度（字节）
+    pub packet_size: usize,
+    /// 按执行顺序排列的全部试验
+    pub trials: Vec<Trial>,
+    /// 丢包率不超过阈值的最高速率（Mbps），最低速率也未通过时为空
+    pub max_rate_mbps: Option<f64>,
+}
+
+impl SizeResult {
+    /// 最高无损速率对应的包速率（包/秒）
+    pub fn max_packets_per_second(&self) -> Option<f64> {
+        self.max_rate_mbps.map(|rate| {
+            rate * 1e6 / (self.packet_size as f64 * 8.0)
+        })
+    }
+}
+
+/// 各数据流收到的包数，由计数任务与试验共享
+type Counts = Arc<Mutex<HashMap<u32, u64>>>;
+
+/// 运行吞吐量测试，每完成一次试验调用一次 `on_trial`
+pub async fn run_throughput_test(
+    config: &ThroughputConfig,
+    mut on_trial: impl FnMut(usize, &Trial),
+) -> Result<Vec<SizeResult>> {
+    config.validate()?;
+
+    // 反射模式在发送套接字上接收回送包，否则在本机接收端计数
+    let (sender, receiver, target) = match config.reflector
+    {
+        Some(reflector) => {
+            let local: IpAddr = if reflector.is_ipv4() {
+                Ipv4Addr::UNSPECIFIED.into()
+            } else {
+                Ipv6Addr::UNSPECIFIED.into()
+            };
+            let sender = Arc::new(
+                bind(SocketAddr::new(local, 0)).await?,
+            );
+            (Arc::clone(&sender), sender, reflector)
+        }
+        None => {
+            let loopback =
+                SocketAddr::from((Ipv4Addr::LOCALHOST, 0));
+            let receiver = Arc::new(bind(loopback).await?);
+            let target = receiver.local_addr()?;
+            (
+                Arc::new(bind(loopback).await?),
+                receiver,
+                target,
+            )
+        }
+    };
+    if let Err(e) =
+        socket2::SockRef::from(receiver.as_ref())
+            .set_recv_buffer_size(8 * 1024 * 1024)
+    {
+        tracing::warn!(
+            "Failed to set receive buffer size: {}",
+            e
+        );
+    }
+
+    let counts: Counts = Arc::default();
+    let (stop_tx, stop_rx) = watch::channel(false);
+    let counter = tokio::spawn(run_counter(
+        receiver,
+        config.reflector.is_some(),
+        config.batch_size.clamp(1, MAX_BATCH_SIZE),
+        Arc::clone(&counts),
+        stop_rx,
+    ));
+
+    let mut trial_runner = TrialRunner {
+        socket: &sender,
+        target,
+        counts: &counts,
+        config,
+        next_stream_id: STREAM_ID_BASE,
+    };
+    let mut results =
+        Vec::with_capacity(config.packet_sizes.len());
+    for &packet_size in &config.packet_sizes {
+        let mut trials = Vec::new();
+        let mut best = None;
+
+        match config.search {
+            SearchMode::Step => {
+                let mut rate = config.min_rate_mbps;
+                loop {
+                    let trial = trial_runner
+                        .run(packet_size, rate)
+                        .await;
+                    on_trial(packet_size, &trial);
+                    trials.push(trial);
+                    if !trial.passed {
+                        break;
+                    }
+                    best = Some(rate);
+                    if rate >= config.max_rate_mbps {
+                        break;
+                    }
+                    rate = (rate + config.step_mbps)
+                        .min(config.max_rate_mbps);
+                }
+            }
+            SearchMode::Binary => {
+                let (mut low, mut high) = (
+                    config.min_rate_mbps,
+                    config.max_rate_mbps,
+                );
+                for rate in [high, low] {
+                    let trial = trial_runner
+                        .run(packet_size, rate)
+                        .await;
+                    on_trial(packet_size, &trial);
+                    trials.push(trial);
+                    if trial.passed {
+                        best = Some(rate);
+                        break;
+                    }
+                }
+                if best == Some(low) && low < high {
+                    while high - low
+                        > config.resolution_mbps
+                    {
+                        let rate = (low + high) / 2.0;
+                        let trial = trial_runner
+                            .run(packet_size, rate)
+                            .await;
+                        on_trial(packet_size, &trial);
+                        trials.push(trial);
+                        if trial.passed {
+                            low = rate;
+                            best = Some(rate);
+                        } else {
+                            high = rate;
+                        }
+                    }
+                }
+            }
+        }
+
+        results.push(SizeResult {
+            packet_size,
+            trials,
+            max_rate_mbps: best,
+        });
+    }
+
+    let _ = stop_tx.send(true);
+    let _ = counter.await;
+    Ok(results)
+}
+
+/// 绑定测试用 UDP 套接字
+async fn bind(addr: SocketAddr) -> Result<TokioUdpSocket> {
+    TokioUdpSocket::bind(addr).await.map_err(|e| {
+        DataTransferError::network(format!(
+            "Failed to bind throughput test socket: {e}"
+        ))
+    })
+}
+
+/// 按数据流统计收到的测试包，`reflected` 为真时只统计回送包
+async fn run_counter(
+    socket: Arc<TokioUdpSocket>,
+    reflected: bool,
+    batch_size: usize,
+    counts: Counts,
+    mut stop: watch::Receiver<bool>,
+) {
+    let mut batch =
+        RecvBatch::new(batch_size, RECV_BUFFER_SIZE);
+    loop {
+        tokio::select! {
+            result = recv_batch(&socket, &mut batch) => {
+                if let Err(e) = result {
+                    // 持续出错时不会等到超时，需主动检查停止信号并让出执行权
+                    tracing::debug!("Throughput receive error: {}", e);
+                    if *stop.borrow() {
+                        break;
+                    }
+                    tokio::task::yield_now().await;
+                    continue;
+                }
+                let Ok(mut counts) = counts.lock() else {
+                    break;
+                };
+                for (data, _) in batch.iter() {
+                    if let Some(header) = TestHeader::decode(data) {
+                        if header.reflected_count.is_some() == reflected {
+                            *counts.entry(header.stream_id).or_insert(0) += 1;
+                        }
+                    }
+                }
+            }
+            _ = stop.changed() => break,
+        }
+    }
+}
+
+/// 试验执行器
+struct TrialRunner<'a> {
+    socket: &'a TokioUdpSocket,
+    target: SocketAddr,
+    counts: &'a Counts,
+    config: &'a ThroughputConfig,
+    next_stream_id: u32,
+}
+
+impl TrialRunner<'_> {
+    /// 以指定包长与速率发送一次并统计丢包
+    async fn run(
+        &mut self,
+        packet_size: usize,
+        rate_mbps: f64,
+    ) -> Trial {
+        let stream_id = self.next_stream_id;
+        self.next_stream_id =
+            self.next_stream_id.wrapping_add(1);
+
+        let pps =
+            rate_mbps * 1e6 / (packet_size as f64 * 8.0);
+        let total = (pps
+            * self.config.trial_duration.as_secs_f64())
+        .round()
+        .max(1.0) as u64;
+        let batch_size =
+            self.config.batch_size.clamp(1, MAX_BATCH_SIZE);
+        let filler = vec![0u8; packet_size - HEADER_LEN];
+        let mut stamper = HeaderStamper::new(stream_id);
+
+        // 按包速率推算每个数据包的发送时刻，落后时合并为一批发送
+        let mut sent = 0u64;
+        let mut errors = 0u64;
+        // 最后一批数据包按计划应发送完毕的时刻（相对开始时间）
+        let mut span = Duration::ZERO;
+        let mut woke = false;
+        let started = Instant::now();
+        while sent + errors < total {
+            let attempted = sent + errors;
+            // 发送端跟不上目标速率时到时即停，由发出速率判定失败；
+            // 定时唤醒说明仍按计划发送，唤醒延迟不触发提前结束
+            if attempted > 0
+                && !woke
+                && started.elapsed()
+                    >= self.config.trial_duration
+            {
+                break;
+            }
+            let due = ((started.elapsed().as_secs_f64()
+                * pps) as u64
+                + 1)
+            .min(total);
+            if due <= attempted {
+                let next = started
+                    + Duration::from_secs_f64(
+                        attempted as f64 / pps,
+                    );
+                tokio::time::sleep_until(next.into()).await;
+                woke = true;
+                continue;
+            }
+
+            // 定时唤醒的延迟不算发送端落后，此时按计划时刻计；
+            // 未等待就发送说明已落后于计划，按实际时刻计
+            let batch_start = if woke {
+                Duration::from_secs_f64(
+                    attempted as f64 / pps,
+                )
+            } else {
+                started.elapsed()
+            };
+            woke = false;
+
+            let count = ((due - attempted) as usize)
+                .min(batch_size);
+            let packets: Vec<Vec<u8>> = (0..count)
+                .map(|_| stamper.stamp(&filler))
+                .collect();
+            let payloads: Vec<&[u8]> =
+                packets.iter().map(Vec::as_slice).collect();
+            let result = send_batch(
+                self.socket,
+                self.target,
+                &payloads,
+            )
+            .await;
+            sent += result.sent as u64;
+            errors += result.errors() as u64;
+            span = batch_start
+                + Duration::from_secs_f64(
+                    count as f64 / pps,
+                );
+        }
+        let elapsed = span.as_secs_f64();
+        let offered_mbps = (sent * packet_size as u64 * 8)
+            as f64
+            / elapsed
+            / 1e6;
+
+        tokio::time::sleep(self.config.settle_time).await;
+        let received = self
+            .counts
+            .lock()
+            .ok()
+            .and_then(|counts| {
+                counts.get(&stream_id).copied()
+            })
+            .unwrap_or(0);
+
+        let mut trial = Trial {
+            rate_mbps,
+            offered_mbps,
+            sent,
+            received,
+            errors,
+            passed: false,
+        };
+        trial.passed = sent > 0
+            && offered_mbps
+                >= rate_mbps * MIN_OFFERED_RATIO
+            && trial.loss_percent()
+                <= self.config.loss_threshold_percent;
+        tracing::debug!(
+            "Throughput trial: {} bytes at {:.3} Mbps ({:.3} offered), {} sent, {} received",
+            packet_size,
+            rate_mbps,
+            offered_mbps,
+            sent,
+            received
+        );
+        trial
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(search: SearchMode) -> ThroughputConfig {
+        ThroughputConfig {
+            packet_sizes: vec![64, 256],
+            search,
+            min_rate_mbps: 0.5,
+            max_rate_mbps: 1.0,
+            step_mbps: 0.25,
+            trial_duration: Duration::from_millis(200),
+            settle_time: Duration::from_millis(100),
+            loss_threshold_percent: 0.0,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_binary_search_passes_at_max_rate() {
+        let mut reported = 0;
+        let results = run_throughput_test(
+            &config(SearchMode::Binary),
+            |_, _| reported += 1,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(reported, 2);
+        for result in &results {
+            // 回环低速率下不丢包，首次试验即以最高速率通过
+            assert_eq!(result.trials.len(), 1);
+            assert_eq!(result.max_rate_mbps, Some(1.0));
+            let trial = result.trials[0];
+            assert_eq!(trial.received, trial.sent);
+        }
+        // 1 Mbps、64 字节：约 1953 包/秒，200 ms 内约 391 包
+        assert_eq!(results[0].trials[0].sent, 391);
+    }
+
+    #[tokio::test]
+    async fn test_step_search_visits_every_rate() {
+        let results = run_throughput_test(
+            &ThroughputConfig {
+                packet_sizes: vec![128],
+                ..config(SearchMode::Step)
+            },
+            |_, _| {},
+        )
+        .await
+        .unwrap();
+
+        let rates: Vec<f64> = results[0]
+            .trials
+            .iter()
+            .map(|trial| trial.rate_mbps)
+            .collect();
+        assert_eq!(rates, vec![0.5, 0.75, 1.0]);
+        assert_eq!(results[0].max_rate_mbps, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_rate_stops_at_trial_duration()
+    {
+        // 1 Tbps 远超回环能力：试验按时长结束并因发出速率不足而失败
+        let results = tokio::time::timeout(
+            Duration::from_secs(5),
+            run_throughput_test(
+                &ThroughputConfig {
+                    packet_sizes: vec![64],
+                    min_rate_mbps: 1e6,
+                    max_rate_mbps: 1e6,
+                    ..config(SearchMode::Step)
+                },
+                |_, _| {},
+            ),
+        )
+        .await
+        .expect("trial must stop at its duration")
+        .unwrap();
+
+        let trial = results[0].trials[0];
+        assert!(!trial.passed);
+        assert!(trial.offered_mbps < 1e6);
+        assert_eq!(results[0].max_rate_mbps, None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_config() {
+        let invalid = ThroughputConfig {
+            packet_sizes: vec![8],
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let invalid = ThroughputConfig {
+            min_rate_mbps: 20.0,
+            max_rate_mbps: 10.0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -3,11 +3,15 @@ use std::net::{IpAddr, SocketAddr};
 use tokio::net::UdpSocket as TokioUdpSocket;
 use tracing::{debug, warn};
 
+use crate::app::config::options::TimestampSource;
 use crate::app::config::types::{
     NetworkConfig, NetworkType,
 };
 use crate::app::error::types::{DataTransferError, Result};
 
+/// 同一端口上允许打开的最大接收套接字数量
+pub const MAX_SOCKET_COUNT: usize = 64;
+
 /// UDP套接字工厂
 pub struct UdpSocketFactory;
 
@@ -19,11 +23,132 @@ impl UdpSocketFactory {
         create_udp_sender_socket(config).await
     }
 
-    /// 为接收器创建UDP套接字
-    pub async fn create_receiver(
+    /// 为接收器创建绑定到同一端口的多个UDP套接字
+    ///
+    /// 数量大于 1 时启用 SO_REUSEPORT，由内核在套接字之间分发数据包。
+    pub async fn create_receivers(
         config: &NetworkConfig,
-    ) -> Result<TokioUdpSocket> {
-        create_udp_receiver_socket(config).await
+        count: usize,
+    ) -> Result<Vec<TokioUdpSocket>> {
+        if count <= 1 {
+            return Ok(vec![
+                create_udp_receiver_socket(config).await?,
+            ]);
+        }
+
+        let mut sockets = Vec::with_capacity(count);
+        for _ in 0..count {
+            let socket = bind_reuse_port_socket(config)?;
+            sockets.push(
+                configure_receiver_socket(socket, config)
+                    .await?,
+            );
+        }
+        debug!(
+            "Created {} UDP receivers with SO_REUSEPORT",
+            count
+        );
+        Ok(sockets)
+    }
+}
+
+/// 为接收接口配置网卡硬件时间戳
+///
+/// 只在请求硬件时间戳时配置网卡，同一接口只需配置一次。返回该接口
+/// 可用的时间戳来源：未指定接口或配置失败时退回内核软件时间戳。
+pub fn enable_nic_timestamps(
+    socket: &TokioUdpSocket,
+    source: TimestampSource,
+    interface: Option<&str>,
+) -> TimestampSource {
+    if source != TimestampSource::Hardware {
+        return source;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        use crate::core::network::sys::enable_nic_rx_timestamps;
+        use std::os::fd::AsRawFd;
+
+        match interface {
+            Some(interface) => {
+                match enable_nic_rx_timestamps(
+                    socket.as_raw_fd(),
+                    interface,
+                ) {
+                    Ok(()) => TimestampSource::Hardware,
+                    Err(e) => {
+                        warn!(
+                        "Failed to enable hardware timestamps on {}: {}",
+                        interface, e
+                    );
+                        TimestampSource::Kernel
+                    }
+                }
+            }
+            None => {
+                warn!("Hardware timestamps require a receive interface");
+                TimestampSource::Kernel
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (socket, interface);
+        TimestampSource::Kernel
+    }
+}
+
+/// 启用接收套接字的内核元数据（接收时间戳与溢出计数）
+///
+/// `source` 为网卡配置后可用的时间戳来源（见 [`enable_nic_timestamps`]），
+/// 返回该套接字实际使用的来源：设置失败时退回用户态时间戳。
+pub fn enable_receive_metadata(
+    socket: &TokioUdpSocket,
+    source: TimestampSource,
+) -> TimestampSource {
+    #[cfg(target_os = "linux")]
+    {
+        use crate::core::network::sys::{
+            enable_rx_timestamps, enable_rxq_ovfl,
+            RxTimestamping,
+        };
+        use std::os::fd::AsRawFd;
+
+        let fd = socket.as_raw_fd();
+        let mut effective = source;
+        let mode = match source {
+            TimestampSource::User => None,
+            TimestampSource::Kernel => {
+                Some(RxTimestamping::Software)
+            }
+            TimestampSource::Hardware => {
+                Some(RxTimestamping::Hardware)
+            }
+        };
+        if let Some(mode) = mode {
+            if let Err(e) = enable_rx_timestamps(fd, mode) {
+                warn!(
+                    "Failed to enable receive timestamps: {}",
+                    e
+                );
+                effective = TimestampSource::User;
+            }
+        }
+        if let Err(e) = enable_rxq_ovfl(fd) {
+            warn!("Failed to enable SO_RXQ_OVFL: {}", e);
+        }
+        effective
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = socket;
+        if source != TimestampSource::User {
+            warn!("Kernel receive timestamps are only supported on Linux");
+        }
+        TimestampSource::User
     }
 }
 
@@ -85,6 +210,55 @@ async fn create_udp_receiver_socket(
     configure_receiver_socket(socket, config).await
 }
 
+/// 创建启用 SO_REUSEPORT 的接收套接字（内部函数）
+#[cfg(unix)]
+fn bind_reuse_port_socket(
+    config: &NetworkConfig,
+) -> Result<TokioUdpSocket> {
+    let bind_addr =
+        SocketAddr::new(config.address, config.port);
+
+    let socket = Socket::new(
+        socket2::Domain::for_address(bind_addr),
+        socket2::Type::DGRAM,
+        Some(socket2::Protocol::UDP),
+    )
+    .map_err(DataTransferError::Network)?;
+    socket.set_reuse_address(true).map_err(|e| {
+        DataTransferError::config(format!(
+            "Failed to set SO_REUSEADDR: {e}"
+        ))
+    })?;
+    socket.set_reuse_port(true).map_err(|e| {
+        DataTransferError::config(format!(
+            "Failed to set SO_REUSEPORT: {e}"
+        ))
+    })?;
+    socket.set_nonblocking(true).map_err(|e| {
+        DataTransferError::config(format!(
+            "Failed to set non-blocking mode: {e}"
+        ))
+    })?;
+    socket.bind(&bind_addr.into()).map_err(|e| {
+        DataTransferError::network(format!(
+            "Failed to bind UDP receiver {bind_addr}: {e}"
+        ))
+    })?;
+
+    TokioUdpSocket::from_std(socket.into())
+        .map_err(DataTransferError::Network)
+}
+
+/// 非 Unix 平台不支持 SO_REUSEPORT（内部函数）
+#[cfg(not(unix))]
+fn bind_reuse_port_socket(
+    _config: &NetworkConfig,
+) -> Result<TokioUdpSocket> {
+    Err(DataTransferError::config(
+        "SO_REUSEPORT is not supported on this platform",
+    ))
+}
+
 /// 配置发送器套接字
 async fn configure_sender_socket(
     socket: TokioUdpSocket,
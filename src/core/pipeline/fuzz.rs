@@ -0,0 +1,483 @@
+//! 模糊测试模块
+//!
+//! 发送端对每个数据包施加随机变异：比特翻转、边界值、截断、追加与拼接。
+//! CSV 数据源按列类型定位字段并写入该类型的边界值，PCAP 数据集在随机
+//! 偏移写入常见宽度的整数边界值。第 N 个数据包的变异只由种子与 N 决定，
+//! 每个变异后的数据包连同序号、变异说明与完整内容写入变异日志，可据此
+//! 精确复现导致接收方崩溃的数据包。
+
+use chrono::{SecondsFormat, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use crate::app::config::options::FuzzOptions;
+use crate::app::error::types::Result;
+use crate::core::csv::types::CsvDataType;
+use crate::core::network::source::SourcePacket;
+use crate::utils::helpers::format_hex;
+
+/// 每个数据包最多施加的变异次数
+pub const MAX_FUZZ_MUTATIONS: usize = 16;
+
+/// 追加字节数与变异后负载长度的上限（UDP 最大负载）
+pub const MAX_FUZZ_EXTEND: usize = 65507;
+
+/// 变异日志的表头
+const LOG_HEADER: &str =
+    "index,timestamp,mutations,length,payload_hex";
+
+/// 变异方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mutation {
+    BitFlip,
+    Boundary,
+    Truncate,
+    Extend,
+    Splice,
+}
+
+/// 模糊测试器
+pub struct Fuzzer {
+    seed: u64,
+    mutations: Vec<Mutation>,
+    max_mutations: usize,
+    max_extend: usize,
+    /// CSV 字段的偏移与类型（PCAP 数据集为空）
+    fields: Vec<(usize, CsvDataType)>,
+    /// 上一个数据包变异前的内容（用于拼接）
+    previous: Vec<u8>,
+    /// 下一个数据包的序号
+    index: u64,
+    log: BufWriter<File>,
+    log_path: PathBuf,
+}
+
+impl Fuzzer {
+    /// 根据选项创建模糊测试器，未启用时返回 `None`
+    ///
+    /// `columns` 为 CSV 数据源的列类型；日志目录为空时写入 `default_dir`。
+    pub fn from_options(
+        options: &FuzzOptions,
+        columns: &[CsvDataType],
+        default_dir: &Path,
+        name: &str,
+    ) -> Result<Option<Self>> {
+        if !options.enabled {
+            return Ok(None);
+        }
+
+        let mutations = [
+            (options.bit_flip, Mutation::BitFlip),
+            (options.boundary, Mutation::Boundary),
+            (options.truncate, Mutation::Truncate),
+            (options.extend, Mutation::Extend),
+            (options.splice, Mutation::Splice),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, mutation)| {
+            enabled.then_some(mutation)
+        })
+        .collect();
+
+        let mut offset = 0;
+        let fields = columns
+            .iter()
+            .map(|data_type| {
+                let field = (offset, data_type.clone());
+                offset += data_type.size();
+                field
+            })
+            .collect();
+
+        let directory = match options.log_directory.trim() {
+            "" => default_dir.to_path_buf(),
+            directory => PathBuf::from(directory),
+        };
+        std::fs::create_dir_all(&directory)?;
+        let log_path = directory.join(format!(
+            "{}_{}_fuzz.csv",
+            name,
+            Utc::now().format("%Y%m%d_%H%M%S")
+        ));
+        let mut log =
+            BufWriter::new(File::create(&log_path)?);
+        writeln!(log, "# seed={}", options.seed)?;
+        writeln!(log, "{LOG_HEADER}")?;
+        tracing::info!(
+            "Fuzzing with seed {}, logging to {}",
+            options.seed,
+            log_path.display()
+        );
+
+        Ok(Some(Self {
+            seed: options.seed,
+            mutations,
+            max_mutations: options
+                .max_mutations
+                .clamp(1, MAX_FUZZ_MUTATIONS),
+            max_extend: options.max_extend,
+            fields,
+            previous: Vec::new(),
+            index: 0,
+            log,
+            log_path,
+        }))
+    }
+
+    /// 已变异的数据包数量
+    pub fn mutated(&self) -> u64 {
+        self.index
+    }
+
+    /// 变异一个数据包并写入日志
+    pub fn mutate(
+        &mut self,
+        packet: &mut SourcePacket,
+    ) -> Result<()> {
+        let index = self.index;
+        self.index += 1;
+        let original = packet.data.clone();
+        let mut rng =
+            StdRng::seed_from_u64(self.seed.wrapping_add(
+                index.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            ));
+
+        let count = rng.gen_range(1..=self.max_mutations);
+        let mut applied = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(description) =
+                self.apply(&mut rng, &mut packet.data)
+            {
+                applied.push(description);
+            }
+        }
+        self.previous = original;
+
+        let mutations = if applied.is_empty() {
+            "none".to_string()
+        } else {
+            applied.join(";")
+        };
+        tracing::debug!(
+            "Fuzzed packet {}: {} ({} bytes)",
+            index,
+            mutations,
+            packet.data.len()
+        );
+        writeln!(
+            self.log,
+            "{},{},{},{},{}",
+            index,
+            packet.timestamp.to_rfc3339_opts(
+                SecondsFormat::Nanos,
+                true
+            ),
+            mutations,
+            packet.data.len(),
+            format_hex(&packet.data)
+        )?;
+        Ok(())
+    }
+
+    /// 写出剩余日志
+    pub fn finish(mut self) -> Result<()> {
+        self.log.flush()?;
+        tracing::info!(
+            "Fuzz log written to {} ({} packets)",
+            self.log_path.display(),
+            self.index
+        );
+        Ok(())
+    }
+
+    /// 施加一次随机变异，返回变异说明（无法施加时返回 `None`）
+    fn apply(
+        &self,
+        rng: &mut StdRng,
+        data: &mut Vec<u8>,
+    ) -> Option<String> {
+        let mutation = *self
+            .mutations
+            .get(rng.gen_range(0..self.mutations.len()))?;
+        match mutation {
+            Mutation::BitFlip if !data.is_empty() => {
+                let byte = rng.gen_range(0..data.len());
+                let bit = rng.gen_range(0..8);
+                data[byte] ^= 1 << bit;
+                Some(format!("bit_flip@{byte}.{bit}"))
+            }
+            Mutation::Boundary => {
+                let (offset, label, value) =
+                    self.boundary(rng, data.len())?;
+                data[offset..offset + value.len()]
+                    .copy_from_slice(&value);
+                Some(format!(
+                    "boundary {label}@{offset}=0x{}",
+                    format_hex(&value)
+                ))
+            }
+            Mutation::Truncate if !data.is_empty() => {
+                let len = rng.gen_range(0..data.len());
+                let description = format!(
+                    "truncate {}->{len}",
+                    data.len()
+                );
+                data.truncate(len);
+                Some(description)
+            }
+            Mutation::Extend
+                if self.max_extend > 0
+                    && data.len() < MAX_FUZZ_EXTEND =>
+            {
+                let room = MAX_FUZZ_EXTEND - data.len();
+                let extra = rng.gen_range(
+                    1..=self.max_extend.min(room),
+                );
+                data.extend(
+                    (0..extra).map(|_| rng.gen::<u8>()),
+                );
+                Some(format!("extend +{extra}"))
+            }
+            Mutation::Splice
+                if !self.previous.is_empty() =>
+            {
+                let head = rng.gen_range(0..=data.len());
+                let tail =
+                    rng.gen_range(0..self.previous.len());
+                let end = self.previous.len().min(
+                    tail + MAX_FUZZ_EXTEND
+                        .saturating_sub(head),
+                );
+                data.truncate(head);
+                data.extend_from_slice(
+                    &self.previous[tail..end],
+                );
+                Some(format!("splice {head}+prev@{tail}"))
+            }
+            _ => None,
+        }
+    }
+
+    /// 选择边界值：CSV 数据源取一个完整的字段，否则在随机偏移取整数宽度
+    fn boundary(
+        &self,
+        rng: &mut StdRng,
+        len: usize,
+    ) -> Option<(usize, String, Vec<u8>)> {
+        let fields: Vec<_> = self
+            .fields
+            .iter()
+            .filter(|(offset, data_type)| {
+                data_type.size() > 0
+                    && offset + data_type.size() <= len
+            })
+            .collect();
+        let (offset, data_type) = if self.fields.is_empty()
+        {
+            let widths: Vec<usize> = [1, 2, 4, 8]
+                .into_iter()
+                .filter(|width| *width <= len)
+                .collect();
+            let width = *widths.get(
+                rng.gen_range(0..widths.len().max(1)),
+            )?;
+            let data_type = match width {
+                1 => CsvDataType::I8,
+                2 => CsvDataType::I16,
+                4 => CsvDataType::I32,
+                _ => CsvDataType::I64,
+            };
+            (rng.gen_range(0..=len - width), data_type)
+        } else {
+            let (offset, data_type) = fields.get(
+                rng.gen_range(0..fields.len().max(1)),
+            )?;
+            (*offset, data_type.clone())
+        };
+
+        let values = boundary_values(&data_type);
+        let value =
+            values[rng.gen_range(0..values.len())].clone();
+        Some((offset, data_type.to_string(), value))
+    }
+}
+
+/// 整数类型的边界值：0、1、-1（无符号时为最大值）、最小值与最大值
+macro_rules! int_bounds {
+    ($t:ty) => {
+        [
+            0 as $t,
+            1,
+            (0 as $t).wrapping_sub(1),
+            <$t>::MIN,
+            <$t>::MAX,
+        ]
+        .iter()
+        .map(|value| value.to_le_bytes().to_vec())
+        .collect()
+    };
+}
+
+/// 浮点类型的边界值：±0、NaN、±∞、最小正规数与最大、最小有限值
+macro_rules! float_bounds {
+    ($t:ty) => {
+        [
+            0.0,
+            -0.0,
+            <$t>::NAN,
+            <$t>::INFINITY,
+            <$t>::NEG_INFINITY,
+            <$t>::MIN_POSITIVE,
+            <$t>::MAX,
+            <$t>::MIN,
+        ]
+        .iter()
+        .map(|value: &$t| value.to_le_bytes().to_vec())
+        .collect()
+    };
+}
+
+/// 指定类型的边界值（小端字节序，与 CSV 编码一致）
+fn boundary_values(
+    data_type: &CsvDataType,
+) -> Vec<Vec<u8>> {
+    match data_type {
+        CsvDataType::I8 => int_bounds!(i8),
+        CsvDataType::I16 => int_bounds!(i16),
+        CsvDataType::I32 => int_bounds!(i32),
+        CsvDataType::I64 => int_bounds!(i64),
+        CsvDataType::U8 => int_bounds!(u8),
+        CsvDataType::U16 => int_bounds!(u16),
+        CsvDataType::U32 => int_bounds!(u32),
+        CsvDataType::U64 => int_bounds!(u64),
+        CsvDataType::F32 => float_bounds!(f32),
+        CsvDataType::F64 => float_bounds!(f64),
+        CsvDataType::Bool => {
+            vec![vec![0], vec![1], vec![2], vec![0xFF]]
+        }
+        CsvDataType::HexDynamic(size) => vec![
+            vec![0x00; *size],
+            vec![0xFF; *size],
+            vec![0x7F; *size],
+            vec![0x80; *size],
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzer(
+        options: &FuzzOptions,
+        columns: &[CsvDataType],
+        name: &str,
+    ) -> Fuzzer {
+        let dir = std::env::temp_dir().join(format!(
+            "pcap_transfer_fuzz_{}",
+            std::process::id()
+        ));
+        Fuzzer::from_options(options, columns, &dir, name)
+            .unwrap()
+            .unwrap()
+    }
+
+    fn packet(data: &[u8]) -> SourcePacket {
+        SourcePacket {
+            data: data.to_vec(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_mutations_reproducible_per_index() {
+        let options = FuzzOptions {
+            enabled: true,
+            seed: 9,
+            max_mutations: 4,
+            ..Default::default()
+        };
+        let mut first = fuzzer(&options, &[], "first");
+        let mut second = fuzzer(&options, &[], "second");
+        let mut outputs = Vec::new();
+        for i in 0..50u8 {
+            let mut a = packet(&[i; 32]);
+            let mut b = packet(&[i; 32]);
+            first.mutate(&mut a).unwrap();
+            second.mutate(&mut b).unwrap();
+            assert_eq!(a.data, b.data);
+            outputs.push(a.data);
+        }
+        assert!(outputs
+            .iter()
+            .enumerate()
+            .any(|(i, data)| *data != vec![i as u8; 32]));
+        assert_eq!(first.mutated(), 50);
+
+        let log_path = first.log_path.clone();
+        first.finish().unwrap();
+        second.finish().unwrap();
+        let log =
+            std::fs::read_to_string(&log_path).unwrap();
+        let mut lines = log.lines();
+        assert_eq!(lines.next(), Some("# seed=9"));
+        assert_eq!(lines.next(), Some(LOG_HEADER));
+        let row: Vec<&str> =
+            lines.next().unwrap().split(',').collect();
+        assert_eq!(row[0], "0");
+        assert_eq!(row[4], format_hex(&outputs[0]));
+        let _ = std::fs::remove_file(log_path);
+    }
+
+    #[test]
+    fn test_boundary_targets_csv_fields() {
+        let options = FuzzOptions {
+            enabled: true,
+            bit_flip: false,
+            truncate: false,
+            extend: false,
+            splice: false,
+            ..Default::default()
+        };
+        let columns = [CsvDataType::U8, CsvDataType::F32];
+        let mut fuzzer = fuzzer(&options, &columns, "csv");
+        let u8_values = boundary_values(&CsvDataType::U8);
+        let f32_values = boundary_values(&CsvDataType::F32);
+        for _ in 0..20 {
+            let mut packet = packet(&[7, 1, 2, 3, 4]);
+            fuzzer.mutate(&mut packet).unwrap();
+            let u8_changed = u8_values
+                .contains(&packet.data[..1].to_vec());
+            let f32_changed = f32_values
+                .contains(&packet.data[1..].to_vec());
+            assert!(u8_changed || f32_changed);
+        }
+        let log_path = fuzzer.log_path.clone();
+        let _ = std::fs::remove_file(log_path);
+    }
+
+    #[test]
+    fn test_growth_capped_at_udp_payload() {
+        // 追加与拼接都不能让负载超过 UDP 最大长度
+        let options = FuzzOptions {
+            enabled: true,
+            bit_flip: false,
+            boundary: false,
+            truncate: false,
+            max_mutations: MAX_FUZZ_MUTATIONS,
+            max_extend: MAX_FUZZ_EXTEND,
+            ..Default::default()
+        };
+        let mut fuzzer = fuzzer(&options, &[], "growth");
+        for _ in 0..20 {
+            let mut packet = packet(&[0xAB; 60_000]);
+            fuzzer.mutate(&mut packet).unwrap();
+            assert!(packet.data.len() <= MAX_FUZZ_EXTEND);
+        }
+        let log_path = fuzzer.log_path.clone();
+        let _ = std::fs::remove_file(log_path);
+    }
+}
@@ -0,0 +1,534 @@
+//! 网络损伤模拟模块
+//!
+//! 发送端在数据源（及测试头）与发送之间依次施加随机丢包、Gilbert–Elliott
+//! 突发丢包、比特翻转、重复、乱序与附加延迟（含抖动）。每种损伤使用由同一
+//! 种子派生的独立随机数序列，且每个数据包都会抽取一次：调整其中一种损伤
+//! 的概率不会改变其他损伤的决策，相同种子与配置可以完全复现。
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::app::config::options::ImpairmentOptions;
+use crate::core::network::source::SourcePacket;
+
+/// 乱序窗口的上限（包数）
+pub const MAX_REORDER_WINDOW: usize = 1024;
+
+/// 附加延迟与抖动的上限（毫秒）
+pub const MAX_IMPAIRMENT_DELAY_MS: u64 = 60_000;
+
+/// 损伤统计
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize,
+)]
+pub struct ImpairmentStats {
+    /// 随机丢弃的数据包数量
+    pub dropped: u64,
+    /// 突发丢包丢弃的数据包数量
+    pub burst_dropped: u64,
+    /// 翻转了比特的数据包数量
+    pub corrupted: u64,
+    /// 重复发送的数据包数量
+    pub duplicated: u64,
+    /// 被推迟发送（乱序）的数据包数量
+    pub reordered: u64,
+    /// 附加了延迟的数据包数量
+    pub delayed: u64,
+}
+
+/// 等待延迟到期的数据包
+struct Delayed {
+    release: Instant,
+    /// 进入延迟队列的顺序（到期时间相同时保持原顺序）
+    order: u64,
+    packet: SourcePacket,
+}
+
+impl PartialEq for Delayed {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Delayed {}
+
+impl PartialOrd for Delayed {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Delayed {
+    // 反向比较，使二叉堆的堆顶为最早到期的数据包
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.release, other.order)
+            .cmp(&(self.release, self.order))
+    }
+}
+
+/// 已决定抖动、等待放行的数据包
+struct Pending {
+    packet: SourcePacket,
+    /// 抖动（微秒）
+    jitter: i64,
+}
+
+/// 各损伤独立的随机数序列
+struct Streams {
+    drop: StdRng,
+    burst: StdRng,
+    corrupt: StdRng,
+    duplicate: StdRng,
+    reorder: StdRng,
+    delay: StdRng,
+}
+
+impl Streams {
+    fn new(seed: u64) -> Self {
+        let rng = |index: u64| {
+            StdRng::seed_from_u64(seed.wrapping_add(
+                index.wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            ))
+        };
+        Self {
+            drop: rng(0),
+            burst: rng(1),
+            corrupt: rng(2),
+            duplicate: rng(3),
+            reorder: rng(4),
+            delay: rng(5),
+        }
+    }
+}
+
+/// 网络损伤模拟器
+pub struct Impairer {
+    options: ImpairmentOptions,
+    streams: Streams,
+    /// Gilbert–Elliott 模型当前是否处于坏状态
+    in_bad_state: bool,
+    /// 已处理的数据包数量
+    processed: u64,
+    /// 乱序推迟的数据包及其放行时的处理序号（按放行顺序排列）
+    held: VecDeque<(u64, Pending)>,
+    delayed: BinaryHeap<Delayed>,
+    next_order: u64,
+    stats: ImpairmentStats,
+}
+
+impl Impairer {
+    /// 根据选项创建模拟器，未启用时返回 `None`
+    pub fn from_options(
+        options: &ImpairmentOptions,
+    ) -> Option<Self> {
+        options.enabled.then(|| Self {
+            options: options.clone(),
+            streams: Streams::new(options.seed),
+            in_bad_state: false,
+            processed: 0,
+            held: VecDeque::new(),
+            delayed: BinaryHeap::new(),
+            next_order: 0,
+            stats: ImpairmentStats::default(),
+        })
+    }
+
+    /// 当前损伤统计
+    pub fn stats(&self) -> ImpairmentStats {
+        self.stats
+    }
+
+    /// 处理一个数据包，可立即发送的数据包追加到 `out`
+    pub fn process(
+        &mut self,
+        packet: SourcePacket,
+        now: Instant,
+        out: &mut Vec<SourcePacket>,
+    ) {
+        self.processed += 1;
+        self.process_packet(packet, now, out);
+
+        // 推迟的数据包在其后的 reorder_window 个数据包处理完后放行，
+        // 不论这些数据包被发送、丢弃还是同样被推迟
+        while self
+            .held
+            .front()
+            .is_some_and(|(due, _)| *due <= self.processed)
+        {
+            if let Some((_, packet)) = self.held.pop_front()
+            {
+                self.schedule(packet, now, out);
+            }
+        }
+    }
+
+    /// 对一个数据包施加各项损伤
+    fn process_packet(
+        &mut self,
+        mut packet: SourcePacket,
+        now: Instant,
+        out: &mut Vec<SourcePacket>,
+    ) {
+        let options = &self.options;
+
+        if chance(
+            &mut self.streams.drop,
+            options.drop_percent,
+        ) {
+            self.stats.dropped += 1;
+            return;
+        }
+
+        let burst = &options.burst_loss;
+        let transition = self.streams.burst.gen::<f64>();
+        let loss = self.streams.burst.gen::<f64>();
+        if burst.enabled {
+            let (switch_percent, loss_percent) =
+                if self.in_bad_state {
+                    (
+                        burst.exit_bad_percent,
+                        burst.bad_loss_percent,
+                    )
+                } else {
+                    (
+                        burst.enter_bad_percent,
+                        burst.good_loss_percent,
+                    )
+                };
+            if transition * 100.0 < switch_percent {
+                self.in_bad_state = !self.in_bad_state;
+            }
+            if loss * 100.0 < loss_percent {
+                self.stats.burst_dropped += 1;
+                return;
+            }
+        }
+
+        let bit = self.streams.corrupt.gen::<u64>();
+        if chance(
+            &mut self.streams.corrupt,
+            options.corrupt_percent,
+        ) && !packet.data.is_empty()
+        {
+            let bit = bit % (packet.data.len() as u64 * 8);
+            packet.data[(bit / 8) as usize] ^=
+                1 << (bit % 8);
+            self.stats.corrupted += 1;
+        }
+
+        // 乱序与抖动按最多两个副本预先抽取，重复与否不影响后续序列
+        let duplicate = chance(
+            &mut self.streams.duplicate,
+            options.duplicate_percent,
+        );
+        let jitter_us = options.jitter_ms as i64 * 1000;
+        let mut decisions = [(false, 0); 2];
+        for decision in &mut decisions {
+            *decision = (
+                chance(
+                    &mut self.streams.reorder,
+                    options.reorder_percent,
+                ) && options.reorder_window > 0,
+                self.streams
+                    .delay
+                    .gen_range(-jitter_us..=jitter_us),
+            );
+        }
+
+        let mut copies = vec![packet];
+        if duplicate {
+            self.stats.duplicated += 1;
+            copies.push(copies[0].clone());
+        }
+        for (packet, (reorder, jitter)) in
+            copies.into_iter().zip(decisions)
+        {
+            let packet = Pending { packet, jitter };
+            if reorder {
+                self.stats.reordered += 1;
+                self.held.push_back((
+                    self.processed
+                        + self.options.reorder_window
+                            as u64,
+                    packet,
+                ));
+            } else {
+                self.schedule(packet, now, out);
+            }
+        }
+    }
+
+    /// 最早到期的延迟数据包的发送时间
+    pub fn next_release(&self) -> Option<Instant> {
+        self.delayed.peek().map(|delayed| delayed.release)
+    }
+
+    /// 取出已到期的延迟数据包
+    pub fn release_due(
+        &mut self,
+        now: Instant,
+        out: &mut Vec<SourcePacket>,
+    ) {
+        while self
+            .delayed
+            .peek()
+            .is_some_and(|delayed| delayed.release <= now)
+        {
+            if let Some(delayed) = self.delayed.pop() {
+                out.push(delayed.packet);
+            }
+        }
+    }
+
+    /// 数据源结束时放行全部乱序推迟的数据包
+    pub fn flush_held(
+        &mut self,
+        now: Instant,
+        out: &mut Vec<SourcePacket>,
+    ) {
+        while let Some((_, packet)) = self.held.pop_front()
+        {
+            self.schedule(packet, now, out);
+        }
+    }
+
+    /// 按附加延迟与抖动安排发送时间
+    fn schedule(
+        &mut self,
+        pending: Pending,
+        now: Instant,
+        out: &mut Vec<SourcePacket>,
+    ) {
+        let delay_us = self.options.delay_ms as i64 * 1000;
+        if delay_us == 0 && self.options.jitter_ms == 0 {
+            out.push(pending.packet);
+            return;
+        }
+
+        self.stats.delayed += 1;
+        let offset = Duration::from_micros(
+            (delay_us + pending.jitter).max(0) as u64,
+        );
+        self.delayed.push(Delayed {
+            release: now + offset,
+            order: self.next_order,
+            packet: pending.packet,
+        });
+        self.next_order += 1;
+    }
+}
+
+/// 以百分比概率返回 `true`（总是抽取一次随机数）
+fn chance(rng: &mut StdRng, percent: f64) -> bool {
+    rng.gen::<f64>() * 100.0 < percent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::config::options::BurstLossOptions;
+    use chrono::Utc;
+
+    fn packets(count: u8) -> Vec<SourcePacket> {
+        (0..count)
+            .map(|i| SourcePacket {
+                data: vec![i; 8],
+                timestamp: Utc::now(),
+            })
+            .collect()
+    }
+
+    fn run(
+        options: &ImpairmentOptions,
+        count: u8,
+        now: Instant,
+    ) -> (Vec<u8>, ImpairmentStats) {
+        let mut impairer =
+            Impairer::from_options(options).unwrap();
+        let mut out = Vec::new();
+        for packet in packets(count) {
+            impairer.process(packet, now, &mut out);
+        }
+        impairer.flush_held(now, &mut out);
+        impairer.release_due(
+            now + Duration::from_secs(60),
+            &mut out,
+        );
+        (
+            out.iter()
+                .map(|packet| packet.data[0])
+                .collect(),
+            impairer.stats(),
+        )
+    }
+
+    #[test]
+    fn test_seeded_impairments_are_reproducible() {
+        let now = Instant::now();
+        let options = ImpairmentOptions {
+            enabled: true,
+            seed: 42,
+            drop_percent: 10.0,
+            duplicate_percent: 10.0,
+            reorder_percent: 10.0,
+            corrupt_percent: 10.0,
+            ..Default::default()
+        };
+        let (first, stats) = run(&options, 200, now);
+        assert_eq!(run(&options, 200, now).0, first);
+        assert!(stats.dropped > 0 && stats.duplicated > 0);
+        assert!(stats.reordered > 0 && stats.corrupted > 0);
+        assert_eq!(
+            first.len() as u64,
+            200 - stats.dropped + stats.duplicated
+        );
+
+        // 调整重复概率不影响丢包决策
+        let (_, other) = run(
+            &ImpairmentOptions {
+                duplicate_percent: 50.0,
+                ..options.clone()
+            },
+            200,
+            now,
+        );
+        assert_eq!(other.dropped, stats.dropped);
+        assert_ne!(
+            run(
+                &ImpairmentOptions { seed: 7, ..options },
+                200,
+                now
+            )
+            .0,
+            first
+        );
+    }
+
+    #[test]
+    fn test_burst_loss_drops_consecutive_packets() {
+        let options = ImpairmentOptions {
+            enabled: true,
+            burst_loss: BurstLossOptions {
+                enabled: true,
+                enter_bad_percent: 5.0,
+                exit_bad_percent: 20.0,
+                good_loss_percent: 0.0,
+                bad_loss_percent: 100.0,
+            },
+            ..Default::default()
+        };
+        let mut impairer =
+            Impairer::from_options(&options).unwrap();
+        let mut out = Vec::new();
+        let now = Instant::now();
+        let mut lost = Vec::new();
+        for i in 0..2000u32 {
+            let before = impairer.stats().burst_dropped;
+            impairer.process(
+                SourcePacket {
+                    data: i.to_be_bytes().to_vec(),
+                    timestamp: Utc::now(),
+                },
+                now,
+                &mut out,
+            );
+            lost.push(
+                impairer.stats().burst_dropped > before,
+            );
+        }
+        let bursts = lost
+            .windows(2)
+            .filter(|w| w[1] && !w[0])
+            .count();
+        let dropped = impairer.stats().burst_dropped;
+        assert_eq!(out.len() as u64 + dropped, 2000);
+        // 平均突发长度约为 1 / 20%
+        assert!(dropped as f64 / bursts as f64 > 2.5);
+    }
+
+    #[test]
+    fn test_reorder_and_delay() {
+        let now = Instant::now();
+        let mut impairer =
+            Impairer::from_options(&ImpairmentOptions {
+                enabled: true,
+                reorder_percent: 100.0,
+                reorder_window: 2,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut out = Vec::new();
+        for packet in packets(2) {
+            impairer.process(packet, now, &mut out);
+        }
+        assert!(out.is_empty());
+        // 全部推迟时也按窗口放行，不会积压到数据源结束
+        for packet in packets(5).into_iter().skip(2) {
+            impairer.process(packet, now, &mut out);
+        }
+        assert_eq!(
+            out.iter()
+                .map(|p| p.data[0])
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        impairer.flush_held(now, &mut out);
+        assert_eq!(out.len(), 5);
+
+        // 推迟的数据包排在其后第 reorder_window 个数据包之后
+        let mut impairer =
+            Impairer::from_options(&ImpairmentOptions {
+                enabled: true,
+                reorder_percent: 100.0,
+                reorder_window: 2,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut out = Vec::new();
+        impairer.process(
+            packets(1).remove(0),
+            now,
+            &mut out,
+        );
+        impairer.options.reorder_percent = 0.0;
+        for packet in packets(4).into_iter().skip(1) {
+            impairer.process(packet, now, &mut out);
+        }
+        assert_eq!(
+            out.iter()
+                .map(|p| p.data[0])
+                .collect::<Vec<_>>(),
+            vec![1, 2, 0, 3]
+        );
+
+        let mut impairer =
+            Impairer::from_options(&ImpairmentOptions {
+                enabled: true,
+                delay_ms: 10,
+                jitter_ms: 5,
+                ..Default::default()
+            })
+            .unwrap();
+        let mut out = Vec::new();
+        for packet in packets(20) {
+            impairer.process(packet, now, &mut out);
+        }
+        assert!(out.is_empty());
+        let release = impairer.next_release().unwrap();
+        assert!(release >= now + Duration::from_millis(5));
+        impairer.release_due(
+            now + Duration::from_millis(15),
+            &mut out,
+        );
+        assert_eq!(out.len(), 20);
+        assert_eq!(impairer.stats().delayed, 20);
+        assert!(impairer.next_release().is_none());
+    }
+}
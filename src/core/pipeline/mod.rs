@@ -0,0 +1,10 @@
+//! 数据管道模块
+//!
+//! 负责在网络收发循环与磁盘读写之间解耦
+
+pub mod fuzz;
+pub mod impairment;
+pub mod prefetch;
+pub mod queue;
+pub mod reorder;
+pub mod writer;
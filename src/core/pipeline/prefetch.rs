@@ -0,0 +1,379 @@
+//! 预读模块
+//!
+//! 由后台线程提前从数据源读取数据包填充环形缓冲区，
+//! 发送循环只从内存中取包，慢速磁盘不再给重放引入抖动。
+
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::network::source::{
+    PacketSource, SourcePacket,
+};
+use crate::core::pipeline::queue::{
+    BoundedQueue, QueueItem, TryPop,
+};
+use crate::core::stats::collector::TransferStats;
+
+/// 缓冲区深度的最短更新间隔（避免每包锁定统计信息）
+const DEPTH_UPDATE_INTERVAL: Duration =
+    Duration::from_millis(100);
+
+impl QueueItem for SourcePacket {
+    fn byte_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// 非阻塞取包结果
+#[derive(Debug)]
+pub enum FeedPoll {
+    /// 取到数据包
+    Packet(SourcePacket),
+    /// 暂无可用数据包
+    Pending,
+    /// 数据源已结束
+    End,
+}
+
+/// 发送循环的数据包输入
+///
+/// 直接读取数据源，或经由预读线程读取。
+pub enum PacketFeed {
+    /// 在发送循环中直接读取数据源
+    Direct(Box<dyn PacketSource>),
+    /// 经由后台预读线程读取
+    Prefetch(PrefetchBuffer),
+}
+
+impl PacketFeed {
+    /// 获取下一个数据包，必要时等待
+    pub async fn next(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        match self {
+            PacketFeed::Direct(source) => {
+                source.next_packet()
+            }
+            PacketFeed::Prefetch(buffer) => {
+                buffer.next().await
+            }
+        }
+    }
+
+    /// 非阻塞地获取下一个数据包（用于批量合并）
+    pub fn poll_next(&mut self) -> Result<FeedPoll> {
+        match self {
+            PacketFeed::Direct(source) => Ok(match source
+                .next_packet()?
+            {
+                Some(packet) => FeedPoll::Packet(packet),
+                None => FeedPoll::End,
+            }),
+            PacketFeed::Prefetch(buffer) => {
+                buffer.poll_next()
+            }
+        }
+    }
+}
+
+/// 预读缓冲区
+pub struct PrefetchBuffer {
+    queue: Arc<BoundedQueue<SourcePacket>>,
+    error: Arc<Mutex<Option<DataTransferError>>>,
+    stats: Arc<Mutex<TransferStats>>,
+    // 上次更新缓冲区深度的时刻
+    depth_updated: Option<Instant>,
+    // 是否已取到过数据包（首次填充前的等待不算欠载）
+    primed: bool,
+    _reader: JoinHandle<()>,
+}
+
+impl PrefetchBuffer {
+    /// 启动预读线程
+    ///
+    /// # 参数
+    /// * `source` - 被预读的数据源
+    /// * `max_packets` - 缓冲区最大包数
+    /// * `max_bytes` - 缓冲区最大字节数，0 表示不限制
+    /// * `stats` - 用于记录缓冲区深度与欠载次数的统计信息
+    pub fn spawn(
+        mut source: Box<dyn PacketSource>,
+        max_packets: usize,
+        max_bytes: usize,
+        stats: Arc<Mutex<TransferStats>>,
+    ) -> Result<Self> {
+        let queue = Arc::new(BoundedQueue::new(
+            max_packets,
+            max_bytes,
+        ));
+        let error = Arc::new(Mutex::new(None));
+
+        let reader_queue = Arc::clone(&queue);
+        let reader_error = Arc::clone(&error);
+        let reader = std::thread::Builder::new()
+            .name("packet-prefetch".to_string())
+            .spawn(move || {
+                loop {
+                    match source.next_packet() {
+                        Ok(Some(packet)) => {
+                            // 发送端提前停止时队列被关闭，线程随之退出
+                            if reader_queue
+                                .push_blocking(packet)
+                                .is_err()
+                            {
+                                break;
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            if let Ok(mut slot) =
+                                reader_error.lock()
+                            {
+                                *slot = Some(e);
+                            }
+                            break;
+                        }
+                    }
+                }
+                reader_queue.close();
+            })
+            .map_err(|e| {
+                DataTransferError::network(format!(
+                    "Failed to spawn prefetch thread: {e}"
+                ))
+            })?;
+
+        if let Ok(mut stats_guard) = stats.lock() {
+            stats_guard.set_prefetch_capacity(max_packets);
+        }
+
+        Ok(Self {
+            queue,
+            error,
+            stats,
+            depth_updated: None,
+            primed: false,
+            _reader: reader,
+        })
+    }
+
+    /// 获取下一个数据包，缓冲区为空时等待并记录一次欠载
+    async fn next(
+        &mut self,
+    ) -> Result<Option<SourcePacket>> {
+        // 按间隔更新缓冲区深度，而不是每包更新
+        if self.depth_updated.is_none_or(|updated| {
+            updated.elapsed() >= DEPTH_UPDATE_INTERVAL
+        }) {
+            let depth = self.queue.depth();
+            if let Ok(mut stats_guard) = self.stats.lock() {
+                stats_guard.update_prefetch_depth(depth);
+            }
+            self.depth_updated = Some(Instant::now());
+        }
+
+        match self.poll_next()? {
+            FeedPoll::Packet(packet) => {
+                self.primed = true;
+                return Ok(Some(packet));
+            }
+            FeedPoll::End => return Ok(None),
+            FeedPoll::Pending => {}
+        }
+
+        let stalled_at = Instant::now();
+        let Some(packet) = self.queue.pop_async().await
+        else {
+            // 数据源结束时的等待不是欠载
+            return self.finish();
+        };
+        // 缓冲区被取空：发送节奏受限于磁盘读取（首次填充除外）
+        if self.primed {
+            if let Ok(mut stats_guard) = self.stats.lock() {
+                stats_guard
+                    .add_underrun(stalled_at.elapsed());
+            }
+        }
+        self.primed = true;
+        Ok(Some(packet))
+    }
+
+    /// 非阻塞地获取下一个数据包
+    fn poll_next(&mut self) -> Result<FeedPoll> {
+        match self.queue.try_pop() {
+            TryPop::Item(packet) => {
+                Ok(FeedPoll::Packet(packet))
+            }
+            TryPop::Empty => Ok(FeedPoll::Pending),
+            TryPop::Closed => {
+                self.finish()?;
+                Ok(FeedPoll::End)
+            }
+        }
+    }
+
+    /// 数据源结束时返回预读线程记录的错误
+    fn finish(&mut self) -> Result<Option<SourcePacket>> {
+        match self
+            .error
+            .lock()
+            .ok()
+            .and_then(|mut e| e.take())
+        {
+            Some(e) => Err(e),
+            None => Ok(None),
+        }
+    }
+}
+
+impl Drop for PrefetchBuffer {
+    fn drop(&mut self) {
+        // 关闭队列以唤醒并结束预读线程
+        self.queue.close();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    /// 依次返回若干数据包，最后可选地返回一个错误
+    struct VecSource {
+        packets: Vec<SourcePacket>,
+        fail_at_end: bool,
+    }
+
+    impl PacketSource for VecSource {
+        fn next_packet(
+            &mut self,
+        ) -> Result<Option<SourcePacket>> {
+            if !self.packets.is_empty() {
+                return Ok(Some(self.packets.remove(0)));
+            }
+            if self.fail_at_end {
+                return Err(DataTransferError::validation(
+                    "source",
+                    "read failed",
+                ));
+            }
+            Ok(None)
+        }
+    }
+
+    fn packets(count: u8) -> Vec<SourcePacket> {
+        (0..count)
+            .map(|i| SourcePacket {
+                data: vec![i],
+                timestamp: Utc::now(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_preserves_order() {
+        let stats =
+            Arc::new(Mutex::new(TransferStats::new()));
+        let source = VecSource {
+            packets: packets(10),
+            fail_at_end: false,
+        };
+        let mut feed = PacketFeed::Prefetch(
+            PrefetchBuffer::spawn(
+                Box::new(source),
+                2,
+                0,
+                Arc::clone(&stats),
+            )
+            .unwrap(),
+        );
+
+        let mut received = Vec::new();
+        while let Some(packet) = feed.next().await.unwrap()
+        {
+            received.push(packet.data[0]);
+        }
+        assert_eq!(received, (0..10).collect::<Vec<_>>());
+        assert_eq!(
+            stats.lock().unwrap().get_prefetch_capacity(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_reports_source_error() {
+        let stats =
+            Arc::new(Mutex::new(TransferStats::new()));
+        let source = VecSource {
+            packets: packets(1),
+            fail_at_end: true,
+        };
+        let mut feed = PacketFeed::Prefetch(
+            PrefetchBuffer::spawn(
+                Box::new(source),
+                4,
+                0,
+                stats,
+            )
+            .unwrap(),
+        );
+
+        assert!(feed.next().await.unwrap().is_some());
+        assert!(feed.next().await.is_err());
+    }
+
+    /// 从通道逐个取包，通道关闭时结束
+    struct ChannelSource(std::sync::mpsc::Receiver<u8>);
+
+    impl PacketSource for ChannelSource {
+        fn next_packet(
+            &mut self,
+        ) -> Result<Option<SourcePacket>> {
+            Ok(self.0.recv().ok().map(|i| SourcePacket {
+                data: vec![i],
+                timestamp: Utc::now(),
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_counts_only_mid_stream_underruns(
+    ) {
+        let stats =
+            Arc::new(Mutex::new(TransferStats::new()));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let mut feed = PacketFeed::Prefetch(
+            PrefetchBuffer::spawn(
+                Box::new(ChannelSource(receiver)),
+                4,
+                0,
+                Arc::clone(&stats),
+            )
+            .unwrap(),
+        );
+        std::thread::spawn(move || {
+            for i in 0..2 {
+                std::thread::sleep(Duration::from_millis(
+                    50,
+                ));
+                sender.send(i).unwrap();
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        });
+
+        // 首次填充与数据源结束时的等待都不计入欠载
+        assert!(feed.next().await.unwrap().is_some());
+        assert_eq!(
+            stats.lock().unwrap().get_underruns(),
+            0
+        );
+        assert!(feed.next().await.unwrap().is_some());
+        assert!(feed.next().await.unwrap().is_none());
+        assert_eq!(
+            stats.lock().unwrap().get_underruns(),
+            1
+        );
+    }
+}
@@ -0,0 +1,388 @@
+//! 有界队列模块
+//!
+//! 在异步任务与阻塞线程之间传递数据包，同时支持按包数和字节数限制容量。
+//! 生产者和消费者都可以选择阻塞等待（线程）或异步等待（tokio 任务）。
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// 可放入队列的元素
+pub trait QueueItem {
+    /// 元素占用的字节数（用于字节容量限制）
+    fn byte_size(&self) -> usize;
+}
+
+/// 入队失败原因
+#[derive(Debug)]
+pub enum PushError<T> {
+    /// 队列已满
+    Full(T),
+    /// 队列已关闭
+    Closed(T),
+}
+
+/// 非阻塞出队结果
+#[derive(Debug)]
+pub enum TryPop<T> {
+    /// 取到元素
+    Item(T),
+    /// 队列暂时为空
+    Empty,
+    /// 队列已关闭且为空
+    Closed,
+}
+
+/// 队列内部状态
+struct QueueState<T> {
+    items: VecDeque<T>,
+    bytes: usize,
+    closed: bool,
+}
+
+/// 有界队列
+pub struct BoundedQueue<T> {
+    state: Mutex<QueueState<T>>,
+    // 阻塞线程使用的条件变量
+    not_empty: Condvar,
+    not_full: Condvar,
+    // 异步任务使用的通知
+    item_available: Notify,
+    space_available: Notify,
+    max_items: usize,
+    max_bytes: usize,
+}
+
+impl<T: QueueItem> BoundedQueue<T> {
+    /// 创建有界队列
+    ///
+    /// # 参数
+    /// * `max_items` - 最大元素数量
+    /// * `max_bytes` - 最大字节数，0 表示不限制
+    pub fn new(max_items: usize, max_bytes: usize) -> Self {
+        Self {
+            state: Mutex::new(QueueState {
+                items: VecDeque::new(),
+                bytes: 0,
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            item_available: Notify::new(),
+            space_available: Notify::new(),
+            max_items: max_items.max(1),
+            max_bytes,
+        }
+    }
+
+    /// 获取内部状态锁（忽略锁中毒）
+    fn lock(&self) -> MutexGuard<'_, QueueState<T>> {
+        self.state.lock().unwrap_or_else(|poisoned| {
+            poisoned.into_inner()
+        })
+    }
+
+    /// 判断放入指定大小的元素后是否超出容量
+    ///
+    /// 队列为空时总是允许放入，避免超大元素永远无法入队。
+    fn is_full(
+        &self,
+        state: &QueueState<T>,
+        incoming_bytes: usize,
+    ) -> bool {
+        if state.items.is_empty() {
+            return false;
+        }
+        state.items.len() >= self.max_items
+            || (self.max_bytes > 0
+                && state.bytes + incoming_bytes
+                    > self.max_bytes)
+    }
+
+    /// 元素入队后通知等待的消费者
+    fn notify_item(&self) {
+        self.not_empty.notify_one();
+        self.item_available.notify_waiters();
+    }
+
+    /// 元素出队后通知等待的生产者
+    fn notify_space(&self) {
+        self.not_full.notify_all();
+        self.space_available.notify_waiters();
+    }
+
+    /// 尝试入队，队列满或已关闭时返回原元素
+    pub fn try_push(
+        &self,
+        item: T,
+    ) -> Result<(), PushError<T>> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(PushError::Closed(item));
+        }
+        if self.is_full(&state, item.byte_size()) {
+            return Err(PushError::Full(item));
+        }
+        state.bytes += item.byte_size();
+        state.items.push_back(item);
+        drop(state);
+        self.notify_item();
+        Ok(())
+    }
+
+    /// 入队，队列满时丢弃最旧的元素，返回被丢弃的元素数量
+    pub fn push_drop_oldest(
+        &self,
+        item: T,
+    ) -> Result<usize, T> {
+        let mut state = self.lock();
+        if state.closed {
+            return Err(item);
+        }
+        let mut dropped = 0;
+        while self.is_full(&state, item.byte_size()) {
+            if let Some(oldest) = state.items.pop_front() {
+                state.bytes -= oldest.byte_size();
+                dropped += 1;
+            }
+        }
+        state.bytes += item.byte_size();
+        state.items.push_back(item);
+        drop(state);
+        self.notify_item();
+        Ok(dropped)
+    }
+
+    /// 阻塞入队，直到有空间或队列关闭
+    pub fn push_blocking(&self, item: T) -> Result<(), T> {
+        let mut state = self.lock();
+        loop {
+            if state.closed {
+                return Err(item);
+            }
+            if !self.is_full(&state, item.byte_size()) {
+                break;
+            }
+            state =
+                self.not_full.wait(state).unwrap_or_else(
+                    |poisoned| poisoned.into_inner(),
+                );
+        }
+        state.bytes += item.byte_size();
+        state.items.push_back(item);
+        drop(state);
+        self.notify_item();
+        Ok(())
+    }
+
+    /// 异步入队，直到有空间或队列关闭
+    pub async fn push_async(
+        &self,
+        mut item: T,
+    ) -> Result<(), T> {
+        loop {
+            // 先注册通知再检查状态，避免丢失唤醒
+            let notified = self.space_available.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            match self.try_push(item) {
+                Ok(()) => return Ok(()),
+                Err(PushError::Closed(rejected)) => {
+                    return Err(rejected)
+                }
+                Err(PushError::Full(rejected)) => {
+                    item = rejected;
+                }
+            }
+
+            notified.await;
+        }
+    }
+
+    /// 非阻塞出队
+    pub fn try_pop(&self) -> TryPop<T> {
+        let mut state = self.lock();
+        match state.items.pop_front() {
+            Some(item) => {
+                state.bytes -= item.byte_size();
+                drop(state);
+                self.notify_space();
+                TryPop::Item(item)
+            }
+            None if state.closed => TryPop::Closed,
+            None => TryPop::Empty,
+        }
+    }
+
+    /// 异步出队，返回 `None` 表示队列已关闭且为空
+    pub async fn pop_async(&self) -> Option<T> {
+        loop {
+            // 先注册通知再检查状态，避免丢失唤醒
+            let notified = self.item_available.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            match self.try_pop() {
+                TryPop::Item(item) => return Some(item),
+                TryPop::Closed => return None,
+                TryPop::Empty => {}
+            }
+
+            notified.await;
+        }
+    }
+
+    /// 阻塞地批量出队，最多取出 `max` 个元素
+    ///
+    /// 队列为空时最多等待 `timeout`；返回 `None` 表示队列已关闭且为空。
+    pub fn pop_batch_blocking(
+        &self,
+        max: usize,
+        timeout: Duration,
+    ) -> Option<Vec<T>> {
+        let mut state = self.lock();
+        if state.items.is_empty() && !state.closed {
+            state = self
+                .not_empty
+                .wait_timeout(state, timeout)
+                .map(|(state, _)| state)
+                .unwrap_or_else(|poisoned| {
+                    poisoned.into_inner().0
+                });
+        }
+
+        if state.items.is_empty() {
+            return if state.closed {
+                None
+            } else {
+                Some(Vec::new())
+            };
+        }
+
+        let count = state.items.len().min(max.max(1));
+        let items: Vec<T> =
+            state.items.drain(..count).collect();
+        state.bytes -= items
+            .iter()
+            .map(|item| item.byte_size())
+            .sum::<usize>();
+        drop(state);
+        self.notify_space();
+        Some(items)
+    }
+
+    /// 关闭队列，唤醒所有等待者；已入队的元素仍可被取出
+    pub fn close(&self) {
+        self.lock().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+        self.item_available.notify_waiters();
+        self.space_available.notify_waiters();
+    }
+
+    /// 当前元素数量
+    pub fn depth(&self) -> usize {
+        self.lock().items.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    impl QueueItem for Vec<u8> {
+        fn byte_size(&self) -> usize {
+            self.len()
+        }
+    }
+
+    #[test]
+    fn test_drop_newest_when_full() {
+        let queue = BoundedQueue::new(2, 0);
+        queue.try_push(vec![1]).unwrap();
+        queue.try_push(vec![2]).unwrap();
+        assert!(matches!(
+            queue.try_push(vec![3]),
+            Err(PushError::Full(_))
+        ));
+        assert_eq!(queue.depth(), 2);
+    }
+
+    #[test]
+    fn test_drop_oldest_when_full() {
+        let queue = BoundedQueue::new(2, 0);
+        queue.try_push(vec![1]).unwrap();
+        queue.try_push(vec![2]).unwrap();
+        assert_eq!(
+            queue.push_drop_oldest(vec![3]).unwrap(),
+            1
+        );
+
+        let items = queue
+            .pop_batch_blocking(10, Duration::ZERO)
+            .unwrap();
+        assert_eq!(items, vec![vec![2], vec![3]]);
+    }
+
+    #[test]
+    fn test_byte_limit() {
+        let queue = BoundedQueue::new(100, 10);
+        queue.try_push(vec![0; 6]).unwrap();
+        assert!(queue.try_push(vec![0; 6]).is_err());
+        queue.try_push(vec![0; 4]).unwrap();
+    }
+
+    #[test]
+    fn test_close_drains_remaining_items() {
+        let queue = BoundedQueue::new(4, 0);
+        queue.try_push(vec![1]).unwrap();
+        queue.close();
+        assert!(queue.try_push(vec![2]).is_err());
+        assert_eq!(
+            queue.pop_batch_blocking(4, Duration::ZERO),
+            Some(vec![vec![1]])
+        );
+        assert!(queue
+            .pop_batch_blocking(1, Duration::ZERO)
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pop_async_waits_for_item() {
+        let queue = Arc::new(BoundedQueue::new(1, 0));
+
+        let producer = Arc::clone(&queue);
+        let handle = std::thread::spawn(move || {
+            producer.push_blocking(vec![1]).unwrap();
+            producer.push_blocking(vec![2]).unwrap();
+            producer.close();
+        });
+
+        assert_eq!(queue.pop_async().await, Some(vec![1]));
+        assert_eq!(queue.pop_async().await, Some(vec![2]));
+        assert_eq!(queue.pop_async().await, None);
+        handle.join().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_push_async_waits_for_space() {
+        let queue = Arc::new(BoundedQueue::new(1, 0));
+        queue.try_push(vec![1]).unwrap();
+
+        let consumer = Arc::clone(&queue);
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(20));
+            consumer.pop_batch_blocking(1, Duration::ZERO)
+        });
+
+        queue.push_async(vec![2]).await.unwrap();
+        assert_eq!(
+            handle.join().unwrap().unwrap(),
+            vec![vec![1]]
+        );
+        assert_eq!(queue.depth(), 1);
+    }
+}
@@ -0,0 +1,169 @@
+//! 重排序模块
+//!
+//! 多个接收套接字并行收包时，进入写入队列的顺序与捕获顺序并不严格一致。
+//! 重排序缓冲区按捕获时间暂存数据包，超过等待窗口后按时间顺序释放。
+//!
+//! 捕获时间可能来自网卡时钟（硬件时间戳），与系统时钟不一定一致，
+//! 因此释放时刻按数据包进入缓冲区时的单调时钟计算，捕获时间只用于排序。
+
+use chrono::{DateTime, Utc};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::time::Instant;
+
+/// 缓冲区中的元素（时间相同时按到达顺序排列）
+struct Entry<T> {
+    time: DateTime<Utc>,
+    seq: u64,
+    release_at: Instant,
+    item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.time == other.time && self.seq == other.seq
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.time, self.seq).cmp(&(other.time, other.seq))
+    }
+}
+
+/// 按时间戳排序的重排序缓冲区
+pub struct ReorderBuffer<T> {
+    heap: BinaryHeap<Reverse<Entry<T>>>,
+    next_seq: u64,
+}
+
+impl<T> ReorderBuffer<T> {
+    /// 创建空的重排序缓冲区
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+            next_seq: 0,
+        }
+    }
+
+    /// 放入一个元素，最早在 `release_at` 时释放
+    pub fn push(
+        &mut self,
+        time: DateTime<Utc>,
+        release_at: Instant,
+        item: T,
+    ) {
+        self.heap.push(Reverse(Entry {
+            time,
+            seq: self.next_seq,
+            release_at,
+            item,
+        }));
+        self.next_seq += 1;
+    }
+
+    /// 按时间顺序取出释放时刻不晚于 `now` 的元素
+    ///
+    /// 遇到尚未到期的最早元素即停止，其后的元素最多多等待一个窗口。
+    pub fn pop_ready(&mut self, now: Instant) -> Vec<T> {
+        let mut ready = Vec::new();
+        while let Some(Reverse(entry)) = self.heap.peek() {
+            if entry.release_at > now {
+                break;
+            }
+            if let Some(Reverse(entry)) = self.heap.pop() {
+                ready.push(entry.item);
+            }
+        }
+        ready
+    }
+
+    /// 按时间顺序取出全部元素
+    pub fn drain(&mut self) -> Vec<T> {
+        let mut items = Vec::with_capacity(self.heap.len());
+        while let Some(Reverse(entry)) = self.heap.pop() {
+            items.push(entry.item);
+        }
+        items
+    }
+}
+
+impl<T> Default for ReorderBuffer<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_releases_in_timestamp_order() {
+        let base = Utc::now();
+        let now = Instant::now();
+        let later = now + std::time::Duration::from_secs(1);
+        let mut buffer = ReorderBuffer::new();
+        buffer.push(
+            base + Duration::milliseconds(2),
+            now,
+            'c',
+        );
+        buffer.push(base, now, 'a');
+        buffer.push(
+            base + Duration::milliseconds(1),
+            now,
+            'b',
+        );
+        buffer.push(
+            base + Duration::milliseconds(10),
+            later,
+            'd',
+        );
+
+        assert_eq!(
+            buffer.pop_ready(now),
+            vec!['a', 'b', 'c']
+        );
+        assert_eq!(buffer.drain(), vec!['d']);
+    }
+
+    #[test]
+    fn test_equal_timestamps_keep_arrival_order() {
+        let base = Utc::now();
+        let now = Instant::now();
+        let mut buffer = ReorderBuffer::new();
+        buffer.push(base, now, 1);
+        buffer.push(base, now, 2);
+        buffer.push(base, now, 3);
+        assert_eq!(buffer.pop_ready(now), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_capture_clock_ahead_of_system_clock() {
+        // 网卡时钟领先系统时钟（如 TAI 领先约 37 秒）时仍按到达时刻释放
+        let ahead = Utc::now() + Duration::seconds(37);
+        let now = Instant::now();
+        let mut buffer = ReorderBuffer::new();
+        buffer.push(
+            ahead + Duration::milliseconds(1),
+            now,
+            'b',
+        );
+        buffer.push(ahead, now, 'a');
+        assert_eq!(buffer.pop_ready(now), vec!['a', 'b']);
+        assert!(buffer.drain().is_empty());
+    }
+}
@@ -0,0 +1,248 @@
+//! 数据集写入线程模块
+//!
+//! 将磁盘写入从接收循环中剥离到独立线程，磁盘抖动不会再阻塞 recv
+
+use chrono::{DateTime, Utc};
+use pcapfile_io::DataPacket;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::error;
+
+use crate::app::config::options::OverflowPolicy;
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::dataset::rotation::RotatingWriter;
+use crate::core::dataset::sidecar::PacketMeta;
+use crate::core::pipeline::queue::{
+    BoundedQueue, PushError, QueueItem,
+};
+use crate::core::pipeline::reorder::ReorderBuffer;
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::window::PacketSizes;
+
+/// 写入线程单次从队列取出的最大包数
+const WRITE_BATCH_SIZE: usize = 256;
+
+/// 已接收、等待写入的数据包
+#[derive(Debug, Clone)]
+pub struct ReceivedPacket {
+    /// 数据包内容
+    pub data: Vec<u8>,
+    /// 捕获时间
+    pub capture_time: DateTime<Utc>,
+    /// 来源地址、接收端口等元数据
+    pub meta: PacketMeta,
+}
+
+impl QueueItem for ReceivedPacket {
+    fn byte_size(&self) -> usize {
+        self.data.len()
+    }
+}
+
+/// 接收队列类型
+pub type PacketQueue = BoundedQueue<ReceivedPacket>;
+
+/// 按溢出策略将数据包放入队列，返回因溢出丢弃的包数
+pub async fn enqueue_packet(
+    queue: &PacketQueue,
+    packet: ReceivedPacket,
+    policy: OverflowPolicy,
+) -> usize {
+    match policy {
+        OverflowPolicy::Block => {
+            // 队列已关闭时写入线程已退出，数据包只能丢弃
+            match queue.push_async(packet).await {
+                Ok(()) => 0,
+                Err(_) => 1,
+            }
+        }
+        OverflowPolicy::DropNewest => {
+            match queue.try_push(packet) {
+                Ok(()) => 0,
+                Err(PushError::Full(_))
+                | Err(PushError::Closed(_)) => 1,
+            }
+        }
+        OverflowPolicy::DropOldest => {
+            queue.push_drop_oldest(packet).unwrap_or(1)
+        }
+    }
+}
+
+/// 启动数据集写入线程
+///
+/// 数据包进入重排序缓冲区后等待 `reorder_window`，按捕获时间顺序写入；
+/// 线程在队列关闭且取空后完成写入并返回。
+pub fn spawn_dataset_writer(
+    writer: RotatingWriter,
+    queue: Arc<PacketQueue>,
+    stats: Arc<Mutex<TransferStats>>,
+    reorder_window: Duration,
+) -> Result<JoinHandle<Result<()>>> {
+    std::thread::Builder::new()
+        .name("dataset-writer".to_string())
+        .spawn(move || {
+            let result = run_writer_loop(
+                writer,
+                &queue,
+                &stats,
+                reorder_window,
+            );
+            // 写入线程退出后不再接受新的数据包
+            queue.close();
+            result
+        })
+        .map_err(|e| {
+            DataTransferError::network(format!(
+                "Failed to spawn writer thread: {e}"
+            ))
+        })
+}
+
+/// 等待写入线程结束
+pub async fn join_dataset_writer(
+    handle: JoinHandle<Result<()>>,
+) -> Result<()> {
+    tokio::task::spawn_blocking(move || handle.join())
+        .await
+        .map_err(|e| {
+            DataTransferError::network(format!(
+                "Failed to join writer thread: {e}"
+            ))
+        })?
+        .map_err(|_| {
+            DataTransferError::network(
+                "Writer thread panicked",
+            )
+        })?
+}
+
+/// 写入线程主循环
+fn run_writer_loop(
+    mut writer: RotatingWriter,
+    queue: &PacketQueue,
+    stats: &Mutex<TransferStats>,
+    reorder_window: Duration,
+) -> Result<()> {
+    let mut reorder = ReorderBuffer::new();
+    // 启用重排序时需要及时释放到期的数据包
+    let pop_timeout = if reorder_window.is_zero() {
+        Duration::from_millis(100)
+    } else {
+        reorder_window.min(Duration::from_millis(100))
+    };
+
+    loop {
+        let ready = match queue.pop_batch_blocking(
+            WRITE_BATCH_SIZE,
+            pop_timeout,
+        ) {
+            // 单个套接字时无需重排序，直接按到达顺序写入
+            Some(packets) if reorder_window.is_zero() => {
+                packets
+            }
+            Some(packets) => {
+                // 释放时刻按到达时的单调时钟计算，与捕获时间的时钟来源无关
+                let release_at =
+                    Instant::now() + reorder_window;
+                for packet in packets {
+                    reorder.push(
+                        packet.capture_time,
+                        release_at,
+                        packet,
+                    );
+                }
+                reorder.pop_ready(Instant::now())
+            }
+            // 队列已关闭：按顺序写出缓冲区中剩余的数据包
+            None => {
+                write_packets(
+                    &mut writer,
+                    reorder.drain(),
+                    queue,
+                    stats,
+                );
+                break;
+            }
+        };
+        write_packets(&mut writer, ready, queue, stats);
+    }
+
+    // 完成写入
+    writer.finalize()?;
+    Ok(())
+}
+
+/// 将一批数据包写入数据集并更新统计信息
+fn write_packets(
+    writer: &mut RotatingWriter,
+    packets: Vec<ReceivedPacket>,
+    queue: &PacketQueue,
+    stats: &Mutex<TransferStats>,
+) {
+    let mut written = Vec::with_capacity(packets.len());
+    let mut errors = 0;
+    let mut rotated = false;
+    let mut sizes = PacketSizes::default();
+
+    for received in packets {
+        let capture_time = received.capture_time;
+        let bytes_received = received.data.len();
+
+        // 创建数据包
+        match DataPacket::from_datetime(
+            capture_time,
+            received.data,
+        ) {
+            Ok(packet) => {
+                // 写入数据包（必要时先轮转数据集）
+                match writer.write_packet(
+                    &packet,
+                    capture_time,
+                    &received.meta,
+                ) {
+                    Ok(did_rotate) => {
+                        rotated |= did_rotate;
+                        sizes.record(bytes_received);
+                        written.push((
+                            bytes_received,
+                            capture_time,
+                        ));
+                    }
+                    Err(e) => {
+                        error!(
+                            "Failed to write packet: {}",
+                            e
+                        );
+                        errors += 1;
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Failed to create packet: {}", e);
+                errors += 1;
+            }
+        }
+    }
+
+    // 每批次只获取一次统计锁
+    let depth = queue.depth();
+    if let Ok(mut stats_guard) = stats.lock() {
+        for (bytes_received, capture_time) in written {
+            stats_guard.update_with_timestamp(
+                bytes_received,
+                capture_time,
+            );
+        }
+        stats_guard.record_packet_sizes(&sizes);
+        stats_guard.add_errors(errors);
+        stats_guard.update_queue_depth(depth);
+        if rotated {
+            stats_guard.set_dataset(
+                writer.dataset_name(),
+                writer.seq(),
+            );
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! 测试头模块
+//!
+//! 发送器可在每个数据包前插入固定格式的测试头，接收器据此识别数据流
+//! 与序号。测试头使用网络字节序：
+//!
+//! | 偏移 | 长度 | 内容                |
+//! |------|------|---------------------|
+//! | 0    | 2    | 魔数 `PT`（0x5054） |
+//! | 2    | 1    | 版本号              |
+//! | 3    | 1    | 标志位              |
+//! | 4    | 4    | 数据流 ID           |
+//! | 8    | 8    | 序号                |
+//! | 16   | 8    | 发送时间（可选）    |
+//! | -    | 8    | 反射计数（可选）    |
+//!
+//! 标志位第 0 位表示测试头带有发送时间（Unix 纪元以来的纳秒数），
+//! 第 1 位表示该包是反射器回送的包，并在发送时间之后带有反射器
+//! 已收到的该数据流包数。
+
+use chrono::Utc;
+
+/// 测试头魔数
+pub const HEADER_MAGIC: [u8; 2] = *b"PT";
+
+/// 测试头版本号
+pub const HEADER_VERSION: u8 = 1;
+
+/// 测试头基本长度（字节）
+pub const HEADER_LEN: usize = 16;
+
+/// 可选字段长度（字节）
+const FIELD_LEN: usize = 8;
+
+/// 标志位：带有发送时间
+const FLAG_TIMESTAMP: u8 = 0x01;
+
+/// 标志位：反射器回送的包
+const FLAG_REFLECTED: u8 = 0x02;
+
+/// 解析后的测试头
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TestHeader {
+    /// 数据流 ID
+    pub stream_id: u32,
+    /// 序号
+    pub seq: u64,
+    /// 发送时间（Unix 纪元以来的纳秒数）
+    pub send_time_ns: Option<u64>,
+    /// 反射器已收到的该数据流包数（仅回送的包）
+    pub reflected_count: Option<u64>,
+}
+
+impl TestHeader {
+    /// 测试头长度（字节）
+    pub fn encoded_len(&self) -> usize {
+        let optional =
+            [self.send_time_ns, self.reflected_count]
+                .iter()
+                .filter(|field| field.is_some())
+                .count();
+        HEADER_LEN + optional * FIELD_LEN
+    }
+
+    /// 写入测试头
+    pub fn encode(&self, out: &mut Vec<u8>) {
+        let mut flags = 0;
+        if self.send_time_ns.is_some() {
+            flags |= FLAG_TIMESTAMP;
+        }
+        if self.reflected_count.is_some() {
+            flags |= FLAG_REFLECTED;
+        }
+
+        out.extend_from_slice(&HEADER_MAGIC);
+        out.push(HEADER_VERSION);
+        out.push(flags);
+        out.extend_from_slice(
+            &self.stream_id.to_be_bytes(),
+        );
+        out.extend_from_slice(&self.seq.to_be_bytes());
+        for value in
+            [self.send_time_ns, self.reflected_count]
+                .into_iter()
+                .flatten()
+        {
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+    }
+
+    /// 从负载开头解析测试头，魔数或版本不匹配时返回 `None`
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let header = data.get(..HEADER_LEN)?;
+        if header[..2] != HEADER_MAGIC
+            || header[2] != HEADER_VERSION
+        {
+            return None;
+        }
+
+        // 按标志位依次读取可选字段
+        let flags = header[3];
+        let mut offset = HEADER_LEN;
+        let mut optional =
+            |flag: u8| -> Option<Option<u64>> {
+                if flags & flag == 0 {
+                    return Some(None);
+                }
+                let bytes =
+                    data.get(offset..offset + FIELD_LEN)?;
+                offset += FIELD_LEN;
+                Some(Some(u64::from_be_bytes(
+                    bytes.try_into().ok()?,
+                )))
+            };
+        let send_time_ns = optional(FLAG_TIMESTAMP)?;
+        let reflected_count = optional(FLAG_REFLECTED)?;
+
+        Some(Self {
+            stream_id: u32::from_be_bytes(
+                header[4..8].try_into().ok()?,
+            ),
+            seq: u64::from_be_bytes(
+                header[8..16].try_into().ok()?,
+            ),
+            send_time_ns,
+            reflected_count,
+        })
+    }
+}
+
+/// 发送端测试头生成器，为每个数据包分配递增序号
+#[derive(Debug)]
+pub struct HeaderStamper {
+    stream_id: u32,
+    next_seq: u64,
+    timestamps: bool,
+}
+
+impl HeaderStamper {
+    /// 创建生成器，序号从 0 开始
+    pub fn new(stream_id: u32) -> Self {
+        Self {
+            stream_id,
+            next_seq: 0,
+            timestamps: false,
+        }
+    }
+
+    /// 在测试头中写入发送时间
+    pub fn with_timestamps(mut self) -> Self {
+        self.timestamps = true;
+        self
+    }
+
+    /// 已生成的测试头数量
+    pub fn stamped(&self) -> u64 {
+        self.next_seq
+    }
+
+    /// 生成带测试头的负载（发送时间取调用时刻）
+    pub fn stamp(&mut self, payload: &[u8]) -> Vec<u8> {
+        let header = TestHeader {
+            stream_id: self.stream_id,
+            seq: self.next_seq,
+            send_time_ns: self.timestamps.then(|| {
+                Utc::now()
+                    .timestamp_nanos_opt()
+                    .unwrap_or_default()
+                    as u64
+            }),
+            reflected_count: None,
+        };
+        let mut out = Vec::with_capacity(
+            header.encoded_len() + payload.len(),
+        );
+        header.encode(&mut out);
+        out.extend_from_slice(payload);
+        self.next_seq += 1;
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stamp_and_decode() {
+        let mut stamper = HeaderStamper::new(7);
+        stamper.stamp(b"first");
+        let packet = stamper.stamp(b"second");
+
+        assert_eq!(packet.len(), HEADER_LEN + 6);
+        assert_eq!(&packet[HEADER_LEN..], b"second");
+        assert_eq!(
+            TestHeader::decode(&packet),
+            Some(TestHeader {
+                stream_id: 7,
+                seq: 1,
+                send_time_ns: None,
+                reflected_count: None,
+            })
+        );
+        assert!(TestHeader::decode(b"PT").is_none());
+        assert!(
+            TestHeader::decode(&[0; HEADER_LEN]).is_none()
+        );
+    }
+
+    #[test]
+    fn test_timestamp_round_trip() {
+        let mut stamper =
+            HeaderStamper::new(1).with_timestamps();
+        let packet = stamper.stamp(b"x");
+        let header = TestHeader::decode(&packet).unwrap();
+
+        assert_eq!(
+            header.encoded_len(),
+            HEADER_LEN + FIELD_LEN
+        );
+        assert_eq!(&packet[header.encoded_len()..], b"x");
+        assert!(header.send_time_ns.unwrap() > 0);
+        // 截断的发送时间字段无法解析
+        assert!(TestHeader::decode(
+            &packet[..HEADER_LEN + 4]
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! 直方图模块
+//!
+//! 对数分桶直方图，以固定内存增量估算时延分位数
+
+/// 每个 2 的幂区间内的子桶位数（精度约 3%）
+const SUB_BUCKET_BITS: u32 = 5;
+
+/// 每个 2 的幂区间内的子桶数量
+const SUB_BUCKETS: u64 = 1 << SUB_BUCKET_BITS;
+
+/// 直方图桶数量（覆盖全部 u64 取值）
+const BUCKET_COUNT: usize = (SUB_BUCKETS
+    + (64 - SUB_BUCKET_BITS as u64) * SUB_BUCKETS)
+    as usize;
+
+/// 对数分桶直方图，用于增量估算分位数
+#[derive(Debug)]
+pub struct Histogram {
+    buckets: Vec<u64>,
+    count: u64,
+}
+
+impl Histogram {
+    /// 创建空直方图
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; BUCKET_COUNT],
+            count: 0,
+        }
+    }
+
+    /// 记录一个取值
+    pub fn record(&mut self, value: u64) {
+        self.buckets[bucket_index(value)] += 1;
+        self.count += 1;
+    }
+
+    /// 估算分位数（返回所在桶的上界）
+    pub fn quantile(&self, quantile: f64) -> u64 {
+        if self.count == 0 {
+            return 0;
+        }
+        let target = ((self.count as f64 * quantile).ceil()
+            as u64)
+            .max(1);
+        let mut seen = 0;
+        for (index, count) in
+            self.buckets.iter().enumerate()
+        {
+            seen += count;
+            if seen >= target {
+                return bucket_upper_bound(index);
+            }
+        }
+        bucket_upper_bound(BUCKET_COUNT - 1)
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 计算取值所在的桶序号
+fn bucket_index(value: u64) -> usize {
+    if value < SUB_BUCKETS {
+        return value as usize;
+    }
+    let exponent = 63 - value.leading_zeros();
+    let shift = exponent - SUB_BUCKET_BITS;
+    let sub = (value >> shift) - SUB_BUCKETS;
+    (SUB_BUCKETS + u64::from(shift) * SUB_BUCKETS + sub)
+        as usize
+}
+
+/// 桶内取值的上界
+fn bucket_upper_bound(index: usize) -> u64 {
+    let index = index as u64;
+    if index < SUB_BUCKETS {
+        return index;
+    }
+    let shift = (index - SUB_BUCKETS) / SUB_BUCKETS;
+    let sub = (index - SUB_BUCKETS) % SUB_BUCKETS;
+    let lower = (SUB_BUCKETS + sub) << shift;
+    lower.saturating_add((1u64 << shift) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_buckets() {
+        for value in [0, 31, 32, 1000, 1_000_000, u64::MAX]
+        {
+            let index = bucket_index(value);
+            assert!(bucket_upper_bound(index) >= value);
+            if index > 0 {
+                assert!(
+                    bucket_upper_bound(index - 1) < value
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! 时延测量模块
+//!
+//! 根据测试头中的发送时间计算单向时延（最小、平均、最大与 P99）
+//! 以及 RFC 3550 定义的到达间隔抖动。
+//!
+//! 单向时延依赖收发两端的时钟同步，未同步时可配置时钟偏差
+//! （接收端时钟减去发送端时钟），计算时从时延中扣除。
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+
+use crate::app::config::options::LatencyOptions;
+use crate::core::probe::header::TestHeader;
+use crate::core::probe::histogram::Histogram;
+
+/// 数据流标识：发送方地址与数据流 ID
+type StreamKey = (Option<SocketAddr>, u32);
+
+/// 时延统计（单位：纳秒）
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize,
+)]
+pub struct LatencyStats {
+    /// 样本数量
+    pub samples: u64,
+    /// 最小时延
+    pub min_ns: i64,
+    /// 平均时延
+    pub avg_ns: i64,
+    /// 最大时延
+    pub max_ns: i64,
+    /// P99 时延（按直方图桶估算）
+    pub p99_ns: i64,
+    /// RFC 3550 到达间隔抖动
+    pub jitter_ns: f64,
+    /// 时延为负的样本数量（通常表示时钟未同步）
+    pub negative: u64,
+}
+
+/// 时延跟踪器
+#[derive(Debug)]
+pub struct LatencyTracker {
+    clock_offset_ns: i64,
+    histogram: Histogram,
+    sum_ns: i128,
+    stats: LatencyStats,
+    /// 各数据流上一个数据包的传输时间（接收时间减发送时间）
+    transits: HashMap<StreamKey, i64>,
+    /// 各数据流的抖动估计
+    jitters: HashMap<StreamKey, f64>,
+}
+
+impl LatencyTracker {
+    /// 根据选项创建跟踪器，未启用时返回 `None`
+    pub fn from_options(
+        options: &LatencyOptions,
+    ) -> Option<Self> {
+        options.enabled.then(|| Self {
+            clock_offset_ns: options
+                .clock_offset_us
+                .saturating_mul(1000),
+            histogram: Histogram::new(),
+            sum_ns: 0,
+            stats: LatencyStats::default(),
+            transits: HashMap::new(),
+            jitters: HashMap::new(),
+        })
+    }
+
+    /// 当前统计（P99 在读取时从直方图计算）
+    pub fn stats(&self) -> LatencyStats {
+        LatencyStats {
+            p99_ns: self.histogram.quantile(0.99) as i64,
+            ..self.stats
+        }
+    }
+
+    /// 记录一个数据包，没有发送时间的数据包被忽略
+    pub fn observe(
+        &mut self,
+        source: Option<SocketAddr>,
+        data: &[u8],
+        receive_time: DateTime<Utc>,
+    ) {
+        let Some(header) = TestHeader::decode(data) else {
+            return;
+        };
+        let (Some(send_ns), Some(receive_ns)) = (
+            header.send_time_ns,
+            receive_time.timestamp_nanos_opt(),
+        ) else {
+            return;
+        };
+
+        // 发送时间来自网络，损坏或伪造时可能超出范围，此时跳过该样本
+        let Some((transit, delay)) = i64::try_from(send_ns)
+            .ok()
+            .and_then(|send_ns| {
+                receive_ns.checked_sub(send_ns)
+            })
+            .and_then(|transit| {
+                transit
+                    .checked_sub(self.clock_offset_ns)
+                    .map(|delay| (transit, delay))
+            })
+        else {
+            return;
+        };
+        self.record_delay(delay);
+
+        // RFC 3550：J += (|D| - J) / 16，D 为相邻两包传输时间之差
+        let key = (source, header.stream_id);
+        if let Some(d) =
+            self.transits.insert(key, transit).and_then(
+                |previous| transit.checked_sub(previous),
+            )
+        {
+            let d = d.unsigned_abs() as f64;
+            let jitter =
+                self.jitters.entry(key).or_insert(0.0);
+            *jitter += (d - *jitter) / 16.0;
+        }
+        // 多个数据流时报告最大的抖动
+        self.stats.jitter_ns = self
+            .jitters
+            .values()
+            .copied()
+            .fold(0.0, f64::max);
+    }
+
+    /// 记录一个时延样本
+    fn record_delay(&mut self, delay: i64) {
+        let stats = &mut self.stats;
+        if stats.samples == 0 {
+            stats.min_ns = delay;
+            stats.max_ns = delay;
+        } else {
+            stats.min_ns = stats.min_ns.min(delay);
+            stats.max_ns = stats.max_ns.max(delay);
+        }
+        stats.samples += 1;
+        if delay < 0 {
+            stats.negative += 1;
+        }
+
+        self.sum_ns += i128::from(delay);
+        stats.avg_ns = (self.sum_ns
+            / i128::from(stats.samples))
+            as i64;
+        self.histogram.record(delay.max(0) as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::probe::header::HEADER_LEN;
+
+    fn packet(stream_id: u32, send_ns: u64) -> Vec<u8> {
+        let mut out = Vec::with_capacity(HEADER_LEN + 8);
+        TestHeader {
+            stream_id,
+            seq: 0,
+            send_time_ns: Some(send_ns),
+            reflected_count: None,
+        }
+        .encode(&mut out);
+        out
+    }
+
+    fn at(ns: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp_nanos(ns)
+    }
+
+    #[test]
+    fn test_delay_and_percentile() {
+        let mut tracker =
+            LatencyTracker::from_options(&LatencyOptions {
+                enabled: true,
+                clock_offset_us: 1,
+            })
+            .unwrap();
+
+        // 传输时间 2..=101 微秒，扣除 1 微秒时钟偏差
+        for i in 1..=100i64 {
+            let send = 1_000_000_000 * i;
+            tracker.observe(
+                None,
+                &packet(1, send as u64),
+                at(send + (i + 1) * 1000),
+            );
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.min_ns, 1000);
+        assert_eq!(stats.max_ns, 100_000);
+        assert_eq!(stats.avg_ns, 50_500);
+        // P99 误差不超过桶宽
+        assert!((99_000..=102_400).contains(&stats.p99_ns));
+        assert_eq!(stats.negative, 0);
+    }
+
+    #[test]
+    fn test_rfc3550_jitter() {
+        let mut tracker =
+            LatencyTracker::from_options(&LatencyOptions {
+                enabled: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        // 传输时间交替为 1ms 与 3ms，|D| 恒为 2ms
+        for i in 0..200i64 {
+            let send = i * 10_000_000;
+            let transit = if i % 2 == 0 {
+                1_000_000
+            } else {
+                3_000_000
+            };
+            tracker.observe(
+                None,
+                &packet(1, send as u64),
+                at(send + transit),
+            );
+        }
+        let jitter = tracker.stats().jitter_ns;
+        assert!((jitter - 2_000_000.0).abs() < 1_000.0);
+    }
+
+    #[test]
+    fn test_out_of_range_times_are_skipped() {
+        let mut tracker =
+            LatencyTracker::from_options(&LatencyOptions {
+                enabled: true,
+                ..Default::default()
+            })
+            .unwrap();
+
+        // 发送时间超出 i64 范围的样本被忽略
+        tracker.observe(None, &packet(1, u64::MAX), at(0));
+        assert_eq!(tracker.stats().samples, 0);
+
+        // 传输时间之差溢出时只记录时延，不更新抖动
+        tracker.observe(None, &packet(1, 0), at(i64::MAX));
+        tracker.observe(
+            None,
+            &packet(1, 1_000_000_000_000_000_000),
+            at(0),
+        );
+        let stats = tracker.stats();
+        assert_eq!(stats.samples, 2);
+        assert_eq!(stats.jitter_ns, 0.0);
+    }
+}
@@ -0,0 +1,11 @@
+//! 测试探针模块
+//!
+//! 测试头的生成与解析、基于序号的丢包与乱序统计、时延测量以及
+//! 反射器回送与往返时延测量
+
+pub mod header;
+pub mod histogram;
+pub mod latency;
+pub mod reflector;
+pub mod rtt;
+pub mod sequence;
@@ -0,0 +1,159 @@
+//! 反射器模块
+//!
+//! 接收器以反射模式运行时，把收到的每个数据包（完整或只取开头若干
+//! 字节）回送给发送方。带测试头的数据包回送时设置反射标志，并写入
+//! 反射器已收到的该数据流包数，发送端据此区分去程与回程丢包。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+
+use crate::app::config::options::{
+    ReflectMode, ReflectorOptions,
+};
+use crate::core::probe::header::TestHeader;
+
+/// 回送前缀的最大字节数（UDP 最大负载）
+pub const MAX_PREFIX_BYTES: usize = 65507;
+
+/// 数据流标识：发送方地址与数据流 ID
+type StreamKey = (SocketAddr, u32);
+
+/// 数据包反射器，由所有接收任务共享
+#[derive(Debug)]
+pub struct Reflector {
+    /// 回送的最大字节数（`None` 表示完整回送）
+    limit: Option<usize>,
+    /// 各数据流已收到的包数
+    counts: Mutex<HashMap<StreamKey, u64>>,
+}
+
+impl Reflector {
+    /// 根据选项创建反射器，未启用时返回 `None`
+    pub fn from_options(
+        options: &ReflectorOptions,
+    ) -> Option<Self> {
+        let limit = match options.mode {
+            ReflectMode::Off => return None,
+            ReflectMode::Full => None,
+            ReflectMode::Prefix => {
+                Some(options.prefix_bytes)
+            }
+        };
+        Some(Self {
+            limit,
+            counts: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// 生成回送给 `source` 的数据包
+    ///
+    /// 测试头总是完整保留（截断长度不足时按测试头长度回送）。
+    pub fn reflect(
+        &self,
+        source: SocketAddr,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let Some(header) = TestHeader::decode(data) else {
+            let len =
+                self.limit.map_or(data.len(), |limit| {
+                    limit.min(data.len())
+                });
+            return data[..len].to_vec();
+        };
+
+        let count = match self.counts.lock() {
+            Ok(mut counts) => {
+                let count = counts
+                    .entry((source, header.stream_id))
+                    .or_insert(0);
+                *count += 1;
+                *count
+            }
+            Err(_) => 0,
+        };
+        let echo_header = TestHeader {
+            reflected_count: Some(count),
+            ..header
+        };
+
+        let payload = &data[header.encoded_len()..];
+        let header_len = echo_header.encoded_len();
+        let payload_len =
+            self.limit.map_or(payload.len(), |limit| {
+                limit
+                    .saturating_sub(header_len)
+                    .min(payload.len())
+            });
+        let mut out =
+            Vec::with_capacity(header_len + payload_len);
+        echo_header.encode(&mut out);
+        out.extend_from_slice(&payload[..payload_len]);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::probe::header::{
+        HeaderStamper, HEADER_LEN,
+    };
+
+    fn reflector(
+        mode: ReflectMode,
+        prefix_bytes: usize,
+    ) -> Reflector {
+        Reflector::from_options(&ReflectorOptions {
+            mode,
+            prefix_bytes,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reflect_counts_per_stream() {
+        let reflector = reflector(ReflectMode::Full, 0);
+        let source: SocketAddr =
+            "127.0.0.1:5000".parse().unwrap();
+        let mut stamper =
+            HeaderStamper::new(3).with_timestamps();
+
+        stamper.stamp(b"lost on the way");
+        reflector.reflect(source, &stamper.stamp(b"a"));
+        let packet = stamper.stamp(b"payload");
+        let echo = reflector.reflect(source, &packet);
+
+        let original = TestHeader::decode(&packet).unwrap();
+        let header = TestHeader::decode(&echo).unwrap();
+        assert_eq!(header.seq, 2);
+        assert_eq!(
+            header.send_time_ns,
+            original.send_time_ns
+        );
+        assert_eq!(header.reflected_count, Some(2));
+        assert_eq!(
+            &echo[header.encoded_len()..],
+            b"payload"
+        );
+    }
+
+    #[test]
+    fn test_reflect_prefix() {
+        let reflector = reflector(ReflectMode::Prefix, 4);
+        let source: SocketAddr =
+            "127.0.0.1:5000".parse().unwrap();
+
+        // 没有测试头时只回送前缀
+        assert_eq!(
+            reflector.reflect(source, b"abcdefgh"),
+            b"abcd"
+        );
+
+        // 测试头不会被截断
+        let packet = HeaderStamper::new(1).stamp(&[0; 100]);
+        let echo = reflector.reflect(source, &packet);
+        assert_eq!(echo.len(), HEADER_LEN + 8);
+        assert!(TestHeader::decode(&echo).is_some());
+    }
+}
@@ -0,0 +1,286 @@
+//! 往返时延模块
+//!
+//! 发送端接收反射器回送的数据包，根据测试头中的发送时间计算往返时延。
+//! 回送包按序号与已发送的包匹配，重复的回送包只计一次。
+//! 回送包中带有反射器已收到的包数，据此分别估算两个方向的丢包：
+//!
+//! - 去程丢包：已回送的最大序号之前发出、但反射器没有收到的包；
+//! - 回程丢包：反射器已回送、但发送端没有收到的包；
+//! - 最后一个回送包之后发出的包无法区分方向，单独记为未应答。
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::core::probe::header::TestHeader;
+use crate::core::probe::histogram::Histogram;
+
+/// 发送结束后等待回送数据包的最长时间（毫秒）
+pub const MAX_ECHO_TIMEOUT_MS: u64 = 60_000;
+
+/// 匹配回送包的序号窗口（包数），早于窗口的回送包不再匹配
+const MATCH_WINDOW: u64 = 1 << 16;
+
+/// 往返时延统计（时间单位：纳秒）
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize,
+)]
+pub struct RttStats {
+    /// 已发送的测试包数量
+    pub sent: u64,
+    /// 与已发送的包匹配的回送包数量
+    pub echoes: u64,
+    /// 重复或早于匹配窗口而被忽略的回送包数量
+    pub ignored: u64,
+    /// 最小往返时延
+    pub min_ns: u64,
+    /// 平均往返时延
+    pub avg_ns: u64,
+    /// 最大往返时延
+    pub max_ns: u64,
+    /// P99 往返时延（按直方图桶估算）
+    pub p99_ns: u64,
+    /// 去程丢包数
+    pub forward_lost: u64,
+    /// 回程丢包数
+    pub return_lost: u64,
+    /// 最后一个回送包之后未得到应答的包数
+    pub unanswered: u64,
+}
+
+/// 往返时延跟踪器
+#[derive(Debug)]
+pub struct RttTracker {
+    stream_id: u32,
+    histogram: Histogram,
+    sum_ns: u128,
+    echoes: u64,
+    ignored: u64,
+    min_ns: u64,
+    max_ns: u64,
+    /// 已回送的最大序号
+    highest_seq: Option<u64>,
+    /// 反射器报告的最大已收包数
+    reflected: u64,
+    /// 匹配窗口的起始序号
+    window_start: u64,
+    /// 窗口内已匹配的序号（按 `seq % MATCH_WINDOW` 存放的位图）
+    answered: Vec<u64>,
+}
+
+impl RttTracker {
+    /// 创建跟踪器，只统计指定数据流的回送包
+    pub fn new(stream_id: u32) -> Self {
+        Self {
+            stream_id,
+            histogram: Histogram::new(),
+            sum_ns: 0,
+            echoes: 0,
+            ignored: 0,
+            min_ns: 0,
+            max_ns: 0,
+            highest_seq: None,
+            reflected: 0,
+            window_start: 0,
+            answered: vec![0; (MATCH_WINDOW / 64) as usize],
+        }
+    }
+
+    /// 当前统计，`sent` 为已发送的测试包数量
+    pub fn stats(&self, sent: u64) -> RttStats {
+        // 最后一个回送包及之前发出的包数
+        let covered = self
+            .highest_seq
+            .map_or(0, |seq| seq.saturating_add(1))
+            .min(sent);
+        // 反射器收到的包中已回送到发送端的部分之外即为回程丢失
+        let reflected = self.reflected.min(covered);
+        RttStats {
+            sent,
+            echoes: self.echoes,
+            ignored: self.ignored,
+            min_ns: self.min_ns,
+            avg_ns: match self.echoes {
+                0 => 0,
+                echoes => {
+                    (self.sum_ns / u128::from(echoes))
+                        as u64
+                }
+            },
+            max_ns: self.max_ns,
+            p99_ns: self.histogram.quantile(0.99),
+            forward_lost: covered
+                .saturating_sub(reflected.max(self.echoes)),
+            return_lost: reflected
+                .saturating_sub(self.echoes),
+            unanswered: sent.saturating_sub(covered),
+        }
+    }
+
+    /// 在匹配窗口中登记序号，重复或早于窗口时返回 `false`
+    fn match_seq(&mut self, seq: u64) -> bool {
+        if seq < self.window_start {
+            return false;
+        }
+        // 序号超出窗口时向前滑动，清除被移出窗口的序号
+        let end = self.window_start + MATCH_WINDOW;
+        if seq >= end {
+            let start = seq - MATCH_WINDOW + 1;
+            if start - self.window_start >= MATCH_WINDOW {
+                self.answered.fill(0);
+            } else {
+                for old in self.window_start..start {
+                    let slot = old % MATCH_WINDOW;
+                    self.answered[(slot / 64) as usize] &=
+                        !(1 << (slot % 64));
+                }
+            }
+            self.window_start = start;
+        }
+
+        let slot = seq % MATCH_WINDOW;
+        let word = &mut self.answered[(slot / 64) as usize];
+        let bit = 1 << (slot % 64);
+        if *word & bit != 0 {
+            return false;
+        }
+        *word |= bit;
+        true
+    }
+
+    /// 记录一个收到的数据包，不是本数据流回送包的数据包被忽略
+    pub fn observe(
+        &mut self,
+        data: &[u8],
+        receive_time: DateTime<Utc>,
+    ) {
+        let Some(header) = TestHeader::decode(data) else {
+            return;
+        };
+        let (Some(send_ns), Some(count), Some(receive_ns)) = (
+            header.send_time_ns,
+            header.reflected_count,
+            receive_time.timestamp_nanos_opt(),
+        ) else {
+            return;
+        };
+        if header.stream_id != self.stream_id {
+            return;
+        }
+        if !self.match_seq(header.seq) {
+            self.ignored += 1;
+            return;
+        }
+
+        // 收发使用同一时钟，往返时延不应为负
+        let rtt =
+            (receive_ns as u64).saturating_sub(send_ns);
+        if self.echoes == 0 {
+            self.min_ns = rtt;
+            self.max_ns = rtt;
+        } else {
+            self.min_ns = self.min_ns.min(rtt);
+            self.max_ns = self.max_ns.max(rtt);
+        }
+        self.echoes = self.echoes.saturating_add(1);
+        self.sum_ns += u128::from(rtt);
+        self.histogram.record(rtt);
+
+        self.highest_seq = Some(
+            self.highest_seq.map_or(header.seq, |seq| {
+                seq.max(header.seq)
+            }),
+        );
+        self.reflected = self.reflected.max(count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::probe::header::HeaderStamper;
+
+    fn echo(seq: u64, send_ns: u64, count: u64) -> Vec<u8> {
+        let mut out = Vec::new();
+        TestHeader {
+            stream_id: 1,
+            seq,
+            send_time_ns: Some(send_ns),
+            reflected_count: Some(count),
+        }
+        .encode(&mut out);
+        out
+    }
+
+    #[test]
+    fn test_rtt_and_directional_loss() {
+        let mut tracker = RttTracker::new(1);
+        let at = DateTime::from_timestamp_nanos;
+
+        // 发送 0..10：序号 3 去程丢失，序号 5 回程丢失，
+        // 序号 8、9 尚未得到回送
+        let mut count = 0;
+        for seq in 0..8u64 {
+            if seq == 3 {
+                continue;
+            }
+            count += 1;
+            if seq == 5 {
+                continue;
+            }
+            let send = seq * 1_000_000;
+            tracker.observe(
+                &echo(seq, send, count),
+                at((send + 2_000 * (seq + 1)) as i64),
+            );
+        }
+        // 非回送包被忽略
+        tracker.observe(
+            &HeaderStamper::new(1)
+                .with_timestamps()
+                .stamp(b""),
+            at(0),
+        );
+
+        // 重复的回送包只计一次
+        tracker.observe(&echo(7, 7_000_000, 7), at(0));
+
+        let stats = tracker.stats(10);
+        assert_eq!(stats.echoes, 6);
+        assert_eq!(stats.ignored, 1);
+        assert_eq!(stats.min_ns, 2_000);
+        assert_eq!(stats.max_ns, 16_000);
+        assert_eq!(stats.forward_lost, 1);
+        assert_eq!(stats.return_lost, 1);
+        assert_eq!(stats.unanswered, 2);
+    }
+
+    #[test]
+    fn test_reordered_and_out_of_range_echoes() {
+        let mut tracker = RttTracker::new(1);
+        let at = DateTime::from_timestamp_nanos;
+
+        // 高序号先到时，其前面尚未回送的包计为丢失而不是已应答
+        tracker.observe(&echo(4, 0, 5), at(1_000));
+        let stats = tracker.stats(5);
+        assert_eq!(stats.echoes, 1);
+        assert_eq!(stats.return_lost, 4);
+        assert_eq!(stats.unanswered, 0);
+
+        for seq in 0..4 {
+            tracker.observe(&echo(seq, 0, 5), at(1_000));
+        }
+        let stats = tracker.stats(5);
+        assert_eq!(stats.echoes, 5);
+        assert_eq!(
+            (stats.forward_lost, stats.return_lost),
+            (0, 0)
+        );
+
+        // 异常的最大序号不会溢出，早于匹配窗口的回送包被忽略
+        tracker.observe(&echo(u64::MAX, 0, 6), at(1_000));
+        tracker.observe(&echo(3, 0, 6), at(1_000));
+        let stats = tracker.stats(6);
+        assert_eq!(stats.ignored, 1);
+        assert_eq!(stats.unanswered, 0);
+    }
+}
@@ -0,0 +1,421 @@
+//! 序号跟踪模块
+//!
+//! 按数据流跟踪接收到的序号，实时统计丢失、乱序、重复与迟到的数据包。
+//!
+//! - 序号跳跃时，中间缺失的序号暂记为丢失；
+//! - 缺失的序号在窗口内到达时记为乱序，并从丢失数中扣除；
+//! - 窗口内已收到过的序号再次到达时记为重复；
+//! - 早于窗口的序号到达时记为迟到（仍计为丢失，无法再区分重复）；
+//! - 序号向前跳跃超过窗口时视为数据流重启，不计入丢失。
+//!
+//! 同时跟踪的数据流数量有上限，超出时淘汰最久未收到数据包的数据流。
+
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::net::SocketAddr;
+
+use crate::app::config::options::{
+    SequenceMode, SequenceOptions,
+};
+use crate::core::probe::header::TestHeader;
+
+/// 乱序判定窗口的最大包数
+pub const MAX_SEQUENCE_WINDOW: u64 = 1 << 20;
+
+/// 同时跟踪的最大数据流数量
+const MAX_STREAMS: usize = 256;
+
+/// 全部数据流窗口的总槽位上限（窗口较大时相应减少可跟踪的数据流）
+const MAX_TRACKED_SLOTS: u64 = 1 << 22;
+
+/// 数据流标识：发送方地址与数据流 ID
+type StreamKey = (Option<SocketAddr>, u32);
+
+/// 序号统计
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize,
+)]
+pub struct SequenceStats {
+    /// 数据流数量
+    pub streams: usize,
+    /// 带有效序号的数据包数量
+    pub sequenced: u64,
+    /// 无法解析序号的数据包数量
+    pub unsequenced: u64,
+    /// 丢失的数据包数量
+    pub lost: u64,
+    /// 乱序到达的数据包数量
+    pub reordered: u64,
+    /// 重复的数据包数量
+    pub duplicates: u64,
+    /// 早于窗口到达的数据包数量
+    pub late: u64,
+    /// 序号跳跃超过窗口、按重启处理的次数
+    pub resets: u64,
+    /// 因数据流数量超出上限被淘汰的数据流数量
+    pub evicted_streams: u64,
+}
+
+impl SequenceStats {
+    /// 丢包率（百分比）
+    pub fn loss_percent(&self) -> f64 {
+        // 重复与迟到的包不计入应收包数
+        let expected = self
+            .sequenced
+            .saturating_sub(self.duplicates + self.late)
+            + self.lost;
+        if expected == 0 {
+            0.0
+        } else {
+            self.lost as f64 * 100.0 / expected as f64
+        }
+    }
+}
+
+/// 单个数据流的跟踪状态
+#[derive(Debug)]
+struct StreamState {
+    /// 已收到的最大序号
+    highest: u64,
+    /// 窗口内尚未到达的序号
+    missing: BTreeSet<u64>,
+    /// 窗口内已收到的序号（按 `seq % window` 存放）
+    seen: Vec<Option<u64>>,
+    /// 最近一次收到数据包时的观测序号（用于淘汰空闲数据流）
+    last_active: u64,
+}
+
+impl StreamState {
+    /// 以 `seq` 为起点（重新）开始跟踪
+    fn restart(&mut self, seq: u64, window: u64) {
+        self.highest = seq;
+        self.missing.clear();
+        self.seen.fill(None);
+        self.seen[(seq % window) as usize] = Some(seq);
+    }
+}
+
+/// 序号跟踪器
+#[derive(Debug)]
+pub struct SequenceTracker {
+    options: SequenceOptions,
+    window: u64,
+    max_streams: usize,
+    streams: HashMap<StreamKey, StreamState>,
+    /// 已观测的数据包数量，作为数据流活跃度的时钟
+    observed: u64,
+    stats: SequenceStats,
+}
+
+impl SequenceTracker {
+    /// 根据选项创建跟踪器，未启用时返回 `None`
+    pub fn from_options(
+        options: &SequenceOptions,
+    ) -> Option<Self> {
+        if options.mode == SequenceMode::Off {
+            return None;
+        }
+        let window = options.window.max(1);
+        Some(Self {
+            options: options.clone(),
+            window,
+            max_streams: ((MAX_TRACKED_SLOTS / window)
+                as usize)
+                .clamp(1, MAX_STREAMS),
+            streams: HashMap::new(),
+            observed: 0,
+            stats: SequenceStats::default(),
+        })
+    }
+
+    /// 当前统计
+    pub fn stats(&self) -> SequenceStats {
+        self.stats
+    }
+
+    /// 记录一个数据包
+    pub fn observe(
+        &mut self,
+        source: Option<SocketAddr>,
+        data: &[u8],
+    ) {
+        let Some((stream_id, raw)) = self.extract(data)
+        else {
+            self.stats.unsequenced += 1;
+            return;
+        };
+        self.stats.sequenced += 1;
+        self.observed += 1;
+
+        let key = (source, stream_id);
+        if !self.streams.contains_key(&key) {
+            self.start_stream(key, raw);
+            return;
+        }
+
+        let window = self.window;
+        let bits = self.sequence_bits();
+        let stats = &mut self.stats;
+        let Some(state) = self.streams.get_mut(&key) else {
+            return;
+        };
+        state.last_active = self.observed;
+
+        let seq = unwrap_sequence(raw, state.highest, bits);
+        let slot = (seq % window) as usize;
+
+        if seq > state.highest
+            && seq - state.highest > window
+        {
+            // 跳跃超过窗口（发送端重启或异常序号），重新开始跟踪
+            stats.resets += 1;
+            state.restart(seq, window);
+        } else if seq > state.highest {
+            let gap = seq - state.highest - 1;
+            stats.lost = stats.lost.saturating_add(gap);
+            // 只在窗口内保留缺失序号，更早的直接确认为丢失
+            let first_missing = (state.highest + 1)
+                .max(seq.saturating_sub(window));
+            state.missing.extend(first_missing..seq);
+            state.highest = seq;
+            let floor = seq.saturating_sub(window);
+            state.missing = state.missing.split_off(&floor);
+            state.seen[slot] = Some(seq);
+        } else if state.highest - seq >= window {
+            stats.late += 1;
+        } else if state.missing.remove(&seq) {
+            stats.reordered += 1;
+            stats.lost -= 1;
+            state.seen[slot] = Some(seq);
+        } else if state.seen[slot] == Some(seq) {
+            stats.duplicates += 1;
+        } else {
+            // 发送端重启等原因导致序号回退，按迟到处理
+            stats.late += 1;
+        }
+    }
+
+    /// 开始跟踪新的数据流，数量达到上限时先淘汰最久未活跃的数据流
+    fn start_stream(&mut self, key: StreamKey, raw: u64) {
+        if self.streams.len() >= self.max_streams {
+            if let Some(idle) = self
+                .streams
+                .iter()
+                .min_by_key(|(_, state)| state.last_active)
+                .map(|(key, _)| *key)
+            {
+                self.streams.remove(&idle);
+                self.stats.evicted_streams += 1;
+            }
+        }
+
+        let mut state = StreamState {
+            highest: raw,
+            missing: BTreeSet::new(),
+            seen: vec![None; self.window as usize],
+            last_active: self.observed,
+        };
+        state.restart(raw, self.window);
+        self.streams.insert(key, state);
+        self.stats.streams = self.streams.len();
+    }
+
+    /// 从负载中提取数据流 ID 与原始序号
+    fn extract(&self, data: &[u8]) -> Option<(u32, u64)> {
+        match self.options.mode {
+            SequenceMode::Off => None,
+            SequenceMode::Header => {
+                TestHeader::decode(data).map(|header| {
+                    (header.stream_id, header.seq)
+                })
+            }
+            SequenceMode::Field => {
+                let width = self.options.width;
+                // 偏移量溢出时视为没有序号
+                let end = self
+                    .options
+                    .offset
+                    .checked_add(width)?;
+                let bytes =
+                    data.get(self.options.offset..end)?;
+                let mut value = 0u64;
+                for i in 0..width {
+                    let byte = if self.options.little_endian
+                    {
+                        bytes[width - 1 - i]
+                    } else {
+                        bytes[i]
+                    };
+                    value = (value << 8) | u64::from(byte);
+                }
+                Some((0, value))
+            }
+        }
+    }
+
+    /// 序号字段的位数（用于处理回绕）
+    fn sequence_bits(&self) -> u32 {
+        match self.options.mode {
+            SequenceMode::Field => {
+                (self.options.width as u32 * 8).min(64)
+            }
+            _ => 64,
+        }
+    }
+}
+
+/// 将可能回绕的序号展开为与 `reference` 最接近的 64 位序号
+fn unwrap_sequence(
+    raw: u64,
+    reference: u64,
+    bits: u32,
+) -> u64 {
+    if bits >= 64 {
+        return raw;
+    }
+    let modulus = 1u64 << bits;
+    let mask = modulus - 1;
+    let delta = raw.wrapping_sub(reference) & mask;
+    if delta < modulus / 2 {
+        reference.wrapping_add(delta)
+    } else {
+        reference.saturating_sub(modulus - delta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::probe::header::HeaderStamper;
+
+    fn tracker(window: u64) -> SequenceTracker {
+        SequenceTracker::from_options(&SequenceOptions {
+            mode: SequenceMode::Field,
+            offset: 0,
+            width: 2,
+            window,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn feed(tracker: &mut SequenceTracker, seqs: &[u16]) {
+        for seq in seqs {
+            tracker.observe(None, &seq.to_be_bytes());
+        }
+    }
+
+    #[test]
+    fn test_loss_reorder_duplicate() {
+        let mut tracker = tracker(64);
+        feed(&mut tracker, &[0, 1, 3, 2, 2, 6, 7]);
+        let stats = tracker.stats();
+        assert_eq!(stats.sequenced, 7);
+        assert_eq!(stats.reordered, 1);
+        assert_eq!(stats.duplicates, 1);
+        assert_eq!(stats.lost, 2);
+        assert_eq!(stats.late, 0);
+    }
+
+    #[test]
+    fn test_late_packet_outside_window() {
+        let mut tracker = tracker(4);
+        feed(&mut tracker, &[0, 2, 3, 4, 5, 6, 1]);
+        let stats = tracker.stats();
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.late, 1);
+        assert_eq!(stats.reordered, 0);
+    }
+
+    #[test]
+    fn test_sequence_wraparound() {
+        let mut tracker = tracker(64);
+        feed(&mut tracker, &[65534, 65535, 0, 2]);
+        let stats = tracker.stats();
+        assert_eq!(stats.lost, 1);
+        assert_eq!(stats.late, 0);
+    }
+
+    #[test]
+    fn test_jump_beyond_window_resets_stream() {
+        let mut tracker = SequenceTracker::from_options(
+            &SequenceOptions {
+                mode: SequenceMode::Field,
+                offset: 0,
+                width: 8,
+                window: 64,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        for seq in [0u64, 1, u64::MAX - 1, u64::MAX, 2] {
+            tracker.observe(None, &seq.to_be_bytes());
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.resets, 1);
+        assert_eq!(stats.lost, 0);
+        // 重启后更早的序号按迟到处理
+        assert_eq!(stats.late, 1);
+    }
+
+    #[test]
+    fn test_stream_count_is_capped() {
+        let mut tracker = SequenceTracker::from_options(
+            &SequenceOptions {
+                mode: SequenceMode::Header,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let streams = MAX_STREAMS as u32 + 10;
+        for stream_id in 0..streams {
+            tracker.observe(
+                None,
+                &HeaderStamper::new(stream_id).stamp(b"x"),
+            );
+        }
+        let stats = tracker.stats();
+        assert_eq!(stats.streams, MAX_STREAMS);
+        assert_eq!(stats.evicted_streams, 10);
+        assert_eq!(stats.sequenced, u64::from(streams));
+    }
+
+    #[test]
+    fn test_field_offset_overflow_has_no_sequence() {
+        let mut tracker = SequenceTracker::from_options(
+            &SequenceOptions {
+                mode: SequenceMode::Field,
+                offset: usize::MAX,
+                width: 2,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        tracker.observe(None, &[0, 1]);
+        assert_eq!(tracker.stats().sequenced, 0);
+    }
+
+    #[test]
+    fn test_header_streams_tracked_separately() {
+        let mut tracker = SequenceTracker::from_options(
+            &SequenceOptions {
+                mode: SequenceMode::Header,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let mut a = HeaderStamper::new(1);
+        let mut b = HeaderStamper::new(2);
+        for _ in 0..3 {
+            tracker.observe(None, &a.stamp(b"x"));
+        }
+        tracker.observe(None, &b.stamp(b"y"));
+        tracker.observe(None, b"no header");
+
+        let stats = tracker.stats();
+        assert_eq!(stats.streams, 2);
+        assert_eq!(stats.sequenced, 4);
+        assert_eq!(stats.unsequenced, 1);
+        assert_eq!(stats.lost, 0);
+    }
+}
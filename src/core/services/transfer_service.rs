@@ -6,23 +6,54 @@ use std::sync::{Arc, Mutex};
 use tracing;
 
 use crate::app::config::manager::ConfigManager;
+use crate::app::config::validator::ConfigValidator;
 use crate::app::error::types::{AppError, Result};
 use crate::core::network::receiver::run_receiver_with_gui_stats;
 use crate::core::network::sender::{
     run_sender_with_gui_stats, TransferState,
 };
 use crate::core::stats::collector::TransferStats;
+use crate::core::stats::metrics::{
+    start_metrics_endpoint, Direction, MetricsRegistry,
+};
+use crate::core::stats::report::dataset_stem;
 use crate::ui::config::{ReceiverConfig, SenderConfig};
 
 /// 传输服务
 pub struct TransferService {
     pub config_manager: ConfigManager,
+    /// 指标登记表（未启用指标导出时不对外提供）
+    metrics: Arc<MetricsRegistry>,
 }
 
 impl TransferService {
     /// 创建新的传输服务实例
     pub fn new(config_manager: ConfigManager) -> Self {
-        Self { config_manager }
+        let metrics = Arc::new(MetricsRegistry::default());
+
+        // 启用指标导出时在后台提供 /metrics
+        let options = &config_manager.config().metrics;
+        if options.enabled {
+            if let Err(e) =
+                ConfigValidator::validate_metrics(options)
+                    .and_then(|_| {
+                        start_metrics_endpoint(
+                            &options.bind,
+                            Arc::clone(&metrics),
+                        )
+                    })
+            {
+                tracing::error!(
+                    "Metrics endpoint failed: {}",
+                    e
+                );
+            }
+        }
+
+        Self {
+            config_manager,
+            metrics,
+        }
     }
 
     /// 启动发送器
@@ -39,14 +70,7 @@ impl TransferService {
         }
 
         // 根据数据格式选择对应的路径
-        let dataset_path = match config.data_format {
-            crate::app::config::types::DataFormat::Pcap => {
-                std::path::PathBuf::from(&config.pcap_path)
-            }
-            crate::app::config::types::DataFormat::Csv => {
-                std::path::PathBuf::from(&config.csv_file)
-            }
-        };
+        let dataset_path = config.source_path();
         let address = config.address.clone();
         let port = config.port;
         let network_type = config.network_type;
@@ -54,6 +78,7 @@ impl TransferService {
         let data_format = config.data_format;
         let csv_packet_interval =
             config.csv_packet_interval;
+        let options = config.options.clone();
 
         // 重置统计信息
         if let Ok(mut stats_guard) = stats.lock() {
@@ -75,6 +100,12 @@ impl TransferService {
             Arc::clone(&transfer_state_ref);
         let transfer_state_for_error =
             Arc::clone(&transfer_state_ref);
+        self.metrics.register(
+            dataset_stem(&dataset_path),
+            Direction::Send,
+            Arc::clone(&stats),
+            Arc::clone(&transfer_state_ref),
+        );
 
         // 在后台运行发送任务
         runtime_handle.spawn(async move {
@@ -86,6 +117,7 @@ impl TransferService {
                 interface,
                 data_format,
                 csv_packet_interval,
+                options,
                 stats,
                 transfer_state_clone,
             )
@@ -133,6 +165,7 @@ impl TransferService {
         let port = config.port;
         let network_type = config.network_type;
         let interface = config.interface.clone();
+        let options = config.options.clone();
 
         // 重置统计信息
         if let Ok(mut stats_guard) = stats.lock() {
@@ -146,6 +179,12 @@ impl TransferService {
             Arc::clone(&shared_state);
         let transfer_state_for_error =
             Arc::clone(&shared_state);
+        self.metrics.register(
+            dataset_name.clone(),
+            Direction::Receive,
+            Arc::clone(&stats),
+            Arc::clone(&shared_state),
+        );
 
         // 在后台运行接收任务
         runtime_handle.spawn(async move {
@@ -156,6 +195,7 @@ impl TransferService {
                 port,
                 network_type,
                 interface,
+                options,
                 stats,
                 transfer_state_clone,
             )
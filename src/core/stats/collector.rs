@@ -1,9 +1,36 @@
 use chrono::{DateTime, Utc};
 use std::time::{Duration, Instant};
 
+use crate::core::pipeline::impairment::ImpairmentStats;
+use crate::core::probe::latency::LatencyStats;
+use crate::core::probe::rtt::RttStats;
+use crate::core::probe::sequence::SequenceStats;
+use crate::core::stats::window::{
+    PacketSizes, Rate, RateWindow,
+};
+
 // Removed display module dependency
 // Removed indicatif and format_bytes dependencies
 
+/// 单个接收通道（套接字）的统计信息
+#[derive(Debug, Default, Clone)]
+pub struct ChannelStats {
+    /// 通道名称
+    pub label: String,
+    /// 接收的数据包数量
+    pub packets: usize,
+    /// 接收的字节数
+    pub bytes: u64,
+    /// 接收错误数量
+    pub errors: usize,
+    /// 因队列溢出丢弃的数据包数量
+    pub drops: usize,
+    /// 内核因套接字缓冲区溢出丢弃的数据包数量
+    pub kernel_drops: u64,
+    /// 未通过接收过滤规则的数据包数量
+    pub filtered: usize,
+}
+
 /// 传输统计信息
 #[derive(Debug, Default, Clone)]
 pub struct TransferStats {
@@ -14,6 +41,44 @@ pub struct TransferStats {
     // 基于数据包时间戳的统计
     first_packet_timestamp: Option<DateTime<Utc>>,
     last_packet_timestamp: Option<DateTime<Utc>>,
+    // 滑动窗口速率与包长统计
+    rate_window: RateWindow,
+    packet_sizes: PacketSizes,
+    // 接收队列统计
+    queue_capacity: usize,
+    queue_depth: usize,
+    queue_peak_depth: usize,
+    queue_drops: usize,
+    // 内核套接字缓冲区溢出丢包（SO_RXQ_OVFL）
+    kernel_drops: u64,
+    // 接收过滤统计
+    filter_active: bool,
+    filtered: usize,
+    // 超出停止条件配额而未写入的数据包
+    over_budget: usize,
+    // 发送预读缓冲区统计
+    prefetch_capacity: usize,
+    prefetch_depth: usize,
+    underruns: usize,
+    underrun_time: Duration,
+    // 各接收通道的统计（按注册顺序排列）
+    channels: Vec<ChannelStats>,
+    // 传输结束原因
+    stop_reason: Option<String>,
+    // 当前写入的数据集名称及序号
+    dataset: Option<(String, u32)>,
+    // 基于序号的丢包与乱序统计（未启用时为空）
+    sequence: Option<SequenceStats>,
+    // 单向时延与抖动统计（未启用时为空）
+    latency: Option<LatencyStats>,
+    // 反射器已回送的数据包数量（未启用反射时为空）
+    reflected: Option<u64>,
+    // 往返时延与分方向丢包统计（未启用时为空）
+    rtt: Option<RttStats>,
+    // 发送端网络损伤统计（未启用时为空）
+    impairment: Option<ImpairmentStats>,
+    // 模糊测试已变异的数据包数量（未启用时为空）
+    fuzzed: Option<u64>,
 }
 
 impl TransferStats {
@@ -38,11 +103,186 @@ impl TransferStats {
         self.last_packet_timestamp = Some(timestamp);
     }
 
+    /// 批量更新统计信息（一次加锁记录多个数据包）
+    pub fn update_batch(
+        &mut self,
+        packets: usize,
+        bytes: u64,
+        first_timestamp: DateTime<Utc>,
+        last_timestamp: DateTime<Utc>,
+    ) {
+        self.packets_processed += packets;
+        self.bytes_processed += bytes;
+
+        // 更新时间戳范围
+        if self.first_packet_timestamp.is_none() {
+            self.first_packet_timestamp =
+                Some(first_timestamp);
+        }
+        self.last_packet_timestamp = Some(last_timestamp);
+    }
+
+    /// 记录一批数据包的包长，并计入滑动窗口速率
+    ///
+    /// 调用方在加锁前汇总整批包长，加锁后只做常数时间的合并。
+    pub fn record_packet_sizes(
+        &mut self,
+        sizes: &PacketSizes,
+    ) {
+        self.packet_sizes.merge(sizes);
+        self.rate_window.record(
+            sizes.count,
+            sizes.total,
+            Instant::now(),
+        );
+    }
+
     /// 增加错误计数
     pub fn add_error(&mut self) {
         self.errors += 1;
     }
 
+    /// 批量增加错误计数
+    pub fn add_errors(&mut self, count: usize) {
+        self.errors += count;
+    }
+
+    /// 设置接收队列容量
+    pub fn set_queue_capacity(&mut self, capacity: usize) {
+        self.queue_capacity = capacity;
+    }
+
+    /// 更新接收队列当前深度（同时记录峰值）
+    pub fn update_queue_depth(&mut self, depth: usize) {
+        self.queue_depth = depth;
+        self.queue_peak_depth =
+            self.queue_peak_depth.max(depth);
+    }
+
+    /// 增加队列溢出丢包计数
+    pub fn add_queue_drops(&mut self, count: usize) {
+        self.queue_drops += count;
+    }
+
+    /// 增加内核溢出丢包计数
+    pub fn add_kernel_drops(&mut self, count: u64) {
+        self.kernel_drops += count;
+    }
+
+    /// 设置是否启用了接收过滤
+    pub fn set_filter_active(&mut self, active: bool) {
+        self.filter_active = active;
+    }
+
+    /// 增加被过滤的数据包计数
+    pub fn add_filtered(&mut self, count: usize) {
+        self.filtered += count;
+    }
+
+    /// 增加超出停止条件配额而未写入的数据包计数
+    pub fn add_over_budget(&mut self, count: usize) {
+        self.over_budget += count;
+    }
+
+    /// 设置发送预读缓冲区容量
+    pub fn set_prefetch_capacity(
+        &mut self,
+        capacity: usize,
+    ) {
+        self.prefetch_capacity = capacity;
+    }
+
+    /// 更新发送预读缓冲区当前深度
+    pub fn update_prefetch_depth(&mut self, depth: usize) {
+        self.prefetch_depth = depth;
+    }
+
+    /// 记录一次预读缓冲区欠载及其等待时长
+    pub fn add_underrun(&mut self, stalled: Duration) {
+        self.underruns += 1;
+        self.underrun_time += stalled;
+    }
+
+    /// 注册接收通道，确定通道的显示顺序
+    pub fn register_channel(&mut self, label: &str) {
+        self.channel_mut(label);
+    }
+
+    /// 获取（必要时创建）指定名称的接收通道统计
+    pub fn channel_mut(
+        &mut self,
+        label: &str,
+    ) -> &mut ChannelStats {
+        let index = match self
+            .channels
+            .iter()
+            .position(|channel| channel.label == label)
+        {
+            Some(index) => index,
+            None => {
+                self.channels.push(ChannelStats {
+                    label: label.to_string(),
+                    ..Default::default()
+                });
+                self.channels.len() - 1
+            }
+        };
+        &mut self.channels[index]
+    }
+
+    /// 记录当前写入的数据集
+    pub fn set_dataset(&mut self, name: &str, seq: u32) {
+        self.dataset = Some((name.to_string(), seq));
+    }
+
+    /// 更新序号统计
+    pub fn set_sequence(
+        &mut self,
+        sequence: SequenceStats,
+    ) {
+        self.sequence = Some(sequence);
+    }
+
+    /// 更新时延统计
+    pub fn set_latency(&mut self, latency: LatencyStats) {
+        self.latency = Some(latency);
+    }
+
+    /// 启用反射统计
+    pub fn set_reflector_active(&mut self) {
+        self.reflected.get_or_insert(0);
+    }
+
+    /// 增加已回送的数据包计数
+    pub fn add_reflected(&mut self, count: u64) {
+        if let Some(reflected) = &mut self.reflected {
+            *reflected += count;
+        }
+    }
+
+    /// 更新往返时延统计
+    pub fn set_rtt(&mut self, rtt: RttStats) {
+        self.rtt = Some(rtt);
+    }
+
+    /// 更新网络损伤统计
+    pub fn set_impairment(
+        &mut self,
+        impairment: ImpairmentStats,
+    ) {
+        self.impairment = Some(impairment);
+    }
+
+    /// 更新模糊测试已变异的数据包数量
+    pub fn set_fuzzed(&mut self, fuzzed: u64) {
+        self.fuzzed = Some(fuzzed);
+    }
+
+    /// 记录传输结束原因
+    pub fn set_stop_reason(&mut self, reason: String) {
+        self.stop_reason = Some(reason);
+    }
+
     /// 标记传输完成
     pub fn finish(&mut self) {
         if self.end_time.is_none() {
@@ -84,7 +324,20 @@ impl TransferStats {
         }
     }
 
-    // Removed unused get_average_packet_size method
+    /// 获取最近 `span` 内的速率（GUI 用，`span` 不超过 10 秒）
+    pub fn get_window_rate(&self, span: Duration) -> Rate {
+        self.rate_window.rate(span, Instant::now())
+    }
+
+    /// 获取 1 秒窗口的峰值速率（GUI 用）
+    pub fn get_peak_rate(&self) -> Rate {
+        self.rate_window.peak(Instant::now())
+    }
+
+    /// 获取包长统计（GUI 用）
+    pub fn get_packet_sizes(&self) -> &PacketSizes {
+        &self.packet_sizes
+    }
 
     // Removed update_progress_sender method (display dependency removed)
 
@@ -110,4 +363,216 @@ impl TransferStats {
     pub fn get_errors(&self) -> usize {
         self.errors
     }
+
+    /// 获取接收队列容量（GUI 用，0 表示未使用队列）
+    pub fn get_queue_capacity(&self) -> usize {
+        self.queue_capacity
+    }
+
+    /// 获取接收队列当前深度（GUI 用）
+    pub fn get_queue_depth(&self) -> usize {
+        self.queue_depth
+    }
+
+    /// 获取接收队列峰值深度（GUI 用）
+    pub fn get_queue_peak_depth(&self) -> usize {
+        self.queue_peak_depth
+    }
+
+    /// 获取队列溢出丢包数量（GUI 用）
+    pub fn get_queue_drops(&self) -> usize {
+        self.queue_drops
+    }
+
+    /// 获取内核溢出丢包数量（GUI 用）
+    pub fn get_kernel_drops(&self) -> u64 {
+        self.kernel_drops
+    }
+
+    /// 是否启用了接收过滤（GUI 用）
+    pub fn is_filter_active(&self) -> bool {
+        self.filter_active
+    }
+
+    /// 获取被过滤的数据包数量（GUI 用）
+    pub fn get_filtered(&self) -> usize {
+        self.filtered
+    }
+
+    /// 获取超出停止条件配额而未写入的数据包数量（GUI 用）
+    pub fn get_over_budget(&self) -> usize {
+        self.over_budget
+    }
+
+    /// 获取发送预读缓冲区容量（GUI 用，0 表示未启用预读）
+    pub fn get_prefetch_capacity(&self) -> usize {
+        self.prefetch_capacity
+    }
+
+    /// 获取发送预读缓冲区当前深度（GUI 用）
+    pub fn get_prefetch_depth(&self) -> usize {
+        self.prefetch_depth
+    }
+
+    /// 获取预读缓冲区欠载次数（GUI 用）
+    pub fn get_underruns(&self) -> usize {
+        self.underruns
+    }
+
+    /// 获取预读缓冲区欠载累计等待时长（GUI 用）
+    pub fn get_underrun_time(&self) -> Duration {
+        self.underrun_time
+    }
+
+    /// 获取各接收通道的统计信息（GUI 用）
+    pub fn get_channels(&self) -> &[ChannelStats] {
+        &self.channels
+    }
+
+    /// 获取传输结束原因（GUI 用）
+    pub fn get_stop_reason(&self) -> Option<&str> {
+        self.stop_reason.as_deref()
+    }
+
+    /// 获取当前写入的数据集名称及序号（GUI 用）
+    pub fn get_dataset(&self) -> Option<(&str, u32)> {
+        self.dataset
+            .as_ref()
+            .map(|(name, seq)| (name.as_str(), *seq))
+    }
+
+    /// 获取序号统计（GUI 用）
+    pub fn get_sequence(&self) -> Option<&SequenceStats> {
+        self.sequence.as_ref()
+    }
+
+    /// 获取时延统计（GUI 用）
+    pub fn get_latency(&self) -> Option<&LatencyStats> {
+        self.latency.as_ref()
+    }
+
+    /// 获取已回送的数据包数量（GUI 用，未启用反射时为空）
+    pub fn get_reflected(&self) -> Option<u64> {
+        self.reflected
+    }
+
+    /// 获取往返时延统计（GUI 用）
+    pub fn get_rtt(&self) -> Option<&RttStats> {
+        self.rtt.as_ref()
+    }
+
+    /// 获取模糊测试已变异的数据包数量（GUI 用）
+    pub fn get_fuzzed(&self) -> Option<u64> {
+        self.fuzzed
+    }
+
+    /// 获取网络损伤统计（GUI 用）
+    pub fn get_impairment(
+        &self,
+    ) -> Option<&ImpairmentStats> {
+        self.impairment.as_ref()
+    }
+
+    /// 生成传输结束时的统计摘要
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} packets, {} bytes, {} errors",
+            self.packets_processed,
+            self.bytes_processed,
+            self.errors
+        );
+        if self.packet_sizes.count > 0 {
+            let peak = self.get_peak_rate();
+            summary.push_str(&format!(
+                ", peak {:.3} Mbps / {:.0} pps, size min/avg/max {}/{:.1}/{} bytes",
+                peak.bps / 1e6,
+                peak.pps,
+                self.packet_sizes.min,
+                self.packet_sizes.average(),
+                self.packet_sizes.max
+            ));
+        }
+        if self.over_budget > 0 {
+            summary.push_str(&format!(
+                ", {} over budget",
+                self.over_budget
+            ));
+        }
+        if let Some(sequence) = &self.sequence {
+            summary.push_str(&format!(
+                ", {} lost ({:.3}%), {} reordered, {} duplicates, {} late",
+                sequence.lost,
+                sequence.loss_percent(),
+                sequence.reordered,
+                sequence.duplicates,
+                sequence.late
+            ));
+            if sequence.resets > 0 {
+                summary.push_str(&format!(
+                    ", {} sequence resets",
+                    sequence.resets
+                ));
+            }
+            if sequence.evicted_streams > 0 {
+                summary.push_str(&format!(
+                    ", {} streams evicted",
+                    sequence.evicted_streams
+                ));
+            }
+        }
+        if let Some(latency) =
+            self.latency.filter(|l| l.samples > 0)
+        {
+            summary.push_str(&format!(
+                ", latency min/avg/max/p99 {:.3}/{:.3}/{:.3}/{:.3} ms, jitter {:.3} ms",
+                latency.min_ns as f64 / 1e6,
+                latency.avg_ns as f64 / 1e6,
+                latency.max_ns as f64 / 1e6,
+                latency.p99_ns as f64 / 1e6,
+                latency.jitter_ns / 1e6
+            ));
+        }
+        if let Some(reflected) = self.reflected {
+            summary.push_str(&format!(
+                ", {reflected} reflected"
+            ));
+        }
+        if let Some(rtt) = &self.rtt {
+            summary.push_str(&format!(
+                ", {} echoes, rtt min/avg/max/p99 {:.3}/{:.3}/{:.3}/{:.3} ms, lost {} forward / {} return / {} unanswered",
+                rtt.echoes,
+                rtt.min_ns as f64 / 1e6,
+                rtt.avg_ns as f64 / 1e6,
+                rtt.max_ns as f64 / 1e6,
+                rtt.p99_ns as f64 / 1e6,
+                rtt.forward_lost,
+                rtt.return_lost,
+                rtt.unanswered
+            ));
+            if rtt.ignored > 0 {
+                summary.push_str(&format!(
+                    ", {} echoes ignored",
+                    rtt.ignored
+                ));
+            }
+        }
+        if let Some(impairment) = &self.impairment {
+            summary.push_str(&format!(
+                ", impaired: {} dropped, {} burst dropped, {} corrupted, {} duplicated, {} reordered, {} delayed",
+                impairment.dropped,
+                impairment.burst_dropped,
+                impairment.corrupted,
+                impairment.duplicated,
+                impairment.reordered,
+                impairment.delayed
+            ));
+        }
+        if let Some(fuzzed) = self.fuzzed {
+            summary.push_str(&format!(", {fuzzed} fuzzed"));
+        }
+        if let Some(reason) = &self.stop_reason {
+            summary.push_str(&format!(", {reason}"));
+        }
+        summary
+    }
 }
@@ -0,0 +1,273 @@
+//! 统计历史模块
+//!
+//! 按固定间隔对传输统计采样，保留最近一段时间的速率、包率、错误数
+//! 与丢包率序列，供界面绘制滚动曲线或导出为 CSV/JSON 报告。
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::time::{Duration, Instant};
+
+use crate::core::stats::collector::TransferStats;
+
+/// 界面曲线的采样间隔
+pub const SAMPLE_INTERVAL: Duration =
+    Duration::from_secs(1);
+
+/// 单个采样点
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize,
+)]
+pub struct StatsSample {
+    /// 采样时刻（UTC）
+    pub timestamp: DateTime<Utc>,
+    /// 自首次采样起经过的秒数
+    pub elapsed_secs: f64,
+    /// 累计包数
+    pub packets: u64,
+    /// 累计字节数
+    pub bytes: u64,
+    /// 本采样间隔内的平均速率（Mbps）
+    pub mbps: f64,
+    /// 本采样间隔内的平均包率（包每秒）
+    pub pps: f64,
+    /// 本采样间隔内新增的错误数
+    pub errors: u64,
+    /// 丢包率（百分比，来自序号检测或往返测量）
+    pub loss_percent: f64,
+}
+
+impl StatsSample {
+    /// CSV 表头
+    pub const CSV_HEADER: &'static str = "timestamp,elapsed_secs,packets,bytes,mbps,pps,errors,loss_percent";
+
+    /// 生成一行 CSV
+    pub fn csv_row(&self) -> String {
+        format!(
+            "{},{:.3},{},{},{:.6},{:.3},{},{:.6}",
+            self.timestamp.to_rfc3339_opts(
+                SecondsFormat::Millis,
+                true
+            ),
+            self.elapsed_secs,
+            self.packets,
+            self.bytes,
+            self.mbps,
+            self.pps,
+            self.errors,
+            self.loss_percent
+        )
+    }
+}
+
+/// 统计历史
+#[derive(Debug, Clone)]
+pub struct StatsHistory {
+    interval: Duration,
+    /// 保留时长（`None` 表示保留全部采样点）
+    retention: Option<Duration>,
+    start: Option<Instant>,
+    last: Option<(Instant, StatsSample)>,
+    last_errors: u64,
+    samples: VecDeque<StatsSample>,
+}
+
+impl StatsHistory {
+    /// 创建统计历史
+    pub fn new(
+        interval: Duration,
+        retention: Option<Duration>,
+    ) -> Self {
+        Self {
+            interval,
+            retention,
+            start: None,
+            last: None,
+            last_errors: 0,
+            samples: VecDeque::new(),
+        }
+    }
+
+    /// 清空历史（开始新的传输时调用）
+    pub fn clear(&mut self) {
+        *self = Self::new(self.interval, self.retention);
+    }
+
+    /// 距上次采样满一个间隔时记录新的采样点，返回是否已采样
+    pub fn sample(
+        &mut self,
+        stats: &TransferStats,
+        now: Instant,
+    ) -> bool {
+        if self.last.is_some_and(|(last, _)| {
+            now.saturating_duration_since(last)
+                < self.interval
+        }) {
+            return false;
+        }
+        self.record(stats, now);
+        true
+    }
+
+    /// 立即记录一个采样点，速率按与上一个采样点的差值计算
+    pub fn record(
+        &mut self,
+        stats: &TransferStats,
+        now: Instant,
+    ) {
+        let start = *self.start.get_or_insert(now);
+        let packets = stats.get_packets_processed() as u64;
+        let bytes = stats.get_bytes_processed();
+        let errors = stats.get_errors() as u64;
+
+        let (mbps, pps) = match self.last {
+            Some((last, previous)) => {
+                let secs = now
+                    .saturating_duration_since(last)
+                    .as_secs_f64();
+                if secs > 0.0 {
+                    (
+                        bytes.saturating_sub(previous.bytes)
+                            as f64
+                            * 8.0
+                            / 1e6
+                            / secs,
+                        packets.saturating_sub(
+                            previous.packets,
+                        ) as f64
+                            / secs,
+                    )
+                } else {
+                    (previous.mbps, previous.pps)
+                }
+            }
+            None => (0.0, 0.0),
+        };
+
+        let sample = StatsSample {
+            timestamp: Utc::now(),
+            elapsed_secs: now
+                .saturating_duration_since(start)
+                .as_secs_f64(),
+            packets,
+            bytes,
+            mbps,
+            pps,
+            errors: errors.saturating_sub(self.last_errors),
+            loss_percent: loss_percent(stats),
+        };
+        self.samples.push_back(sample);
+        self.last = Some((now, sample));
+        self.last_errors = errors;
+        self.trim();
+    }
+
+    /// 全部采样点（按时间顺序）
+    pub fn samples(&self) -> &VecDeque<StatsSample> {
+        &self.samples
+    }
+
+    /// 导出为 CSV 文本
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(StatsSample::CSV_HEADER);
+        csv.push('\n');
+        for sample in &self.samples {
+            let _ = writeln!(csv, "{}", sample.csv_row());
+        }
+        csv
+    }
+
+    /// 丢弃早于保留时长的采样点
+    fn trim(&mut self) {
+        let (Some(retention), Some(latest)) = (
+            self.retention,
+            self.samples.back().map(|s| s.elapsed_secs),
+        ) else {
+            return;
+        };
+        let floor = latest - retention.as_secs_f64();
+        while self
+            .samples
+            .front()
+            .is_some_and(|s| s.elapsed_secs < floor)
+        {
+            self.samples.pop_front();
+        }
+    }
+}
+
+/// 当前丢包率：优先使用序号检测结果，其次使用往返测量结果
+pub fn loss_percent(stats: &TransferStats) -> f64 {
+    if let Some(sequence) = stats.get_sequence() {
+        return sequence.loss_percent();
+    }
+    match stats.get_rtt() {
+        Some(rtt) if rtt.sent > 0 => {
+            (rtt.forward_lost + rtt.return_lost) as f64
+                * 100.0
+                / rtt.sent as f64
+        }
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_interval_and_retention() {
+        let start = Instant::now();
+        let mut history = StatsHistory::new(
+            SAMPLE_INTERVAL,
+            Some(Duration::from_secs(5)),
+        );
+        let mut stats = TransferStats::new();
+
+        assert!(history.sample(&stats, start));
+        assert!(!history.sample(
+            &stats,
+            start + Duration::from_millis(500)
+        ));
+
+        // 每秒 100 个 125 字节的包（0.1 Mbps）与 2 个错误
+        for second in 1..=10u64 {
+            stats.update_batch(
+                100,
+                12_500,
+                Utc::now(),
+                Utc::now(),
+            );
+            stats.add_errors(2);
+            assert!(history.sample(
+                &stats,
+                start + Duration::from_secs(second)
+            ));
+        }
+
+        let samples = history.samples();
+        assert_eq!(samples.len(), 6);
+        assert_eq!(samples[0].elapsed_secs, 5.0);
+        assert_eq!(samples[0].packets, 500);
+        assert!(samples.iter().all(|s| s.errors == 2));
+        assert!(samples.iter().all(|s| s.pps == 100.0));
+        assert!(samples
+            .iter()
+            .all(|s| (s.mbps - 0.1).abs() < 1e-9));
+
+        let csv = history.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some(StatsSample::CSV_HEADER)
+        );
+        let row = lines.next().unwrap();
+        assert!(row.ends_with(
+            ",5.000,500,62500,0.100000,100.000,2,0.000000"
+        ));
+
+        history.clear();
+        assert!(history.samples().is_empty());
+    }
+}
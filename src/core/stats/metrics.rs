@@ -0,0 +1,474 @@
+//! 指标导出模块
+//!
+//! 启用后在本地 HTTP 端口上提供 `/metrics`，以 Prometheus 文本格式
+//! 导出发送与接收会话的包数、字节数、错误、速率、丢包率与传输状态。
+//! 指标直接读取会话的 [`TransferStats`]，以会话名称与方向作为标签。
+
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, info, warn};
+
+use crate::app::error::types::{DataTransferError, Result};
+use crate::core::network::sender::TransferState;
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::history::loss_percent;
+
+/// 请求头的最大长度
+const MAX_REQUEST_LEN: usize = 8192;
+
+/// 读取请求的超时时间
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Prometheus 文本格式的内容类型
+const CONTENT_TYPE: &str =
+    "text/plain; version=0.0.4; charset=utf-8";
+
+/// 传输状态标签的全部取值
+const STATES: [&str; 4] =
+    ["idle", "running", "completed", "error"];
+
+/// 传输方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Send,
+    Receive,
+}
+
+impl Direction {
+    fn label(self) -> &'static str {
+        match self {
+            Direction::Send => "send",
+            Direction::Receive => "receive",
+        }
+    }
+}
+
+/// 已登记的会话
+struct Session {
+    name: String,
+    direction: Direction,
+    stats: Arc<Mutex<TransferStats>>,
+    state: Arc<Mutex<TransferState>>,
+}
+
+/// 指标登记表，由传输服务与 HTTP 服务共享
+#[derive(Default)]
+pub struct MetricsRegistry {
+    /// 每个方向只保留最近一次启动的会话
+    sessions: Mutex<Vec<Session>>,
+}
+
+impl MetricsRegistry {
+    /// 登记会话，替换同一方向的旧会话
+    pub fn register(
+        &self,
+        name: String,
+        direction: Direction,
+        stats: Arc<Mutex<TransferStats>>,
+        state: Arc<Mutex<TransferState>>,
+    ) {
+        if let Ok(mut sessions) = self.sessions.lock() {
+            sessions.retain(|s| s.direction != direction);
+            sessions.push(Session {
+                name,
+                direction,
+                stats,
+                state,
+            });
+        }
+    }
+
+    /// 以 Prometheus 文本格式输出全部指标
+    pub fn render(&self) -> String {
+        let mut rows: Vec<(String, Row)> = Vec::new();
+        if let Ok(sessions) = self.sessions.lock() {
+            for session in sessions.iter() {
+                let labels = format!(
+                    "session=\"{}\",direction=\"{}\"",
+                    escape_label(&session.name),
+                    session.direction.label()
+                );
+                let state = session
+                    .state
+                    .lock()
+                    .map_or("error", |state| {
+                        state_label(&state)
+                    });
+                if let Ok(stats) = session.stats.lock() {
+                    rows.push((
+                        labels,
+                        Row::new(&stats, state),
+                    ));
+                }
+            }
+        }
+
+        let mut out = String::new();
+        for metric in METRICS {
+            let _ = writeln!(
+                out,
+                "# HELP {} {}",
+                metric.name, metric.help
+            );
+            let _ = writeln!(
+                out,
+                "# TYPE {} {}",
+                metric.name, metric.kind
+            );
+            for (labels, row) in &rows {
+                let _ = writeln!(
+                    out,
+                    "{}{{{}}} {}",
+                    metric.name,
+                    labels,
+                    (metric.value)(row)
+                );
+            }
+        }
+
+        let name = "pcap_transfer_state";
+        let _ = writeln!(
+            out,
+            "# HELP {name} Current transfer state (1 for the active state)"
+        );
+        let _ = writeln!(out, "# TYPE {name} gauge");
+        for (labels, row) in &rows {
+            for state in STATES {
+                let _ = writeln!(
+                    out,
+                    "{name}{{{labels},state=\"{state}\"}} {}",
+                    u8::from(row.state == state)
+                );
+            }
+        }
+        out
+    }
+}
+
+/// 单个会话的指标快照
+struct Row {
+    packets: u64,
+    bytes: u64,
+    errors: u64,
+    queue_drops: u64,
+    kernel_drops: u64,
+    bps: f64,
+    pps: f64,
+    loss_percent: f64,
+    state: &'static str,
+}
+
+impl Row {
+    fn new(
+        stats: &TransferStats,
+        state: &'static str,
+    ) -> Self {
+        let rate =
+            stats.get_window_rate(Duration::from_secs(1));
+        Self {
+            packets: stats.get_packets_processed() as u64,
+            bytes: stats.get_bytes_processed(),
+            errors: stats.get_errors() as u64,
+            queue_drops: stats.get_queue_drops() as u64,
+            kernel_drops: stats.get_kernel_drops(),
+            bps: rate.bps,
+            pps: rate.pps,
+            loss_percent: loss_percent(stats),
+            state,
+        }
+    }
+}
+
+/// 指标定义
+struct Metric {
+    name: &'static str,
+    kind: &'static str,
+    help: &'static str,
+    value: fn(&Row) -> String,
+}
+
+const METRICS: [Metric; 8] = [
+    Metric {
+        name: "pcap_transfer_packets_total",
+        kind: "counter",
+        help: "Packets sent or received",
+        value: |row| row.packets.to_string(),
+    },
+    Metric {
+        name: "pcap_transfer_bytes_total",
+        kind: "counter",
+        help: "Payload bytes sent or received",
+        value: |row| row.bytes.to_string(),
+    },
+    Metric {
+        name: "pcap_transfer_errors_total",
+        kind: "counter",
+        help: "Send or receive errors",
+        value: |row| row.errors.to_string(),
+    },
+    Metric {
+        name: "pcap_transfer_queue_drops_total",
+        kind: "counter",
+        help: "Packets dropped by the receive queue",
+        value: |row| row.queue_drops.to_string(),
+    },
+    Metric {
+        name: "pcap_transfer_kernel_drops_total",
+        kind: "counter",
+        help: "Packets dropped by the kernel socket buffer",
+        value: |row| row.kernel_drops.to_string(),
+    },
+    Metric {
+        name: "pcap_transfer_rate_bits_per_second",
+        kind: "gauge",
+        help: "Rate over the last second in bits per second",
+        value: |row| format!("{:.3}", row.bps),
+    },
+    Metric {
+        name: "pcap_transfer_rate_packets_per_second",
+        kind: "gauge",
+        help: "Rate over the last second in packets per second",
+        value: |row| format!("{:.3}", row.pps),
+    },
+    Metric {
+        name: "pcap_transfer_loss_percent",
+        kind: "gauge",
+        help: "Packet loss from sequence tracking or RTT probing",
+        value: |row| format!("{:.6}", row.loss_percent),
+    },
+];
+
+/// 传输状态标签
+fn state_label(state: &TransferState) -> &'static str {
+    match state {
+        TransferState::Idle => "idle",
+        TransferState::Running => "running",
+        TransferState::Completed => "completed",
+        TransferState::Error(_) => "error",
+    }
+}
+
+/// 转义标签值中的反斜杠、引号与换行
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 绑定 `bind` 地址并在后台提供 `/metrics`，直到所在运行时结束
+///
+/// 绑定失败时立即返回错误，成功时返回实际监听地址。
+/// 需要在 Tokio 运行时中调用。
+pub fn start_metrics_endpoint(
+    bind: &str,
+    registry: Arc<MetricsRegistry>,
+) -> Result<SocketAddr> {
+    let handle = tokio::runtime::Handle::try_current()
+        .map_err(|_| {
+            DataTransferError::config(
+                "Metrics endpoint requires a Tokio runtime",
+            )
+        })?;
+    let _guard = handle.enter();
+
+    let listener = std::net::TcpListener::bind(bind)
+        .map_err(|e| {
+            DataTransferError::network(format!(
+                "Failed to bind metrics endpoint {bind}: {e}"
+            ))
+        })?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let addr = listener.local_addr()?;
+    info!("Serving metrics on http://{}/metrics", addr);
+    handle.spawn(serve_metrics(listener, registry));
+    Ok(addr)
+}
+
+/// 逐个接受连接并提供 `/metrics`
+async fn serve_metrics(
+    listener: TcpListener,
+    registry: Arc<MetricsRegistry>,
+) {
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(e) => {
+                warn!("Metrics accept failed: {}", e);
+                continue;
+            }
+        };
+        let registry = Arc::clone(&registry);
+        tokio::spawn(async move {
+            if let Err(e) =
+                handle_connection(stream, &registry).await
+            {
+                debug!(
+                    "Metrics request from {} failed: {}",
+                    peer, e
+                );
+            }
+        });
+    }
+}
+
+/// 处理单个 HTTP 请求后关闭连接
+async fn handle_connection(
+    mut stream: TcpStream,
+    registry: &MetricsRegistry,
+) -> std::io::Result<()> {
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+        if request.len() >= MAX_REQUEST_LEN {
+            break;
+        }
+        let read = tokio::time::timeout(
+            REQUEST_TIMEOUT,
+            stream.read(&mut buf),
+        )
+        .await
+        .map_err(|_| {
+            std::io::Error::from(
+                std::io::ErrorKind::TimedOut,
+            )
+        })??;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..read]);
+    }
+
+    let request = String::from_utf8_lossy(&request);
+    let mut parts =
+        request.lines().next().unwrap_or("").split(' ');
+    let (method, path) = (parts.next(), parts.next());
+    let path = path.map(|p| p.split('?').next());
+    let (status, content_type, body) = match (method, path)
+    {
+        (Some("GET"), Some(Some("/metrics"))) => {
+            ("200 OK", CONTENT_TYPE, registry.render())
+        }
+        (Some("GET"), _) => (
+            "404 Not Found",
+            "text/plain",
+            "Not Found\n".to_string(),
+        ),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "Method Not Allowed\n".to_string(),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_render_labels_and_state() {
+        let registry = MetricsRegistry::default();
+        let stats =
+            Arc::new(Mutex::new(TransferStats::new()));
+        stats.lock().unwrap().update_batch(
+            3,
+            300,
+            Utc::now(),
+            Utc::now(),
+        );
+        let state =
+            Arc::new(Mutex::new(TransferState::Running));
+        registry.register(
+            "old".to_string(),
+            Direction::Receive,
+            Arc::new(Mutex::new(TransferStats::new())),
+            Arc::clone(&state),
+        );
+        registry.register(
+            "lab \"a\"".to_string(),
+            Direction::Receive,
+            Arc::clone(&stats),
+            Arc::clone(&state),
+        );
+
+        let text = registry.render();
+        let labels =
+            r#"session="lab \"a\"",direction="receive""#;
+        assert!(text.contains(&format!(
+            "pcap_transfer_packets_total{{{labels}}} 3\n"
+        )));
+        assert!(text.contains(&format!(
+            "pcap_transfer_bytes_total{{{labels}}} 300\n"
+        )));
+        assert!(text.contains(&format!(
+            "pcap_transfer_state{{{labels},state=\"running\"}} 1\n"
+        )));
+        assert!(!text.contains("session=\"old\""));
+
+        *state.lock().unwrap() =
+            TransferState::Error("failed".to_string());
+        assert!(registry.render().contains(&format!(
+            "pcap_transfer_state{{{labels},state=\"error\"}} 1\n"
+        )));
+    }
+
+    #[tokio::test]
+    async fn test_serve_metrics() {
+        let registry = Arc::new(MetricsRegistry::default());
+        let port = start_metrics_endpoint(
+            "127.0.0.1:0",
+            Arc::clone(&registry),
+        )
+        .unwrap()
+        .port();
+
+        // 端口已被占用时立即返回错误
+        assert!(start_metrics_endpoint(
+            &format!("127.0.0.1:{port}"),
+            Arc::clone(&registry),
+        )
+        .is_err());
+
+        let get = |path: &'static str| async move {
+            let mut stream =
+                TcpStream::connect(("127.0.0.1", port))
+                    .await
+                    .unwrap();
+            stream
+                .write_all(
+                    format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n")
+                        .as_bytes(),
+                )
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream
+                .read_to_string(&mut response)
+                .await
+                .unwrap();
+            response
+        };
+
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains(
+            "# TYPE pcap_transfer_packets_total counter"
+        ));
+        assert!(get("/")
+            .await
+            .starts_with("HTTP/1.1 404 Not Found"));
+    }
+}
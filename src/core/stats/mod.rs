@@ -1,3 +1,7 @@
 //! 统计信息模块
 
 pub mod collector;
+pub mod history;
+pub mod metrics;
+pub mod report;
+pub mod window;
@@ -0,0 +1,318 @@
+//! 统计报告模块
+//!
+//! 传输期间按配置的间隔对统计采样，结束时把时间序列与最终摘要
+//! （配置、起止时间、总量、错误与丢包）写入 JSON 与 CSV 文件：
+//!
+//! - `<名称>_<时间>_<角色>_report.json`：摘要与全部采样点；
+//! - `<名称>_<时间>_<角色>_series.csv`：时间序列；
+//! - `<名称>_<时间>_<角色>_summary.csv`：表头与一行摘要。
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::info;
+
+use crate::app::config::options::ReportOptions;
+use crate::app::error::types::{AppError, Result};
+use crate::core::pipeline::impairment::ImpairmentStats;
+use crate::core::probe::latency::LatencyStats;
+use crate::core::probe::rtt::RttStats;
+use crate::core::probe::sequence::SequenceStats;
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::history::{
+    loss_percent, StatsHistory, StatsSample,
+};
+use crate::core::stats::window::PacketSizes;
+
+/// 采样间隔的下限（毫秒）
+pub const MIN_REPORT_INTERVAL_MS: u64 = 100;
+
+/// 采样间隔的上限（毫秒）
+pub const MAX_REPORT_INTERVAL_MS: u64 = 3_600_000;
+
+/// 摘要 CSV 的表头
+const SUMMARY_CSV_HEADER: &str = "role,name,started_at,ended_at,duration_secs,packets,bytes,errors,average_mbps,peak_mbps,peak_pps,loss_percent,queue_drops,kernel_drops,filtered,stop_reason";
+
+/// 传输结束时的统计摘要
+#[derive(Debug, Serialize)]
+struct ReportSummary {
+    role: &'static str,
+    name: String,
+    started_at: DateTime<Utc>,
+    ended_at: DateTime<Utc>,
+    duration_secs: f64,
+    config: serde_json::Value,
+    packets: u64,
+    bytes: u64,
+    errors: u64,
+    average_mbps: f64,
+    peak_mbps: f64,
+    peak_pps: f64,
+    loss_percent: f64,
+    packet_sizes: PacketSizes,
+    queue_drops: u64,
+    kernel_drops: u64,
+    filtered: u64,
+    sequence: Option<SequenceStats>,
+    latency: Option<LatencyStats>,
+    rtt: Option<RttStats>,
+    reflected: Option<u64>,
+    impairment: Option<ImpairmentStats>,
+    fuzzed: Option<u64>,
+    stop_reason: Option<String>,
+}
+
+impl ReportSummary {
+    /// 生成一行 CSV
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{:.3},{},{},{},{:.6},{:.6},{:.3},{:.6},{},{},{},{}",
+            self.role,
+            csv_field(&self.name),
+            rfc3339(self.started_at),
+            rfc3339(self.ended_at),
+            self.duration_secs,
+            self.packets,
+            self.bytes,
+            self.errors,
+            self.average_mbps,
+            self.peak_mbps,
+            self.peak_pps,
+            self.loss_percent,
+            self.queue_drops,
+            self.kernel_drops,
+            self.filtered,
+            csv_field(
+                self.stop_reason.as_deref().unwrap_or("")
+            )
+        )
+    }
+}
+
+/// JSON 报告内容
+#[derive(Serialize)]
+struct Report<'a> {
+    summary: &'a ReportSummary,
+    samples: Vec<&'a StatsSample>,
+}
+
+/// 统计报告记录器
+///
+/// 后台任务按间隔采样，[`ReportRecorder::finish`] 停止采样并写入报告。
+pub struct ReportRecorder {
+    role: &'static str,
+    name: String,
+    directory: PathBuf,
+    config: serde_json::Value,
+    interval: Duration,
+    started_at: DateTime<Utc>,
+    started: Instant,
+    stats: Arc<Mutex<TransferStats>>,
+    stop: watch::Sender<bool>,
+    task: JoinHandle<StatsHistory>,
+}
+
+impl ReportRecorder {
+    /// 开始记录，未启用报告时返回 `None`
+    ///
+    /// 报告目录为空时写入 `default_dir`；`config` 原样写入摘要。
+    pub fn start(
+        options: &ReportOptions,
+        default_dir: &Path,
+        role: &'static str,
+        name: String,
+        config: serde_json::Value,
+        stats: Arc<Mutex<TransferStats>>,
+    ) -> Option<Self> {
+        if !options.enabled {
+            return None;
+        }
+        let directory = match options.directory.trim() {
+            "" => default_dir.to_path_buf(),
+            directory => PathBuf::from(directory),
+        };
+        let interval = Duration::from_millis(
+            options.interval_ms.clamp(
+                MIN_REPORT_INTERVAL_MS,
+                MAX_REPORT_INTERVAL_MS,
+            ),
+        );
+        let (stop, stop_rx) = watch::channel(false);
+        let task = tokio::spawn(run_sampler(
+            interval,
+            Arc::clone(&stats),
+            stop_rx,
+        ));
+        Some(Self {
+            role,
+            name,
+            directory,
+            config,
+            interval,
+            started_at: Utc::now(),
+            started: Instant::now(),
+            stats,
+            stop,
+            task,
+        })
+    }
+
+    /// 停止采样并写入报告，返回 JSON 报告路径
+    pub async fn finish(self) -> Result<PathBuf> {
+        let _ = self.stop.send(true);
+        let mut history =
+            self.task.await.unwrap_or_else(|_| {
+                StatsHistory::new(self.interval, None)
+            });
+        let ended_at = Utc::now();
+        let duration = self.started.elapsed();
+
+        let summary = {
+            let stats =
+                self.stats.lock().map_err(|_| {
+                    AppError::config(
+                        "Statistics lock poisoned",
+                    )
+                })?;
+            history.record(&stats, Instant::now());
+            let bytes = stats.get_bytes_processed();
+            let peak = stats.get_peak_rate();
+            let secs = duration.as_secs_f64();
+            ReportSummary {
+                role: self.role,
+                name: self.name.clone(),
+                started_at: self.started_at,
+                ended_at,
+                duration_secs: secs,
+                config: self.config.clone(),
+                packets: stats.get_packets_processed()
+                    as u64,
+                bytes,
+                errors: stats.get_errors() as u64,
+                average_mbps: if secs > 0.0 {
+                    bytes as f64 * 8.0 / 1e6 / secs
+                } else {
+                    0.0
+                },
+                peak_mbps: peak.bps / 1e6,
+                peak_pps: peak.pps,
+                loss_percent: loss_percent(&stats),
+                packet_sizes: *stats.get_packet_sizes(),
+                queue_drops: stats.get_queue_drops() as u64,
+                kernel_drops: stats.get_kernel_drops(),
+                filtered: stats.get_filtered() as u64,
+                sequence: stats.get_sequence().copied(),
+                latency: stats.get_latency().copied(),
+                rtt: stats.get_rtt().copied(),
+                reflected: stats.get_reflected(),
+                impairment: stats.get_impairment().copied(),
+                fuzzed: stats.get_fuzzed(),
+                stop_reason: stats
+                    .get_stop_reason()
+                    .map(str::to_string),
+            }
+        };
+
+        std::fs::create_dir_all(&self.directory)?;
+        let base = self.directory.join(format!(
+            "{}_{}_{}",
+            self.name,
+            self.started_at.format("%Y%m%d_%H%M%S"),
+            self.role
+        ));
+        let path = |suffix: &str| {
+            let mut path = base.clone().into_os_string();
+            path.push(suffix);
+            PathBuf::from(path)
+        };
+
+        let report = Report {
+            summary: &summary,
+            samples: history.samples().iter().collect(),
+        };
+        let json = serde_json::to_string_pretty(&report)
+            .map_err(|e| {
+                AppError::config(format!(
+                    "Failed to encode report: {e}"
+                ))
+            })?;
+        let json_path = path("_report.json");
+        std::fs::write(&json_path, json)?;
+        std::fs::write(
+            path("_series.csv"),
+            history.to_csv(),
+        )?;
+        std::fs::write(
+            path("_summary.csv"),
+            format!(
+                "{SUMMARY_CSV_HEADER}\n{}\n",
+                summary.csv_row()
+            ),
+        )?;
+
+        info!("Report written to {}", json_path.display());
+        Ok(json_path)
+    }
+}
+
+/// 发送数据集的名称（数据集目录名或 CSV 文件名，不含扩展名）
+pub fn dataset_stem(dataset_path: &Path) -> String {
+    dataset_path.file_stem().map_or_else(
+        || "dataset".to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    )
+}
+
+/// 按间隔采样，直到收到停止信号
+async fn run_sampler(
+    interval: Duration,
+    stats: Arc<Mutex<TransferStats>>,
+    mut stop: watch::Receiver<bool>,
+) -> StatsHistory {
+    let mut history = StatsHistory::new(interval, None);
+    let mut ticker = tokio::time::interval(interval);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                if let Ok(stats) = stats.lock() {
+                    history.record(&stats, Instant::now());
+                }
+            }
+            _ = stop.changed() => break,
+        }
+    }
+    history
+}
+
+/// 以毫秒精度格式化 UTC 时间
+fn rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// 含逗号、引号或换行的 CSV 字段加引号转义
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_field_escaping() {
+        assert_eq!(csv_field("capture"), "capture");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(
+            csv_field("say \"hi\""),
+            "\"say \"\"hi\"\"\""
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! 滑动窗口统计模块
+//!
+//! 按 100ms 分槽累计包数与字节数，计算最近 1 秒与 10 秒的速率以及
+//! 1 秒窗口的峰值速率；同时统计包长的最小、平均、最大值与分布。
+//! 记录操作只更新当前槽位，读取时才汇总窗口，统计锁的持有时间很短。
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+
+/// 单个槽位的时长
+const SLOT_DURATION: Duration = Duration::from_millis(100);
+
+/// 槽位数量（覆盖 10 秒）
+const SLOT_COUNT: usize = 100;
+
+/// 峰值速率的统计窗口（槽位数，即 1 秒）
+const PEAK_SLOTS: u64 = 10;
+
+/// 包长分布各区间的上界（字节，含上界），超出最后一个上界的归入末尾区间
+pub const SIZE_BUCKET_BOUNDS: [usize; 7] =
+    [64, 128, 256, 512, 1024, 1500, 9000];
+
+/// 速率
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Serialize,
+)]
+pub struct Rate {
+    /// 比特每秒
+    pub bps: f64,
+    /// 包每秒
+    pub pps: f64,
+}
+
+/// 单个槽位的累计值
+#[derive(Debug, Clone, Copy, Default)]
+struct Slot {
+    /// 槽位序号（自起始时刻按 100ms 计）
+    index: u64,
+    packets: u64,
+    bytes: u64,
+}
+
+/// 滑动窗口速率统计
+#[derive(Debug, Clone)]
+pub struct RateWindow {
+    start: Option<Instant>,
+    slots: Vec<Slot>,
+    /// 最近一次记录所在的槽位序号
+    current: u64,
+    peak: Rate,
+}
+
+impl Default for RateWindow {
+    fn default() -> Self {
+        Self {
+            start: None,
+            slots: vec![Slot::default(); SLOT_COUNT],
+            current: 0,
+            peak: Rate::default(),
+        }
+    }
+}
+
+impl RateWindow {
+    /// 记录一批数据包
+    pub fn record(
+        &mut self,
+        packets: u64,
+        bytes: u64,
+        now: Instant,
+    ) {
+        let start = *self.start.get_or_insert(now);
+        let index = slot_index(start, now);
+
+        // 进入新槽位时，以刚结束的 1 秒窗口更新峰值
+        if index > self.current {
+            let completed = self.sum(
+                self.current + 1
+                    - PEAK_SLOTS.min(self.current + 1),
+                self.current,
+            );
+            let span = SLOT_DURATION * PEAK_SLOTS as u32;
+            let rate = to_rate(completed, span);
+            self.peak.bps = self.peak.bps.max(rate.bps);
+            self.peak.pps = self.peak.pps.max(rate.pps);
+            self.current = index;
+        }
+
+        let slot =
+            &mut self.slots[index as usize % SLOT_COUNT];
+        if slot.index != index {
+            *slot = Slot {
+                index,
+                ..Default::default()
+            };
+        }
+        slot.packets += packets;
+        slot.bytes += bytes;
+    }
+
+    /// 截至 `now` 的最近 `span` 内的速率（`span` 不超过 10 秒）
+    pub fn rate(
+        &self,
+        span: Duration,
+        now: Instant,
+    ) -> Rate {
+        let Some(start) = self.start else {
+            return Rate::default();
+        };
+        let index = slot_index(start, now);
+        let slots = (span.as_nanos()
+            / SLOT_DURATION.as_nanos())
+        .clamp(1, SLOT_COUNT as u128)
+            as u64;
+        let first = (index + 1).saturating_sub(slots);
+
+        // 当前槽位只计入已经过的部分，运行时间不足窗口时按实际时长计算
+        let elapsed_in_slot = now
+            .saturating_duration_since(start)
+            - SLOT_DURATION * index as u32;
+        let span = SLOT_DURATION * (index - first) as u32
+            + elapsed_in_slot;
+        to_rate(self.sum(first, index), span)
+    }
+
+    /// 1 秒窗口的峰值速率（包含截至 `now` 的当前窗口）
+    pub fn peak(&self, now: Instant) -> Rate {
+        let current = self
+            .rate(SLOT_DURATION * PEAK_SLOTS as u32, now);
+        Rate {
+            bps: self.peak.bps.max(current.bps),
+            pps: self.peak.pps.max(current.pps),
+        }
+    }
+
+    /// 汇总 `first..=last` 槽位的包数与字节数
+    fn sum(&self, first: u64, last: u64) -> (u64, u64) {
+        self.slots
+            .iter()
+            .filter(|slot| {
+                (first..=last).contains(&slot.index)
+                    && slot.packets > 0
+            })
+            .fold((0, 0), |(packets, bytes), slot| {
+                (packets + slot.packets, bytes + slot.bytes)
+            })
+    }
+}
+
+/// 自起始时刻的槽位序号
+fn slot_index(start: Instant, now: Instant) -> u64 {
+    (now.saturating_duration_since(start).as_nanos()
+        / SLOT_DURATION.as_nanos()) as u64
+}
+
+/// 将包数与字节数换算为速率
+fn to_rate(
+    (packets, bytes): (u64, u64),
+    span: Duration,
+) -> Rate {
+    let secs = span.as_secs_f64();
+    if secs <= 0.0 {
+        return Rate::default();
+    }
+    Rate {
+        bps: bytes as f64 * 8.0 / secs,
+        pps: packets as f64 / secs,
+    }
+}
+
+/// 包长统计
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, Serialize,
+)]
+pub struct PacketSizes {
+    /// 数据包数量
+    pub count: u64,
+    /// 总字节数
+    pub total: u64,
+    /// 最小包长
+    pub min: usize,
+    /// 最大包长
+    pub max: usize,
+    /// 各包长区间的包数（见 [`SIZE_BUCKET_BOUNDS`]）
+    pub buckets: [u64; SIZE_BUCKET_BOUNDS.len() + 1],
+}
+
+impl PacketSizes {
+    /// 记录一个数据包
+    pub fn record(&mut self, len: usize) {
+        if self.count == 0 {
+            self.min = len;
+            self.max = len;
+        } else {
+            self.min = self.min.min(len);
+            self.max = self.max.max(len);
+        }
+        self.count += 1;
+        self.total += len as u64;
+        let bucket = SIZE_BUCKET_BOUNDS
+            .iter()
+            .position(|bound| len <= *bound)
+            .unwrap_or(SIZE_BUCKET_BOUNDS.len());
+        self.buckets[bucket] += 1;
+    }
+
+    /// 合并另一组统计
+    pub fn merge(&mut self, other: &PacketSizes) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.min = other.min;
+            self.max = other.max;
+        } else {
+            self.min = self.min.min(other.min);
+            self.max = self.max.max(other.max);
+        }
+        self.count += other.count;
+        self.total += other.total;
+        for (bucket, count) in
+            self.buckets.iter_mut().zip(other.buckets)
+        {
+            *bucket += count;
+        }
+    }
+
+    /// 平均包长
+    pub fn average(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.total as f64 / self.count as f64
+        }
+    }
+
+    /// 包长分布：区间名称与包数
+    pub fn histogram(
+        &self,
+    ) -> impl Iterator<Item = (String, u64)> + '_ {
+        self.buckets.iter().enumerate().map(|(i, count)| {
+            let label = match i {
+                0 => format!("≤{}", SIZE_BUCKET_BOUNDS[0]),
+                i if i == SIZE_BUCKET_BOUNDS.len() => {
+                    format!(
+                        ">{}",
+                        SIZE_BUCKET_BOUNDS[i - 1]
+                    )
+                }
+                i => format!(
+                    "{}-{}",
+                    SIZE_BUCKET_BOUNDS[i - 1] + 1,
+                    SIZE_BUCKET_BOUNDS[i]
+                ),
+            };
+            (label, *count)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(value: u64) -> Duration {
+        Duration::from_millis(value)
+    }
+
+    #[test]
+    fn test_window_rates_and_peak() {
+        let start = Instant::now();
+        let mut window = RateWindow::default();
+
+        // 前 1 秒每 100ms 发送 10 个 1000 字节的包（100 pps）
+        for i in 0..10 {
+            window.record(10, 10_000, start + ms(i * 100));
+        }
+        // 之后 9 秒每 100ms 只发送 1 个包（10 pps）
+        for i in 10..100 {
+            window.record(1, 1_000, start + ms(i * 100));
+        }
+
+        let now = start + ms(9_999);
+        let rate_1s =
+            window.rate(Duration::from_secs(1), now);
+        assert!((rate_1s.pps - 10.0).abs() < 0.1);
+        assert!((rate_1s.bps - 80_000.0).abs() < 100.0);
+
+        let rate_10s =
+            window.rate(Duration::from_secs(10), now);
+        assert!((rate_10s.pps - 19.0).abs() < 0.1);
+
+        let peak = window.peak(now);
+        assert!((peak.pps - 100.0).abs() < 0.1);
+        assert!((peak.bps - 800_000.0).abs() < 1.0);
+
+        // 停止发送后当前速率归零，峰值保留
+        let idle = start + ms(30_000);
+        assert_eq!(
+            window.rate(Duration::from_secs(1), idle).pps,
+            0.0
+        );
+        assert_eq!(window.peak(idle), peak);
+    }
+
+    #[test]
+    fn test_packet_sizes() {
+        let mut batch = PacketSizes::default();
+        for len in [60, 64, 65, 1500, 9001] {
+            batch.record(len);
+        }
+        let mut sizes = PacketSizes::default();
+        sizes.merge(&batch);
+        sizes.merge(&PacketSizes::default());
+
+        assert_eq!(sizes.count, 5);
+        assert_eq!(sizes.min, 60);
+        assert_eq!(sizes.max, 9001);
+        assert_eq!(sizes.average(), 10690.0 / 5.0);
+
+        let histogram: Vec<_> = sizes.histogram().collect();
+        assert_eq!(histogram[0], ("≤64".to_string(), 2));
+        assert_eq!(histogram[1], ("65-128".to_string(), 1));
+        assert_eq!(
+            histogram[5],
+            ("1025-1500".to_string(), 1)
+        );
+        assert_eq!(histogram[7], (">9000".to_string(), 1));
+    }
+}
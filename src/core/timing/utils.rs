@@ -53,6 +53,25 @@ impl TimingController {
             }
         }
     }
+
+    /// 判断数据包的发送时间是否已到（用于批量合并）
+    pub fn is_due(
+        &self,
+        packet_time: DateTime<Utc>,
+    ) -> bool {
+        match (self.first_packet_time, self.real_start_time)
+        {
+            (Some(first_time), Some(real_start)) => {
+                let packet_offset = packet_time
+                    .signed_duration_since(first_time)
+                    .to_std()
+                    .unwrap_or_default();
+                real_start + packet_offset
+                    <= TokioInstant::now()
+            }
+            _ => true,
+        }
+    }
 }
 
 impl Default for TimingController {
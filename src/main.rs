@@ -17,6 +17,11 @@ async fn main() -> Result<()> {
     // 初始化日志系统
     init_logging();
 
+    // 带子命令时执行命令行工具
+    if app::cli::run(std::env::args().skip(1)).await? {
+        return Ok(());
+    }
+
     // 启动 GUI
     ui::run_gui()
 }
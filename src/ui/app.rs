@@ -18,6 +18,7 @@ use super::config::{
 };
 use super::fonts::loader;
 use super::widgets;
+use super::widgets::chart::StatsChart;
 
 /// GUI 应用程序
 pub struct DataTransferApp {
@@ -28,11 +29,13 @@ pub struct DataTransferApp {
     sender_transfer_state: TransferState,
     sender_stats: Arc<Mutex<TransferStats>>,
     sender_shared_state: Option<Arc<Mutex<TransferState>>>,
+    sender_chart: StatsChart,
     // 接收器状态
     receiver_transfer_state: TransferState,
     receiver_stats: Arc<Mutex<TransferStats>>,
     receiver_shared_state:
         Option<Arc<Mutex<TransferState>>>,
+    receiver_chart: StatsChart,
     // Tokio runtime handle
     runtime_handle: Option<tokio::runtime::Handle>,
     // 服务
@@ -60,67 +63,10 @@ impl Default for DataTransferApp {
         }
 
         // 从配置管理器初始化GUI配置
-        let config = config_manager.config();
-        let sender_config = SenderConfig {
-            data_format: config_manager
-                .get_sender_data_format(), // 从配置中加载数据格式
-            pcap_path: config.sender.dataset_path.clone(),
-            csv_file: config.sender.csv_file.clone(), // 从配置中加载CSV文件路径
-            csv_packet_interval: config
-                .sender
-                .csv_packet_interval, // 从配置中加载发送周期
-            address: config.sender.network.address.clone(),
-            port: config.sender.network.port,
-            network_type: config_manager
-                .get_sender_network_type(),
-            interface: if config
-                .sender
-                .network
-                .interface
-                .is_empty()
-            {
-                None
-            } else {
-                Some(
-                    config.sender.network.interface.clone(),
-                )
-            },
-        };
-
-        let receiver_config = ReceiverConfig {
-            output_path: config
-                .receiver
-                .output_path
-                .clone(),
-            dataset_name: config
-                .receiver
-                .dataset_name
-                .clone(),
-            address: config
-                .receiver
-                .network
-                .address
-                .clone(),
-            port: config.receiver.network.port,
-            network_type: config_manager
-                .get_receiver_network_type(),
-            interface: if config
-                .receiver
-                .network
-                .interface
-                .is_empty()
-            {
-                None
-            } else {
-                Some(
-                    config
-                        .receiver
-                        .network
-                        .interface
-                        .clone(),
-                )
-            },
-        };
+        let sender_config =
+            config_manager.get_sender_config();
+        let receiver_config =
+            config_manager.get_receiver_config();
 
         let transfer_service =
             TransferService::new(config_manager);
@@ -134,11 +80,13 @@ impl Default for DataTransferApp {
                 TransferStats::default(),
             )),
             sender_shared_state: None,
+            sender_chart: StatsChart::default(),
             receiver_transfer_state: TransferState::Idle,
             receiver_stats: Arc::new(Mutex::new(
                 TransferStats::default(),
             )),
             receiver_shared_state: None,
+            receiver_chart: StatsChart::default(),
             runtime_handle: None,
             transfer_service,
         }
@@ -184,6 +132,7 @@ impl DataTransferApp {
             &handle,
         ) {
             Ok(shared_state) => {
+                self.sender_chart.reset();
                 self.sender_shared_state =
                     Some(shared_state);
                 self.sender_transfer_state =
@@ -232,6 +181,7 @@ impl DataTransferApp {
             &handle,
         ) {
             Ok(shared_state) => {
+                self.receiver_chart.reset();
                 self.receiver_shared_state =
                     Some(shared_state);
                 self.receiver_transfer_state =
@@ -292,6 +242,24 @@ impl eframe::App for DataTransferApp {
             &self.receiver_shared_state,
         );
 
+        // 运行中的会话按采样间隔记录统计曲线
+        if matches!(
+            self.sender_transfer_state,
+            TransferState::Running
+        ) {
+            if let Ok(stats) = self.sender_stats.lock() {
+                self.sender_chart.sample(&stats);
+            }
+        }
+        if matches!(
+            self.receiver_transfer_state,
+            TransferState::Running
+        ) {
+            if let Ok(stats) = self.receiver_stats.lock() {
+                self.receiver_chart.sample(&stats);
+            }
+        }
+
         // 标签按钮区域
         egui::TopBottomPanel::top("tab_buttons")
             .resizable(false)
@@ -401,6 +369,7 @@ impl eframe::App for DataTransferApp {
                         &mut self.sender_config,
                         &self.sender_transfer_state,
                         &self.sender_stats,
+                        &mut self.sender_chart,
                     );
 
                     // 处理用户动作
@@ -421,6 +390,7 @@ impl eframe::App for DataTransferApp {
                             &mut self.receiver_config,
                             &self.receiver_transfer_state,
                             &self.receiver_stats,
+                            &mut self.receiver_chart,
                         );
 
                     // 处理用户动作
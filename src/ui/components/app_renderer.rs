@@ -9,6 +9,7 @@ use crate::core::network::sender::TransferState;
 use crate::core::stats::collector::TransferStats;
 use crate::ui::app_state::AppStateManager;
 use crate::ui::config::{ReceiverConfig, SenderConfig};
+use crate::ui::widgets::chart::StatsChart;
 
 /// 用户动作枚举
 #[derive(Debug, Clone, PartialEq)]
@@ -30,6 +31,7 @@ impl AppRenderer {
         config: &mut SenderConfig,
         transfer_state: &TransferState,
         stats: &Arc<Mutex<TransferStats>>,
+        chart: &mut StatsChart,
     ) -> UserAction {
         // 下半部分：传输统计 (固定高度)
         egui::TopBottomPanel::bottom("sender_stats_panel")
@@ -38,10 +40,21 @@ impl AppRenderer {
             .show(ui.ctx(), |ui| {
                 ui.vertical(|ui| {
                     ui.add_space(8.0); // 补偿TopBottomPanel的默认内边距
-                    ui.heading("Transfer Statistics");
+                    ui.horizontal(|ui| {
+                        ui.heading("Transfer Statistics");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            chart.show_toggle(ui);
+                        });
+                    });
                     ui.separator();
                     ui.add_space(8.0);
-                    AppStateManager::render_sender_stats_safely(stats, ui);
+                    if chart.is_visible() {
+                        chart.show(ui);
+                    } else {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            AppStateManager::render_sender_stats_safely(stats, ui);
+                        });
+                    }
                 });
             });
 
@@ -70,6 +83,7 @@ impl AppRenderer {
         config: &mut ReceiverConfig,
         transfer_state: &TransferState,
         stats: &Arc<Mutex<TransferStats>>,
+        chart: &mut StatsChart,
     ) -> UserAction {
         // 下半部分：传输统计 (固定高度)
         egui::TopBottomPanel::bottom("receiver_stats_panel")
@@ -78,10 +92,21 @@ impl AppRenderer {
             .show(ui.ctx(), |ui| {
                 ui.vertical(|ui| {
                     ui.add_space(8.0); // 补偿TopBottomPanel的默认内边距
-                    ui.heading("Transfer Statistics");
+                    ui.horizontal(|ui| {
+                        ui.heading("Transfer Statistics");
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            chart.show_toggle(ui);
+                        });
+                    });
                     ui.separator();
                     ui.add_space(8.0);
-                    AppStateManager::render_receiver_stats_safely(stats, ui);
+                    if chart.is_visible() {
+                        chart.show(ui);
+                    } else {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            AppStateManager::render_receiver_stats_safely(stats, ui);
+                        });
+                    }
                 });
             });
 
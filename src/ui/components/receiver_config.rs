@@ -2,7 +2,18 @@
 
 use super::super::config::ReceiverConfig;
 use super::PathSelector;
+use crate::app::config::options::{
+    FilterMode, OverflowPolicy, ReflectMode, RotationMode,
+    SequenceMode, TimestampSource,
+};
 use crate::app::config::types::NetworkType;
+use crate::core::network::sys::MAX_BATCH_SIZE;
+use crate::core::network::types::MAX_SOCKET_COUNT;
+use crate::core::probe::reflector::MAX_PREFIX_BYTES;
+use crate::core::probe::sequence::MAX_SEQUENCE_WINDOW;
+use crate::core::stats::report::{
+    MAX_REPORT_INTERVAL_MS, MIN_REPORT_INTERVAL_MS,
+};
 use egui;
 
 /// 渲染网络类型选择组合框
@@ -36,6 +47,167 @@ fn render_network_type_combo(
     });
 }
 
+/// 渲染队列溢出策略选择组合框
+fn render_overflow_policy_combo(
+    ui: &mut egui::Ui,
+    policy: &mut OverflowPolicy,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_overflow_policy_combo",
+        )
+        .selected_text(format!("{}", policy))
+        .show_ui(ui, |ui| {
+            for option in [
+                OverflowPolicy::Block,
+                OverflowPolicy::DropNewest,
+                OverflowPolicy::DropOldest,
+            ] {
+                ui.selectable_value(
+                    policy,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染接收时间戳来源选择组合框
+fn render_timestamp_source_combo(
+    ui: &mut egui::Ui,
+    source: &mut TimestampSource,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_timestamp_source_combo",
+        )
+        .selected_text(format!("{}", source))
+        .show_ui(ui, |ui| {
+            for option in [
+                TimestampSource::User,
+                TimestampSource::Kernel,
+                TimestampSource::Hardware,
+            ] {
+                ui.selectable_value(
+                    source,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染数据集轮转方式选择组合框
+fn render_rotation_mode_combo(
+    ui: &mut egui::Ui,
+    mode: &mut RotationMode,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_rotation_mode_combo",
+        )
+        .selected_text(format!("{}", mode))
+        .show_ui(ui, |ui| {
+            for option in [
+                RotationMode::None,
+                RotationMode::Minutes,
+                RotationMode::Packets,
+                RotationMode::Bytes,
+                RotationMode::Hourly,
+            ] {
+                ui.selectable_value(
+                    mode,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染接收过滤规则组合方式选择组合框
+fn render_filter_mode_combo(
+    ui: &mut egui::Ui,
+    mode: &mut FilterMode,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_filter_mode_combo",
+        )
+        .selected_text(format!("{}", mode))
+        .show_ui(ui, |ui| {
+            for option in [FilterMode::All, FilterMode::Any]
+            {
+                ui.selectable_value(
+                    mode,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染序号来源选择组合框
+fn render_sequence_mode_combo(
+    ui: &mut egui::Ui,
+    mode: &mut SequenceMode,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_sequence_mode_combo",
+        )
+        .selected_text(format!("{}", mode))
+        .show_ui(ui, |ui| {
+            for option in [
+                SequenceMode::Off,
+                SequenceMode::Header,
+                SequenceMode::Field,
+            ] {
+                ui.selectable_value(
+                    mode,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染反射模式选择组合框
+fn render_reflect_mode_combo(
+    ui: &mut egui::Ui,
+    mode: &mut ReflectMode,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "receiver_reflect_mode_combo",
+        )
+        .selected_text(format!("{}", mode))
+        .show_ui(ui, |ui| {
+            for option in [
+                ReflectMode::Off,
+                ReflectMode::Full,
+                ReflectMode::Prefix,
+            ] {
+                ui.selectable_value(
+                    mode,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
 /// 渲染接收器配置区域
 pub fn render_receiver_config(
     ui: &mut egui::Ui,
@@ -90,5 +262,305 @@ pub fn render_receiver_config(
                 enabled,
             );
             ui.end_row();
+
+            ui.label("Batch Size");
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(
+                    &mut config.options.batch_size,
+                )
+                .range(1..=MAX_BATCH_SIZE),
+            );
+            ui.end_row();
+
+            ui.label("Queue Capacity");
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(
+                    &mut config.options.queue_capacity,
+                )
+                .range(1..=1_000_000),
+            );
+            ui.end_row();
+
+            ui.label("Queue Overflow");
+            render_overflow_policy_combo(
+                ui,
+                &mut config.options.overflow_policy,
+                enabled,
+            );
+            ui.end_row();
+
+            ui.label("Sockets");
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(
+                    &mut config.options.socket_count,
+                )
+                .range(1..=MAX_SOCKET_COUNT),
+            )
+            .on_hover_text("Receive sockets sharing the port via SO_REUSEPORT");
+            ui.end_row();
+
+            // 每行一个附加端点
+            ui.label("Extra Endpoints");
+            let mut endpoints =
+                config.options.endpoints.join("\n");
+            let response = ui
+                .add_enabled(
+                    enabled,
+                    egui::TextEdit::multiline(&mut endpoints)
+                        .desired_rows(2)
+                        .hint_text("30002-30010"),
+                )
+                .on_hover_text("One per line: port, port range, addr:port or addr:port-port");
+            if response.changed() {
+                config.options.endpoints = endpoints
+                    .lines()
+                    .map(String::from)
+                    .collect();
+            }
+            ui.end_row();
+
+            ui.label("Dataset per Endpoint");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut config.options.dataset_per_endpoint,
+                ),
+            )
+            .on_hover_text("Write each endpoint into its own dataset");
+            ui.end_row();
+
+            ui.label("Timestamps");
+            render_timestamp_source_combo(
+                ui,
+                &mut config.options.timestamp_source,
+                enabled,
+            );
+            ui.end_row();
+
+            // 停止条件（0 表示不限制）
+            for (label, value, hint) in [
+                (
+                    "Max Packets",
+                    &mut config.options.max_packets,
+                    "Stop after this many packets, 0 = unlimited",
+                ),
+                (
+                    "Max Bytes",
+                    &mut config.options.max_bytes,
+                    "Stop after this many bytes, 0 = unlimited",
+                ),
+                (
+                    "Max Duration (s)",
+                    &mut config.options.max_duration_secs,
+                    "Stop after this many seconds, 0 = unlimited",
+                ),
+                (
+                    "Idle Timeout (s)",
+                    &mut config.options.idle_timeout_secs,
+                    "Stop when no packet arrives for this many seconds after the first one, 0 = disabled",
+                ),
+            ] {
+                ui.label(label);
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(value),
+                )
+                .on_hover_text(hint);
+                ui.end_row();
+            }
+
+            let rotation = &mut config.options.rotation;
+            ui.label("Rotation");
+            render_rotation_mode_combo(
+                ui,
+                &mut rotation.mode,
+                enabled,
+            );
+            ui.end_row();
+
+            if matches!(
+                rotation.mode,
+                RotationMode::Minutes
+                    | RotationMode::Packets
+                    | RotationMode::Bytes
+            ) {
+                ui.label("Rotation Interval");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut rotation.interval)
+                        .range(1..=u64::MAX),
+                )
+                .on_hover_text("Minutes, packets or bytes per dataset");
+                ui.end_row();
+            }
+
+            if rotation.mode != RotationMode::None {
+                ui.label("Name Template");
+                ui.add_enabled(
+                    enabled,
+                    egui::TextEdit::singleline(
+                        &mut rotation.name_template,
+                    ),
+                )
+                .on_hover_text("Placeholders: {name}, {seq}, {yyyyMMdd_HHmmss}");
+                ui.end_row();
+            }
+
+            let filter = &mut config.options.filter;
+            ui.label("Filter Mode");
+            render_filter_mode_combo(
+                ui,
+                &mut filter.mode,
+                enabled,
+            );
+            ui.end_row();
+
+            // 每行一条规则
+            ui.label("Filter Rules");
+            let mut rules = filter.rules.join("\n");
+            let response = ui
+                .add_enabled(
+                    enabled,
+                    egui::TextEdit::multiline(&mut rules)
+                        .desired_rows(3)
+                        .hint_text("src 10.0.0.0/8"),
+                )
+                .on_hover_text("One rule per line: src <cidr>, sport/dport <a-b>, len <a-b>, offset <n> == 0x<hex>; prefix with 'not' to negate");
+            if response.changed() {
+                filter.rules =
+                    rules.lines().map(String::from).collect();
+            }
+            ui.end_row();
+
+            let sequence = &mut config.options.sequence;
+            ui.label("Sequence");
+            render_sequence_mode_combo(
+                ui,
+                &mut sequence.mode,
+                enabled,
+            );
+            ui.end_row();
+
+            if sequence.mode == SequenceMode::Field {
+                ui.label("Sequence Offset");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut sequence.offset),
+                )
+                .on_hover_text("Byte offset of the sequence field in the payload");
+                ui.end_row();
+
+                ui.label("Sequence Width");
+                ui.add_enabled_ui(enabled, |ui| {
+                    ui.horizontal(|ui| {
+                        for width in [1, 2, 4, 8] {
+                            ui.selectable_value(
+                                &mut sequence.width,
+                                width,
+                                format!("{width} B"),
+                            );
+                        }
+                        ui.checkbox(
+                            &mut sequence.little_endian,
+                            "Little endian",
+                        );
+                    });
+                });
+                ui.end_row();
+            }
+
+            if sequence.mode != SequenceMode::Off {
+                ui.label("Reorder Window");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut sequence.window)
+                        .range(1..=MAX_SEQUENCE_WINDOW),
+                )
+                .on_hover_text("Packets; older sequence numbers count as late");
+                ui.end_row();
+            }
+
+            let latency = &mut config.options.latency;
+            ui.label("Measure Latency");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut latency.enabled,
+                ),
+            )
+            .on_hover_text("Requires test headers with send timestamps");
+            ui.end_row();
+
+            if latency.enabled {
+                ui.label("Clock Offset (µs)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut latency.clock_offset_us,
+                    ),
+                )
+                .on_hover_text("Receiver clock minus sender clock");
+                ui.end_row();
+            }
+
+            let reflector = &mut config.options.reflector;
+            ui.label("Reflector");
+            render_reflect_mode_combo(
+                ui,
+                &mut reflector.mode,
+                enabled,
+            );
+            ui.end_row();
+
+            if reflector.mode == ReflectMode::Prefix {
+                ui.label("Prefix Bytes");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut reflector.prefix_bytes,
+                    )
+                    .range(1..=MAX_PREFIX_BYTES),
+                )
+                .on_hover_text("Bytes echoed back to the sender; test headers are never truncated");
+                ui.end_row();
+            }
+
+            let report = &mut config.options.report;
+            ui.label("Write Report");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut report.enabled,
+                ),
+            )
+            .on_hover_text("Write JSON and CSV statistics reports when the transfer ends");
+            ui.end_row();
+
+            if report.enabled {
+                ui.label("Report Interval (ms)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut report.interval_ms,
+                    )
+                    .range(
+                        MIN_REPORT_INTERVAL_MS
+                            ..=MAX_REPORT_INTERVAL_MS,
+                    ),
+                )
+                .on_hover_text("Sampling interval of the time series");
+                ui.end_row();
+
+                ui.label("Report Directory");
+                ui.add_enabled(
+                    enabled,
+                    PathSelector::new(&mut report.directory),
+                )
+                .on_hover_text("Leave empty to write reports to the output path");
+                ui.end_row();
+            }
         });
 }
@@ -2,21 +2,52 @@
 
 use super::super::config::SenderConfig;
 use super::PathSelector;
+use crate::app::config::options::{
+    PayloadPattern, SizeDistribution,
+};
 use crate::app::config::types::{DataFormat, NetworkType};
+use crate::core::network::generator::MAX_GENERATOR_PACKET_SIZE;
+use crate::core::network::sys::MAX_BATCH_SIZE;
+use crate::core::pipeline::fuzz::{
+    MAX_FUZZ_EXTEND, MAX_FUZZ_MUTATIONS,
+};
+use crate::core::pipeline::impairment::{
+    MAX_IMPAIRMENT_DELAY_MS, MAX_REORDER_WINDOW,
+};
+use crate::core::probe::rtt::MAX_ECHO_TIMEOUT_MS;
+use crate::core::stats::report::{
+    MAX_REPORT_INTERVAL_MS, MIN_REPORT_INTERVAL_MS,
+};
 use egui;
 
-/// CSV文件选择组件
-struct CsvFileSelector<'a> {
+/// 文件选择组件（按扩展名过滤）
+struct FileSelector<'a> {
     path: &'a mut String,
+    kind: &'static str,
+    extensions: &'static [&'static str],
 }
 
-impl<'a> CsvFileSelector<'a> {
-    fn new(path: &'a mut String) -> Self {
-        Self { path }
+impl<'a> FileSelector<'a> {
+    /// CSV 文件
+    fn csv(path: &'a mut String) -> Self {
+        Self {
+            path,
+            kind: "CSV",
+            extensions: &["csv"],
+        }
+    }
+
+    /// pcap / pcapng 抓包文件
+    fn capture(path: &'a mut String) -> Self {
+        Self {
+            path,
+            kind: "Capture",
+            extensions: &["pcap", "pcapng", "cap"],
+        }
     }
 }
 
-impl<'a> egui::Widget for CsvFileSelector<'a> {
+impl<'a> egui::Widget for FileSelector<'a> {
     fn ui(self, ui: &mut egui::Ui) -> egui::Response {
         let button_width = 60.0;
         let spacing = ui.spacing().item_spacing.x;
@@ -28,13 +59,19 @@ impl<'a> egui::Widget for CsvFileSelector<'a> {
             let text_edit =
                 egui::TextEdit::singleline(self.path)
                     .desired_width(input_width)
-                    .hint_text("Select CSV file...");
+                    .hint_text(format!(
+                        "Select {} file...",
+                        self.kind
+                    ));
 
             let text_response = ui.add(text_edit);
 
             if ui.button("Browse").clicked() {
                 if let Some(path) = rfd::FileDialog::new()
-                    .add_filter("CSV files", &["csv"])
+                    .add_filter(
+                        format!("{} files", self.kind),
+                        self.extensions,
+                    )
                     .pick_file()
                 {
                     *self.path =
@@ -70,6 +107,71 @@ fn render_data_format_combo(
                 DataFormat::Csv,
                 format!("{}", DataFormat::Csv),
             );
+            ui.selectable_value(
+                data_format,
+                DataFormat::Generator,
+                format!("{}", DataFormat::Generator),
+            );
+            ui.selectable_value(
+                data_format,
+                DataFormat::Capture,
+                format!("{}", DataFormat::Capture),
+            );
+        });
+    });
+}
+
+/// 渲染合成流量包长分布选择组合框
+fn render_size_distribution_combo(
+    ui: &mut egui::Ui,
+    size: &mut SizeDistribution,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "sender_size_distribution_combo",
+        )
+        .selected_text(format!("{}", size))
+        .show_ui(ui, |ui| {
+            for option in [
+                SizeDistribution::Fixed,
+                SizeDistribution::Uniform,
+                SizeDistribution::Imix,
+            ] {
+                ui.selectable_value(
+                    size,
+                    option,
+                    format!("{}", option),
+                );
+            }
+        });
+    });
+}
+
+/// 渲染合成流量负载内容选择组合框
+fn render_payload_pattern_combo(
+    ui: &mut egui::Ui,
+    pattern: &mut PayloadPattern,
+    enabled: bool,
+) {
+    ui.add_enabled_ui(enabled, |ui| {
+        egui::ComboBox::from_id_salt(
+            "sender_payload_pattern_combo",
+        )
+        .selected_text(format!("{}", pattern))
+        .show_ui(ui, |ui| {
+            for option in [
+                PayloadPattern::Zeros,
+                PayloadPattern::Incrementing,
+                PayloadPattern::Random,
+                PayloadPattern::Sequence,
+            ] {
+                ui.selectable_value(
+                    pattern,
+                    option,
+                    format!("{}", option),
+                );
+            }
         });
     });
 }
@@ -141,7 +243,7 @@ pub fn render_sender_config(
                     ui.label("CSV File");
                     ui.add_enabled(
                         enabled,
-                        CsvFileSelector::new(
+                        FileSelector::csv(
                             &mut config.csv_file,
                         ),
                     );
@@ -158,6 +260,123 @@ pub fn render_sender_config(
                     );
                     ui.end_row();
                 }
+                DataFormat::Capture => {
+                    ui.label("Capture File");
+                    ui.add_enabled(
+                        enabled,
+                        FileSelector::capture(
+                            &mut config.capture_file,
+                        ),
+                    )
+                    .on_hover_text("Classic pcap or pcapng file, UDP payloads are replayed");
+                    ui.end_row();
+
+                    ui.label("Capture Filter");
+                    ui.add_enabled(
+                        enabled,
+                        egui::TextEdit::singleline(
+                            &mut config.options.capture_filter,
+                        )
+                        .hint_text("e.g. udp and dst port 9000"),
+                    )
+                    .on_hover_text("host, net, port, portrange (with src/dst), iface; and/or/not");
+                    ui.end_row();
+                }
+                DataFormat::Generator => {
+                    let generator =
+                        &mut config.options.generator;
+                    let size_row =
+                        |ui: &mut egui::Ui,
+                         label: &str,
+                         value: &mut usize| {
+                            ui.label(label);
+                            ui.add_enabled(
+                                enabled,
+                                egui::DragValue::new(value)
+                                    .range(
+                                        1..=MAX_GENERATOR_PACKET_SIZE,
+                                    )
+                                    .suffix(" B"),
+                            );
+                            ui.end_row();
+                        };
+
+                    ui.label("Packet Size");
+                    render_size_distribution_combo(
+                        ui,
+                        &mut generator.size,
+                        enabled,
+                    );
+                    ui.end_row();
+                    match generator.size {
+                        SizeDistribution::Fixed => size_row(
+                            ui,
+                            "Size",
+                            &mut generator.packet_size,
+                        ),
+                        SizeDistribution::Uniform => {
+                            size_row(
+                                ui,
+                                "Min Size",
+                                &mut generator.min_size,
+                            );
+                            size_row(
+                                ui,
+                                "Max Size",
+                                &mut generator.max_size,
+                            );
+                        }
+                        SizeDistribution::Imix => {}
+                    }
+
+                    ui.label("Payload");
+                    render_payload_pattern_combo(
+                        ui,
+                        &mut generator.pattern,
+                        enabled,
+                    );
+                    ui.end_row();
+
+                    ui.label("Rate");
+                    ui.add_enabled(
+                        enabled,
+                        egui::DragValue::new(
+                            &mut generator.rate_mbps,
+                        )
+                        .range(0.0..=100_000.0)
+                        .speed(1.0)
+                        .suffix(" Mbps"),
+                    )
+                    .on_hover_text("UDP payload rate, 0 sends as fast as possible");
+                    ui.end_row();
+
+                    ui.label("Duration");
+                    ui.add_enabled(
+                        enabled,
+                        egui::DragValue::new(
+                            &mut generator.duration_secs,
+                        )
+                        .range(0..=86_400)
+                        .suffix(" s"),
+                    )
+                    .on_hover_text("0 sends until stopped");
+                    ui.end_row();
+
+                    if generator.size != SizeDistribution::Fixed
+                        || generator.pattern
+                            == PayloadPattern::Random
+                    {
+                        ui.label("Generator Seed");
+                        ui.add_enabled(
+                            enabled,
+                            egui::DragValue::new(
+                                &mut generator.seed,
+                            ),
+                        )
+                        .on_hover_text("Seed of random sizes and payloads");
+                        ui.end_row();
+                    }
+                }
             }
 
             ui.label("Target Address");
@@ -185,5 +404,332 @@ pub fn render_sender_config(
                 enabled,
             );
             ui.end_row();
+
+            ui.label("Batch Size");
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(
+                    &mut config.options.batch_size,
+                )
+                .range(1..=MAX_BATCH_SIZE),
+            );
+            ui.end_row();
+
+            ui.label("Prefetch Depth");
+            ui.add_enabled(
+                enabled,
+                egui::DragValue::new(
+                    &mut config.options.prefetch_packets,
+                )
+                .range(0..=1_000_000),
+            )
+            .on_hover_text("Packets read ahead in memory, 0 disables prefetch");
+            ui.end_row();
+
+            // 按来源地址过滤仅适用于带元数据的 PCAP 数据集
+            if config.data_format == DataFormat::Pcap {
+                ui.label("Source Filter");
+                ui.add_enabled(
+                    enabled,
+                    egui::TextEdit::singleline(
+                        &mut config.options.source_filter,
+                    )
+                    .hint_text("IP or IP:port, empty = all"),
+                );
+                ui.end_row();
+            }
+
+            let test_header = &mut config.options.test_header;
+            ui.label("Test Header");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut test_header.enabled,
+                ),
+            )
+            .on_hover_text("Prepend a sequence header to every packet");
+            ui.end_row();
+
+            if test_header.enabled {
+                ui.label("Stream ID");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut test_header.stream_id,
+                    ),
+                );
+                ui.end_row();
+
+                ui.label("Send Timestamp");
+                ui.add_enabled(
+                    enabled,
+                    egui::Checkbox::without_text(
+                        &mut test_header.timestamp,
+                    ),
+                )
+                .on_hover_text("Embed the send time for one-way latency measurement");
+                ui.end_row();
+            }
+
+            let rtt = &mut config.options.rtt;
+            ui.label("Measure RTT");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(&mut rtt.enabled),
+            )
+            .on_hover_text("Listen for packets echoed by a reflector; implies timestamped test headers");
+            ui.end_row();
+
+            if rtt.enabled {
+                ui.label("Echo Timeout (ms)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut rtt.timeout_ms)
+                        .range(0..=MAX_ECHO_TIMEOUT_MS),
+                )
+                .on_hover_text("How long to wait for echoes after the last packet");
+                ui.end_row();
+            }
+
+            let impairment = &mut config.options.impairment;
+            ui.label("Impairments");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut impairment.enabled,
+                ),
+            )
+            .on_hover_text("Emulate drop, burst loss, duplication, reordering, delay and corruption in software");
+            ui.end_row();
+
+            if impairment.enabled {
+                let percent_row =
+                    |ui: &mut egui::Ui,
+                     label: &str,
+                     value: &mut f64| {
+                        ui.label(label);
+                        ui.add_enabled(
+                            enabled,
+                            egui::DragValue::new(value)
+                                .range(0.0..=100.0)
+                                .speed(0.1)
+                                .suffix(" %"),
+                        );
+                        ui.end_row();
+                    };
+
+                ui.label("Seed");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut impairment.seed),
+                )
+                .on_hover_text("The same seed and settings reproduce the same impairments");
+                ui.end_row();
+
+                percent_row(
+                    ui,
+                    "Drop",
+                    &mut impairment.drop_percent,
+                );
+
+                let burst = &mut impairment.burst_loss;
+                ui.label("Burst Loss");
+                ui.add_enabled(
+                    enabled,
+                    egui::Checkbox::without_text(
+                        &mut burst.enabled,
+                    ),
+                )
+                .on_hover_text("Gilbert–Elliott two-state loss model");
+                ui.end_row();
+                if burst.enabled {
+                    percent_row(
+                        ui,
+                        "Enter Burst",
+                        &mut burst.enter_bad_percent,
+                    );
+                    percent_row(
+                        ui,
+                        "Exit Burst",
+                        &mut burst.exit_bad_percent,
+                    );
+                    percent_row(
+                        ui,
+                        "Good Loss",
+                        &mut burst.good_loss_percent,
+                    );
+                    percent_row(
+                        ui,
+                        "Burst Loss",
+                        &mut burst.bad_loss_percent,
+                    );
+                }
+
+                percent_row(
+                    ui,
+                    "Duplicate",
+                    &mut impairment.duplicate_percent,
+                );
+                percent_row(
+                    ui,
+                    "Reorder",
+                    &mut impairment.reorder_percent,
+                );
+
+                ui.label("Reorder Window");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut impairment.reorder_window,
+                    )
+                    .range(1..=MAX_REORDER_WINDOW),
+                )
+                .on_hover_text("A reordered packet is sent after this many later packets");
+                ui.end_row();
+
+                ui.label("Delay (ms)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut impairment.delay_ms,
+                    )
+                    .range(0..=MAX_IMPAIRMENT_DELAY_MS),
+                );
+                ui.end_row();
+
+                ui.label("Jitter (ms)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut impairment.jitter_ms,
+                    )
+                    .range(0..=MAX_IMPAIRMENT_DELAY_MS),
+                )
+                .on_hover_text("Uniformly distributed around the delay");
+                ui.end_row();
+
+                percent_row(
+                    ui,
+                    "Corrupt",
+                    &mut impairment.corrupt_percent,
+                );
+            }
+
+            let fuzz = &mut config.options.fuzz;
+            ui.label("Fuzz");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(&mut fuzz.enabled),
+            )
+            .on_hover_text("Mutate every packet with a seed and log each mutated packet");
+            ui.end_row();
+
+            if fuzz.enabled {
+                ui.label("Fuzz Seed");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(&mut fuzz.seed),
+                )
+                .on_hover_text("Packet N is mutated from the seed and N only");
+                ui.end_row();
+
+                ui.label("Max Mutations");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut fuzz.max_mutations,
+                    )
+                    .range(1..=MAX_FUZZ_MUTATIONS),
+                )
+                .on_hover_text("Each packet receives between 1 and this many mutations");
+                ui.end_row();
+
+                ui.label("Mutators");
+                ui.add_enabled_ui(enabled, |ui| {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.checkbox(
+                            &mut fuzz.bit_flip,
+                            "Bit Flip",
+                        );
+                        ui.checkbox(
+                            &mut fuzz.boundary,
+                            "Boundary",
+                        )
+                        .on_hover_text("Write min/max/zero values of the CSV column types");
+                        ui.checkbox(
+                            &mut fuzz.truncate,
+                            "Truncate",
+                        );
+                        ui.checkbox(
+                            &mut fuzz.extend,
+                            "Extend",
+                        );
+                        ui.checkbox(
+                            &mut fuzz.splice,
+                            "Splice",
+                        )
+                        .on_hover_text("Join with the tail of the previous packet");
+                    });
+                });
+                ui.end_row();
+
+                if fuzz.extend {
+                    ui.label("Max Extend");
+                    ui.add_enabled(
+                        enabled,
+                        egui::DragValue::new(
+                            &mut fuzz.max_extend,
+                        )
+                        .range(0..=MAX_FUZZ_EXTEND)
+                        .suffix(" B"),
+                    );
+                    ui.end_row();
+                }
+
+                ui.label("Fuzz Log Directory");
+                ui.add_enabled(
+                    enabled,
+                    PathSelector::new(
+                        &mut fuzz.log_directory,
+                    ),
+                )
+                .on_hover_text("Leave empty to write the log next to the dataset");
+                ui.end_row();
+            }
+
+            let report = &mut config.options.report;
+            ui.label("Write Report");
+            ui.add_enabled(
+                enabled,
+                egui::Checkbox::without_text(
+                    &mut report.enabled,
+                ),
+            )
+            .on_hover_text("Write JSON and CSV statistics reports when the transfer ends");
+            ui.end_row();
+
+            if report.enabled {
+                ui.label("Report Interval (ms)");
+                ui.add_enabled(
+                    enabled,
+                    egui::DragValue::new(
+                        &mut report.interval_ms,
+                    )
+                    .range(
+                        MIN_REPORT_INTERVAL_MS
+                            ..=MAX_REPORT_INTERVAL_MS,
+                    ),
+                )
+                .on_hover_text("Sampling interval of the time series");
+                ui.end_row();
+
+                ui.label("Report Directory");
+                ui.add_enabled(
+                    enabled,
+                    PathSelector::new(&mut report.directory),
+                )
+                .on_hover_text("Leave empty to write reports next to the dataset");
+                ui.end_row();
+            }
         });
 }
@@ -3,6 +3,7 @@
 use crate::core::stats::collector::TransferStats;
 use crate::utils::helpers::format_bytes;
 use egui;
+use std::time::Duration;
 
 /// 渲染统计信息
 pub fn render_stats(
@@ -16,124 +17,413 @@ pub fn render_stats(
         .spacing([20.0, 4.0])
         .striped(true)
         .show(ui, |ui| {
-            ui.label("Packets Transferred");
-            // 让数值列占用剩余空间
-            ui.with_layout(
-                egui::Layout::left_to_right(
-                    egui::Align::Center,
-                ),
-                |ui| {
-                    ui.label(
-                        stats
-                            .get_packets_processed()
-                            .to_string(),
-                    );
-                    ui.allocate_response(
-                        egui::Vec2::new(
-                            ui.available_width(),
-                            0.0,
-                        ),
-                        egui::Sense::hover(),
-                    );
-                },
+            render_stat_row(
+                ui,
+                "Packets Transferred",
+                stats.get_packets_processed().to_string(),
             );
-            ui.end_row();
-
-            ui.label("Bytes Transferred");
-            ui.with_layout(
-                egui::Layout::left_to_right(
-                    egui::Align::Center,
-                ),
-                |ui| {
-                    ui.label(format_bytes(
-                        stats.get_bytes_processed(),
-                    ));
-                    ui.allocate_response(
-                        egui::Vec2::new(
-                            ui.available_width(),
-                            0.0,
-                        ),
-                        egui::Sense::hover(),
-                    );
-                },
+
+            render_stat_row(
+                ui,
+                "Bytes Transferred",
+                format_bytes(stats.get_bytes_processed()),
             );
-            ui.end_row();
-
-            ui.label("Data Rate");
-            ui.with_layout(
-                egui::Layout::left_to_right(
-                    egui::Align::Center,
-                ),
-                |ui| {
-                    if let Some(packet_rate) =
-                        stats.get_packet_rate_bps()
-                    {
-                        ui.label(format!(
-                            "{}/s",
-                            format_bytes(
-                                packet_rate as u64 / 8
-                            )
-                        ));
-                    } else {
-                        ui.label("Unknown".to_string());
-                    }
-                    ui.allocate_response(
-                        egui::Vec2::new(
-                            ui.available_width(),
-                            0.0,
+
+            let data_rate = if let Some(packet_rate) =
+                stats.get_packet_rate_bps()
+            {
+                format!(
+                    "{}/s",
+                    format_bytes(packet_rate as u64 / 8)
+                )
+            } else {
+                "Unknown".to_string()
+            };
+            render_stat_row(ui, "Data Rate", data_rate);
+
+            // 滑动窗口速率、峰值与包长统计
+            let sizes = stats.get_packet_sizes();
+            if sizes.count > 0 {
+                let rate_1s = stats
+                    .get_window_rate(Duration::from_secs(1));
+                let rate_10s = stats
+                    .get_window_rate(Duration::from_secs(10));
+                let peak = stats.get_peak_rate();
+                let per_sec =
+                    |bps: f64| format!("{}/s", format_bytes(bps as u64 / 8));
+                render_stat_row(
+                    ui,
+                    "Rate (1s / 10s)",
+                    format!(
+                        "{} / {}",
+                        per_sec(rate_1s.bps),
+                        per_sec(rate_10s.bps)
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Packets/s (1s / 10s)",
+                    format!(
+                        "{:.0} / {:.0}",
+                        rate_1s.pps, rate_10s.pps
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Peak Rate",
+                    format!(
+                        "{} ({:.0} pps)",
+                        per_sec(peak.bps),
+                        peak.pps
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Packet Size",
+                    format!(
+                        "min {} / avg {:.1} / max {}",
+                        sizes.min,
+                        sizes.average(),
+                        sizes.max
+                    ),
+                );
+                let histogram: Vec<String> = sizes
+                    .histogram()
+                    .filter(|(_, count)| *count > 0)
+                    .map(|(label, count)| {
+                        format!("{label}: {count}")
+                    })
+                    .collect();
+                render_stat_row(
+                    ui,
+                    "Size Distribution",
+                    histogram.join(", "),
+                );
+            }
+
+            let duration = if let Some(packet_duration) =
+                stats.get_packet_duration()
+            {
+                format!(
+                    "{:.3}s",
+                    packet_duration.as_secs_f64()
+                )
+            } else {
+                "Unknown".to_string()
+            };
+            render_stat_row(ui, "Duration", duration);
+
+            render_stat_row(
+                ui,
+                "Error Count",
+                format!("{}", stats.get_errors()),
+            );
+
+            // 内核套接字缓冲区溢出丢包（仅接收器使用）
+            if stats.get_queue_capacity() > 0 {
+                render_stat_row(
+                    ui,
+                    "Kernel Drops",
+                    stats.get_kernel_drops().to_string(),
+                );
+            }
+
+            // 接收过滤统计
+            if stats.is_filter_active() {
+                render_stat_row(
+                    ui,
+                    "Filtered",
+                    stats.get_filtered().to_string(),
+                );
+            }
+
+            // 达到停止条件后未写入的数据包
+            if stats.get_over_budget() > 0 {
+                render_stat_row(
+                    ui,
+                    "Over Budget",
+                    stats.get_over_budget().to_string(),
+                );
+            }
+
+            // 基于序号的丢包与乱序统计
+            if let Some(sequence) = stats.get_sequence() {
+                render_stat_row(
+                    ui,
+                    "Lost",
+                    format!(
+                        "{} ({:.3}%)",
+                        sequence.lost,
+                        sequence.loss_percent()
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Reordered / Dup / Late",
+                    format!(
+                        "{} / {} / {}",
+                        sequence.reordered,
+                        sequence.duplicates,
+                        sequence.late
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Sequenced",
+                    format!(
+                        "{} in {} streams ({} without sequence)",
+                        sequence.sequenced,
+                        sequence.streams,
+                        sequence.unsequenced
+                    ),
+                );
+                if sequence.resets > 0
+                    || sequence.evicted_streams > 0
+                {
+                    render_stat_row(
+                        ui,
+                        "Resets / Evicted",
+                        format!(
+                            "{} / {}",
+                            sequence.resets,
+                            sequence.evicted_streams
                         ),
-                        egui::Sense::hover(),
                     );
-                },
-            );
-            ui.end_row();
-
-            ui.label("Duration");
-            ui.with_layout(
-                egui::Layout::left_to_right(
-                    egui::Align::Center,
-                ),
-                |ui| {
-                    if let Some(packet_duration) =
-                        stats.get_packet_duration()
-                    {
-                        ui.label(format!(
-                            "{:.3}s",
-                            packet_duration.as_secs_f64()
-                        ));
+                }
+            }
+
+            // 单向时延与抖动
+            if let Some(latency) = stats.get_latency() {
+                let ms = |ns: i64| ns as f64 / 1e6;
+                render_stat_row(
+                    ui,
+                    "Latency (ms)",
+                    if latency.samples == 0 {
+                        "-".to_string()
                     } else {
-                        ui.label("Unknown".to_string());
-                    }
-                    ui.allocate_response(
-                        egui::Vec2::new(
-                            ui.available_width(),
-                            0.0,
+                        format!(
+                            "min {:.3} / avg {:.3} / max {:.3} / p99 {:.3}",
+                            ms(latency.min_ns),
+                            ms(latency.avg_ns),
+                            ms(latency.max_ns),
+                            ms(latency.p99_ns)
+                        )
+                    },
+                );
+                render_stat_row(
+                    ui,
+                    "Jitter (ms)",
+                    format!("{:.3}", latency.jitter_ns / 1e6),
+                );
+                if latency.negative > 0 {
+                    render_stat_row(
+                        ui,
+                        "Negative Delays",
+                        format!(
+                            "{} (check clock offset)",
+                            latency.negative
                         ),
-                        egui::Sense::hover(),
                     );
-                },
-            );
-            ui.end_row();
-
-            ui.label("Error Count");
-            ui.with_layout(
-                egui::Layout::left_to_right(
-                    egui::Align::Center,
-                ),
-                |ui| {
-                    ui.label(format!(
-                        "{}",
-                        stats.get_errors()
-                    ));
-                    ui.allocate_response(
-                        egui::Vec2::new(
-                            ui.available_width(),
-                            0.0,
+                }
+            }
+
+            // 反射器回送计数
+            if let Some(reflected) = stats.get_reflected() {
+                render_stat_row(
+                    ui,
+                    "Reflected",
+                    reflected.to_string(),
+                );
+            }
+
+            // 往返时延与分方向丢包
+            if let Some(rtt) = stats.get_rtt() {
+                let ms = |ns: u64| ns as f64 / 1e6;
+                render_stat_row(
+                    ui,
+                    "RTT (ms)",
+                    if rtt.echoes == 0 {
+                        "-".to_string()
+                    } else {
+                        format!(
+                            "min {:.3} / avg {:.3} / max {:.3} / p99 {:.3}",
+                            ms(rtt.min_ns),
+                            ms(rtt.avg_ns),
+                            ms(rtt.max_ns),
+                            ms(rtt.p99_ns)
+                        )
+                    },
+                );
+                render_stat_row(
+                    ui,
+                    "Echoes",
+                    if rtt.ignored == 0 {
+                        format!("{} / {}", rtt.echoes, rtt.sent)
+                    } else {
+                        format!(
+                            "{} / {} ({} ignored)",
+                            rtt.echoes, rtt.sent, rtt.ignored
+                        )
+                    },
+                );
+                render_stat_row(
+                    ui,
+                    "Loss (fwd / ret)",
+                    format!(
+                        "{} / {} ({} unanswered)",
+                        rtt.forward_lost,
+                        rtt.return_lost,
+                        rtt.unanswered
+                    ),
+                );
+            }
+
+            // 网络损伤统计（仅发送器使用）
+            if let Some(impairment) = stats.get_impairment()
+            {
+                render_stat_row(
+                    ui,
+                    "Impaired Loss",
+                    format!(
+                        "{} dropped / {} burst",
+                        impairment.dropped,
+                        impairment.burst_dropped
+                    ),
+                );
+                render_stat_row(
+                    ui,
+                    "Impairments",
+                    format!(
+                        "{} dup / {} reorder / {} delay / {} corrupt",
+                        impairment.duplicated,
+                        impairment.reordered,
+                        impairment.delayed,
+                        impairment.corrupted
+                    ),
+                );
+            }
+
+            if let Some(fuzzed) = stats.get_fuzzed() {
+                render_stat_row(
+                    ui,
+                    "Fuzzed",
+                    fuzzed.to_string(),
+                );
+            }
+
+            // 接收队列统计（仅接收器使用）
+            if stats.get_queue_capacity() > 0 {
+                render_stat_row(
+                    ui,
+                    "Queue Depth",
+                    format!(
+                        "{} / {} (peak {})",
+                        stats.get_queue_depth(),
+                        stats.get_queue_capacity(),
+                        stats.get_queue_peak_depth()
+                    ),
+                );
+
+                render_stat_row(
+                    ui,
+                    "Queue Drops",
+                    stats.get_queue_drops().to_string(),
+                );
+            }
+
+            // 多套接字接收时的分通道统计
+            let channels = stats.get_channels();
+            if channels.len() > 1 {
+                let total: usize = channels
+                    .iter()
+                    .map(|channel| channel.packets)
+                    .sum();
+                render_stat_row(
+                    ui,
+                    "Packets Received",
+                    format!(
+                        "{} ({} sockets)",
+                        total,
+                        channels.len()
+                    ),
+                );
+
+                for channel in channels {
+                    render_stat_row(
+                        ui,
+                        &channel.label,
+                        format!(
+                            "{} pkts, {}, {} errors, {} drops, {} kernel drops, {} filtered",
+                            channel.packets,
+                            format_bytes(channel.bytes),
+                            channel.errors,
+                            channel.drops,
+                            channel.kernel_drops,
+                            channel.filtered
                         ),
-                        egui::Sense::hover(),
                     );
-                },
-            );
-            ui.end_row();
+                }
+            }
+
+            if let Some((name, seq)) = stats.get_dataset() {
+                render_stat_row(
+                    ui,
+                    "Dataset",
+                    format!("{} (#{})", name, seq),
+                );
+            }
+
+            if let Some(reason) = stats.get_stop_reason() {
+                render_stat_row(
+                    ui,
+                    "Stop Reason",
+                    reason.to_string(),
+                );
+            }
+
+            // 预读缓冲区统计（仅发送器使用）
+            if stats.get_prefetch_capacity() > 0 {
+                render_stat_row(
+                    ui,
+                    "Prefetch Buffer",
+                    format!(
+                        "{} / {}",
+                        stats.get_prefetch_depth(),
+                        stats.get_prefetch_capacity()
+                    ),
+                );
+
+                render_stat_row(
+                    ui,
+                    "Underruns",
+                    format!(
+                        "{} ({:.3}s)",
+                        stats.get_underruns(),
+                        stats
+                            .get_underrun_time()
+                            .as_secs_f64()
+                    ),
+                );
+            }
         });
 }
+
+/// 渲染一行统计信息，数值列占用剩余空间
+fn render_stat_row(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: String,
+) {
+    ui.label(label);
+    ui.with_layout(
+        egui::Layout::left_to_right(egui::Align::Center),
+        |ui| {
+            ui.label(value);
+            ui.allocate_response(
+                egui::Vec2::new(ui.available_width(), 0.0),
+                egui::Sense::hover(),
+            );
+        },
+    );
+    ui.end_row();
+}
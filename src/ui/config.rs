@@ -2,21 +2,19 @@
 //!
 //! 定义GUI应用程序的配置结构体和枚举类型。
 
+use crate::app::config::options::{
+    ReceiverOptions, SenderOptions,
+};
 use crate::app::config::types::{DataFormat, NetworkType};
 
 /// 当前选中的标签页
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum SelectedTab {
+    #[default]
     Sender,
     Receiver,
 }
 
-impl Default for SelectedTab {
-    fn default() -> Self {
-        Self::Sender
-    }
-}
-
 /// 发送器配置
 #[derive(Debug, Clone)]
 pub struct SenderConfig {
@@ -24,10 +22,12 @@ pub struct SenderConfig {
     pub pcap_path: String, // PCAP数据集路径（文件夹）
     pub csv_file: String,  // CSV文件路径（文件）
     pub csv_packet_interval: u64, // CSV发送周期（毫秒）
+    pub capture_file: String, // 抓包文件路径（pcap / pcapng）
     pub address: String,
     pub port: u16,
     pub network_type: NetworkType,
     pub interface: Option<String>,
+    pub options: SenderOptions,
 }
 
 impl Default for SenderConfig {
@@ -37,10 +37,33 @@ impl Default for SenderConfig {
             pcap_path: String::new(),
             csv_file: String::new(),
             csv_packet_interval: 1000, // 默认1秒（1000毫秒）
+            capture_file: String::new(),
             address: "127.0.0.1".to_string(),
             port: 8080,
             network_type: NetworkType::Unicast,
             interface: None,
+            options: Default::default(),
+        }
+    }
+}
+
+impl SenderConfig {
+    /// 当前数据格式对应的数据源路径
+    pub fn source_path(&self) -> std::path::PathBuf {
+        match self.data_format {
+            DataFormat::Pcap => {
+                std::path::PathBuf::from(&self.pcap_path)
+            }
+            DataFormat::Csv => {
+                std::path::PathBuf::from(&self.csv_file)
+            }
+            // 合成流量没有数据集，以名称作为报告与指标的会话名
+            DataFormat::Generator => {
+                std::path::PathBuf::from("generator")
+            }
+            DataFormat::Capture => {
+                std::path::PathBuf::from(&self.capture_file)
+            }
         }
     }
 }
@@ -54,6 +77,7 @@ pub struct ReceiverConfig {
     pub port: u16,
     pub network_type: NetworkType,
     pub interface: Option<String>,
+    pub options: ReceiverOptions,
 }
 
 impl Default for ReceiverConfig {
@@ -65,6 +89,7 @@ impl Default for ReceiverConfig {
             port: 8080, // 修改为8080，与发送器端口匹配
             network_type: NetworkType::Unicast,
             interface: None,
+            options: Default::default(),
         }
     }
 }
@@ -0,0 +1,448 @@
+//! 统计曲线组件
+//!
+//! 以滚动曲线显示最近若干分钟的速率、包率、错误数与丢包率，
+//! 支持暂停、悬停读数以及导出为 PNG 截图或 CSV 数据。
+
+use egui;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::app::error::types::{AppError, Result};
+use crate::core::stats::collector::TransferStats;
+use crate::core::stats::history::{
+    StatsHistory, StatsSample, SAMPLE_INTERVAL,
+};
+
+/// 可选择的最长显示时长（分钟），同时作为历史保留时长
+const MAX_WINDOW_MINUTES: u64 = 60;
+
+/// 默认显示时长（分钟）
+const DEFAULT_WINDOW_MINUTES: u64 = 5;
+
+/// 单条曲线的最小高度
+const STRIP_HEIGHT: f32 = 24.0;
+
+/// 曲线之间的间距
+const STRIP_GAP: f32 = 4.0;
+
+/// 曲线指标
+#[derive(Debug, Clone, Copy)]
+enum Metric {
+    Mbps,
+    Pps,
+    Errors,
+    Loss,
+}
+
+impl Metric {
+    const ALL: [Metric; 4] = [
+        Metric::Mbps,
+        Metric::Pps,
+        Metric::Errors,
+        Metric::Loss,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Metric::Mbps => "Mbps",
+            Metric::Pps => "pps",
+            Metric::Errors => "Errors/s",
+            Metric::Loss => "Loss %",
+        }
+    }
+
+    fn color(self) -> egui::Color32 {
+        match self {
+            Metric::Mbps => {
+                egui::Color32::from_rgb(66, 150, 250)
+            }
+            Metric::Pps => {
+                egui::Color32::from_rgb(80, 200, 120)
+            }
+            Metric::Errors => {
+                egui::Color32::from_rgb(240, 90, 80)
+            }
+            Metric::Loss => {
+                egui::Color32::from_rgb(240, 180, 60)
+            }
+        }
+    }
+
+    fn value(self, sample: &StatsSample) -> f64 {
+        match self {
+            Metric::Mbps => sample.mbps,
+            Metric::Pps => sample.pps,
+            Metric::Errors => sample.errors as f64,
+            Metric::Loss => sample.loss_percent,
+        }
+    }
+}
+
+/// 单个会话的统计曲线
+pub struct StatsChart {
+    history: StatsHistory,
+    /// 是否以曲线代替数字显示统计信息
+    visible: bool,
+    /// 暂停时冻结的显示终点（秒）
+    paused_at: Option<f64>,
+    window_minutes: u64,
+    /// 上一帧曲线区域（用于截图裁剪）
+    plot_rect: Option<egui::Rect>,
+    /// 等待截图结果的 PNG 导出路径
+    pending_png: Option<PathBuf>,
+    /// 最近一次导出的结果提示
+    message: Option<String>,
+}
+
+impl Default for StatsChart {
+    fn default() -> Self {
+        Self {
+            history: StatsHistory::new(
+                SAMPLE_INTERVAL,
+                Some(Duration::from_secs(
+                    MAX_WINDOW_MINUTES * 60,
+                )),
+            ),
+            visible: false,
+            paused_at: None,
+            window_minutes: DEFAULT_WINDOW_MINUTES,
+            plot_rect: None,
+            pending_png: None,
+            message: None,
+        }
+    }
+}
+
+impl StatsChart {
+    /// 清空历史（开始新的传输时调用）
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.paused_at = None;
+        self.message = None;
+    }
+
+    /// 按采样间隔记录当前统计
+    pub fn sample(&mut self, stats: &TransferStats) {
+        self.history.sample(stats, Instant::now());
+    }
+
+    /// 是否以曲线显示统计信息
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// 渲染数字与曲线视图的切换按钮
+    pub fn show_toggle(&mut self, ui: &mut egui::Ui) {
+        ui.selectable_value(
+            &mut self.visible,
+            false,
+            "Numbers",
+        );
+        ui.selectable_value(
+            &mut self.visible,
+            true,
+            "Charts",
+        );
+    }
+
+    /// 渲染工具栏与曲线
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.handle_screenshot(ui.ctx());
+        self.show_toolbar(ui);
+
+        let samples = self.history.samples();
+        let end = self.paused_at.unwrap_or_else(|| {
+            samples.back().map_or(0.0, |s| s.elapsed_secs)
+        });
+        let span = (self.window_minutes * 60) as f64;
+        let start = end - span;
+        let visible: Vec<&StatsSample> = samples
+            .iter()
+            .filter(|s| {
+                (start..=end).contains(&s.elapsed_secs)
+            })
+            .collect();
+
+        let width = ui.available_width();
+        let height = (ui.available_height()).max(
+            Metric::ALL.len() as f32
+                * (STRIP_HEIGHT + STRIP_GAP),
+        );
+        let (rect, response) = ui.allocate_exact_size(
+            egui::vec2(width, height),
+            egui::Sense::hover(),
+        );
+        self.plot_rect = Some(rect);
+
+        let painter = ui.painter_at(rect);
+        let strip_height = (height
+            - STRIP_GAP * (Metric::ALL.len() - 1) as f32)
+            / Metric::ALL.len() as f32;
+        let x_of = |t: f64| {
+            rect.left()
+                + ((t - start) / span) as f32 * rect.width()
+        };
+
+        for (i, metric) in
+            Metric::ALL.into_iter().enumerate()
+        {
+            let top = rect.top()
+                + i as f32 * (strip_height + STRIP_GAP);
+            let strip = egui::Rect::from_min_size(
+                egui::pos2(rect.left(), top),
+                egui::vec2(rect.width(), strip_height),
+            );
+            draw_strip(
+                ui, &painter, strip, metric, &visible,
+                &x_of,
+            );
+        }
+
+        // 悬停时显示最近采样点的读数
+        let Some(pointer) = response.hover_pos() else {
+            return;
+        };
+        let t = start
+            + ((pointer.x - rect.left()) / rect.width())
+                as f64
+                * span;
+        let Some(sample) = visible.iter().min_by(|a, b| {
+            (a.elapsed_secs - t)
+                .abs()
+                .total_cmp(&(b.elapsed_secs - t).abs())
+        }) else {
+            return;
+        };
+        let x = x_of(sample.elapsed_secs);
+        painter.vline(
+            x,
+            rect.y_range(),
+            egui::Stroke::new(
+                1.0,
+                ui.visuals().weak_text_color(),
+            ),
+        );
+        let sample = **sample;
+        response.on_hover_ui_at_pointer(|ui| {
+            ui.label(format!(
+                "t = {:.0}s",
+                sample.elapsed_secs
+            ));
+            for metric in Metric::ALL {
+                ui.colored_label(
+                    metric.color(),
+                    format!(
+                        "{}: {}",
+                        metric.label(),
+                        format_value(metric.value(&sample))
+                    ),
+                );
+            }
+        });
+    }
+
+    /// 渲染工具栏：显示时长、暂停与导出
+    fn show_toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Window");
+            ui.add(
+                egui::DragValue::new(
+                    &mut self.window_minutes,
+                )
+                .range(1..=MAX_WINDOW_MINUTES)
+                .suffix(" min"),
+            );
+
+            let pause_label = if self.paused_at.is_some() {
+                "Resume"
+            } else {
+                "Pause"
+            };
+            if ui.button(pause_label).clicked() {
+                self.paused_at = match self.paused_at {
+                    Some(_) => None,
+                    None => Some(
+                        self.history
+                            .samples()
+                            .back()
+                            .map_or(0.0, |s| {
+                                s.elapsed_secs
+                            }),
+                    ),
+                };
+            }
+
+            if ui.button("Export CSV").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CSV files", &["csv"])
+                    .set_file_name("stats.csv")
+                    .save_file()
+                {
+                    self.message = Some(
+                        match std::fs::write(
+                            &path,
+                            self.history.to_csv(),
+                        ) {
+                            Ok(()) => format!(
+                                "Saved {}",
+                                path.display()
+                            ),
+                            Err(e) => format!(
+                                "Failed to save CSV: {e}"
+                            ),
+                        },
+                    );
+                }
+            }
+
+            if ui.button("Export PNG").clicked() {
+                if let Some(path) = rfd::FileDialog::new()
+                    .add_filter("PNG images", &["png"])
+                    .set_file_name("stats.png")
+                    .save_file()
+                {
+                    // 截图结果在后续帧的事件中返回
+                    self.pending_png = Some(path);
+                    ui.ctx().send_viewport_cmd(
+                        egui::ViewportCommand::Screenshot,
+                    );
+                }
+            }
+
+            if let Some(message) = &self.message {
+                ui.weak(message);
+            }
+        });
+    }
+
+    /// 收到截图后裁剪曲线区域并保存为 PNG
+    fn handle_screenshot(&mut self, ctx: &egui::Context) {
+        if self.pending_png.is_none() {
+            return;
+        }
+        let image = ctx.input(|input| {
+            input.raw.events.iter().find_map(|event| {
+                match event {
+                    egui::Event::Screenshot {
+                        image,
+                        ..
+                    } => Some(Arc::clone(image)),
+                    _ => None,
+                }
+            })
+        });
+        let (Some(image), Some(rect), Some(path)) = (
+            image,
+            self.plot_rect,
+            self.pending_png.take(),
+        ) else {
+            return;
+        };
+
+        let region = image
+            .region(&rect, Some(ctx.pixels_per_point()));
+        self.message =
+            Some(match save_png(&path, &region) {
+                Ok(()) => {
+                    format!("Saved {}", path.display())
+                }
+                Err(e) => e.to_string(),
+            });
+    }
+}
+
+/// 绘制单条曲线及其标题
+fn draw_strip(
+    ui: &egui::Ui,
+    painter: &egui::Painter,
+    strip: egui::Rect,
+    metric: Metric,
+    samples: &[&StatsSample],
+    x_of: &impl Fn(f64) -> f32,
+) {
+    painter.rect_filled(
+        strip,
+        2.0,
+        ui.visuals().extreme_bg_color,
+    );
+
+    let max = samples
+        .iter()
+        .map(|s| metric.value(s))
+        .fold(0.0, f64::max);
+    let scale = if max > 0.0 { max * 1.1 } else { 1.0 };
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .map(|s| {
+            egui::pos2(
+                x_of(s.elapsed_secs),
+                strip.bottom()
+                    - (metric.value(s) / scale) as f32
+                        * strip.height(),
+            )
+        })
+        .collect();
+    if points.len() > 1 {
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.5, metric.color()),
+        ));
+    }
+
+    let current =
+        samples.last().map_or(0.0, |s| metric.value(s));
+    painter.text(
+        strip.left_top() + egui::vec2(4.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        format!(
+            "{}  {} (max {})",
+            metric.label(),
+            format_value(current),
+            format_value(max)
+        ),
+        egui::FontId::proportional(11.0),
+        ui.visuals().text_color(),
+    );
+}
+
+/// 格式化曲线读数
+fn format_value(value: f64) -> String {
+    if value >= 100.0 {
+        format!("{value:.0}")
+    } else {
+        format!("{value:.2}")
+    }
+}
+
+/// 将截图保存为 PNG 文件
+fn save_png(
+    path: &Path,
+    image: &egui::ColorImage,
+) -> Result<()> {
+    let file =
+        std::fs::File::create(path).map_err(|e| {
+            AppError::Gui(format!(
+                "Failed to create {}: {e}",
+                path.display()
+            ))
+        })?;
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        image.width() as u32,
+        image.height() as u32,
+    );
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| {
+            writer.write_image_data(image.as_raw())
+        })
+        .map_err(|e| {
+            AppError::Gui(format!(
+                "Failed to encode PNG: {e}"
+            ))
+        })
+}
@@ -1,3 +1,4 @@
 //! 自定义控件模块
 
+pub mod chart;
 pub mod status;
@@ -100,4 +100,15 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
+/// 十六进制编码（小写，无前缀）
+pub fn format_hex(data: &[u8]) -> String {
+    use std::fmt::Write as _;
+
+    let mut out = String::with_capacity(data.len() * 2);
+    for byte in data {
+        let _ = write!(out, "{byte:02x}");
+    }
+    out
+}
+
 // Removed unused format_rate function
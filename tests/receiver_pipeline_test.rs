@@ -0,0 +1,768 @@
+use pcap_transfer::app::config::options::{
+    FilterMode, FilterOptions, LatencyOptions,
+    ReceiverOptions, ReflectMode, ReflectorOptions,
+    ReportOptions, SequenceMode, SequenceOptions,
+};
+use pcap_transfer::app::config::types::NetworkType;
+use pcap_transfer::core::capture::export::{
+    export_dataset, ExportOptions,
+};
+use pcap_transfer::core::capture::packet::udp_datagram;
+use pcap_transfer::core::capture::reader::CaptureReader;
+use pcap_transfer::core::capture::writer::CaptureFormat;
+use pcap_transfer::core::dataset::sidecar::SidecarReader;
+use pcap_transfer::core::network::receiver::run_receiver_with_gui_stats;
+use pcap_transfer::core::network::sender::TransferState;
+use pcap_transfer::core::network::throughput::{
+    run_throughput_test, SearchMode, ThroughputConfig,
+};
+use pcap_transfer::core::probe::header::HeaderStamper;
+use pcap_transfer::core::probe::rtt::RttTracker;
+use pcap_transfer::core::stats::collector::TransferStats;
+use pcapfile_io::{PcapReader, ReaderConfig};
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// 等待接收器状态变化的最长时间
+const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 获取一个当前空闲的本地 UDP 端口
+fn free_port() -> u16 {
+    std::net::UdpSocket::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+/// 测试专用的临时输出目录
+fn test_dir(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "pcap_transfer_{name}_{}",
+        std::process::id()
+    ))
+}
+
+/// 在本地回环地址上运行的接收器
+struct TestReceiver {
+    output: PathBuf,
+    port: u16,
+    stats: Arc<Mutex<TransferStats>>,
+    state: Arc<Mutex<TransferState>>,
+    task: JoinHandle<
+        pcap_transfer::app::error::types::Result<()>,
+    >,
+}
+
+impl TestReceiver {
+    /// 在空闲端口上启动接收器，数据集名为 `received`
+    async fn start(
+        name: &str,
+        options: ReceiverOptions,
+    ) -> Self {
+        Self::start_with(
+            name,
+            "received",
+            free_port(),
+            options,
+        )
+        .await
+    }
+
+    /// 启动接收器并等待其绑定全部套接字
+    async fn start_with(
+        name: &str,
+        dataset: &str,
+        port: u16,
+        options: ReceiverOptions,
+    ) -> Self {
+        let output = test_dir(name);
+        let stats =
+            Arc::new(Mutex::new(TransferStats::new()));
+        let state =
+            Arc::new(Mutex::new(TransferState::Running));
+        let task =
+            tokio::spawn(run_receiver_with_gui_stats(
+                output.clone(),
+                dataset.to_string(),
+                "127.0.0.1".to_string(),
+                port,
+                NetworkType::Unicast,
+                None,
+                options,
+                Arc::clone(&stats),
+                Arc::clone(&state),
+            ));
+        let receiver = Self {
+            output,
+            port,
+            stats,
+            state,
+            task,
+        };
+
+        // 接收器在全部套接字绑定后才记录数据集名
+        receiver
+            .wait_until("receiver to bind", |stats| {
+                stats.get_dataset().is_some()
+            })
+            .await;
+        receiver
+    }
+
+    /// 主端点地址
+    fn addr(&self) -> SocketAddr {
+        SocketAddr::from(([127, 0, 0, 1], self.port))
+    }
+
+    /// 轮询统计信息直到条件成立
+    async fn wait_until(
+        &self,
+        what: &str,
+        condition: impl Fn(&TransferStats) -> bool,
+    ) {
+        let waited = tokio::time::timeout(WAIT_TIMEOUT, async {
+            while !condition(&self.stats.lock().unwrap()) {
+                assert!(
+                    !self.task.is_finished(),
+                    "receiver exited while waiting for {what}"
+                );
+                tokio::time::sleep(Duration::from_millis(5))
+                    .await;
+            }
+        })
+        .await;
+        assert!(
+            waited.is_ok(),
+            "timed out waiting for {what}"
+        );
+    }
+
+    /// 等待接收器处理完指定数量的数据包
+    async fn wait_for_packets(&self, count: usize) {
+        self.wait_until("packets", |stats| {
+            stats.get_packets_processed() >= count
+        })
+        .await;
+    }
+
+    /// 通知接收器停止并等待其结束
+    async fn stop(&mut self) {
+        *self.state.lock().unwrap() = TransferState::Idle;
+        self.finished().await;
+    }
+
+    /// 等待接收器结束
+    async fn finished(&mut self) {
+        tokio::time::timeout(WAIT_TIMEOUT, &mut self.task)
+            .await
+            .expect("receiver did not stop")
+            .unwrap()
+            .unwrap();
+    }
+}
+
+impl Drop for TestReceiver {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.output);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_writes_dataset() {
+    let mut receiver = TestReceiver::start(
+        "receiver",
+        ReceiverOptions {
+            batch_size: 8,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for i in 0u8..20 {
+        socket
+            .send_to(&[i; 32], receiver.addr())
+            .await
+            .unwrap();
+    }
+    receiver.wait_for_packets(20).await;
+    receiver.stop().await;
+    {
+        let stats = receiver.stats.lock().unwrap();
+        assert_eq!(stats.get_packets_processed(), 20);
+        let sizes = stats.get_packet_sizes();
+        assert_eq!(
+            (sizes.count, sizes.min, sizes.max),
+            (20, 32, 32)
+        );
+        assert!(stats.get_peak_rate().pps > 0.0);
+    }
+
+    let mut reader = PcapReader::new_with_config(
+        &receiver.output,
+        "received",
+        ReaderConfig::default(),
+    )
+    .unwrap();
+    let mut count = 0;
+    while let Some(packet) = reader.read_packet().unwrap() {
+        assert_eq!(
+            packet.packet.data,
+            vec![count as u8; 32]
+        );
+        count += 1;
+    }
+    assert_eq!(count, 20);
+
+    // 元数据文件逐包记录来源地址与接收端口
+    let mut sidecar = SidecarReader::open(
+        &receiver.output.join("received"),
+    )
+    .unwrap()
+    .unwrap();
+    let mut records = 0;
+    while let Some(record) = sidecar.next_record().unwrap()
+    {
+        assert_eq!(
+            record.meta.source,
+            Some(socket.local_addr().unwrap())
+        );
+        assert_eq!(record.meta.local_port, receiver.port);
+        records += 1;
+    }
+    assert_eq!(records, 20);
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reuse_port_receivers_write_in_order() {
+    let mut receiver = TestReceiver::start(
+        "reuse_port",
+        ReceiverOptions {
+            socket_count: 4,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // 不同的源端口会被内核分发到不同的套接字
+    for i in 0u8..40 {
+        let socket =
+            tokio::net::UdpSocket::bind("127.0.0.1:0")
+                .await
+                .unwrap();
+        socket
+            .send_to(&[i; 16], receiver.addr())
+            .await
+            .unwrap();
+    }
+    receiver.wait_for_packets(40).await;
+    receiver.stop().await;
+
+    {
+        let stats = receiver.stats.lock().unwrap();
+        assert_eq!(stats.get_packets_processed(), 40);
+        assert_eq!(stats.get_channels().len(), 4);
+        let total: usize = stats
+            .get_channels()
+            .iter()
+            .map(|channel| channel.packets)
+            .sum();
+        assert_eq!(total, 40);
+    }
+
+    let mut reader = PcapReader::new_with_config(
+        &receiver.output,
+        "received",
+        ReaderConfig::default(),
+    )
+    .unwrap();
+    let mut last_timestamp = 0;
+    let mut count = 0;
+    while let Some(packet) = reader.read_packet().unwrap() {
+        let timestamp = packet.packet.get_timestamp_ns();
+        assert!(timestamp >= last_timestamp);
+        last_timestamp = timestamp;
+        count += 1;
+    }
+    assert_eq!(count, 40);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_stops_at_packet_limit() {
+    let mut receiver = TestReceiver::start(
+        "limit",
+        ReceiverOptions {
+            max_packets: 5,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for i in 0u8..10 {
+        socket
+            .send_to(&[i; 8], receiver.addr())
+            .await
+            .unwrap();
+    }
+
+    // 接收器应自行结束，无需外部停止信号
+    receiver.finished().await;
+    assert!(matches!(
+        *receiver.state.lock().unwrap(),
+        TransferState::Completed
+    ));
+
+    {
+        let stats = receiver.stats.lock().unwrap();
+        assert_eq!(stats.get_packets_processed(), 5);
+        assert_eq!(
+            stats.get_stop_reason(),
+            Some("Packet limit reached (5)")
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_counts_filtered_packets() {
+    let mut receiver = TestReceiver::start(
+        "filter",
+        ReceiverOptions {
+            max_packets: 5,
+            filter: FilterOptions {
+                mode: FilterMode::All,
+                rules: vec![
+                    "src 127.0.0.0/8".to_string(),
+                    "offset 0 == 0x5054".to_string(),
+                ],
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for i in 0u8..10 {
+        // 偶数包带有匹配的头部，奇数包应被过滤
+        let header = if i % 2 == 0 { 0x50 } else { 0x00 };
+        socket
+            .send_to(&[header, 0x54, i, i], receiver.addr())
+            .await
+            .unwrap();
+    }
+
+    receiver.finished().await;
+
+    {
+        let stats = receiver.stats.lock().unwrap();
+        assert_eq!(stats.get_packets_processed(), 5);
+        assert!(stats.is_filter_active());
+        assert!(stats.get_filtered() >= 4);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_writes_dataset_per_endpoint() {
+    let ports = [free_port(), free_port()];
+    let mut receiver = TestReceiver::start_with(
+        "endpoints",
+        "received",
+        ports[0],
+        ReceiverOptions {
+            endpoints: vec![ports[1].to_string()],
+            dataset_per_endpoint: true,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for (index, port) in ports.iter().enumerate() {
+        for _ in 0..(index + 1) * 5 {
+            socket
+                .send_to(
+                    &[index as u8; 16],
+                    ("127.0.0.1", *port),
+                )
+                .await
+                .unwrap();
+        }
+    }
+    receiver.wait_for_packets(15).await;
+    receiver.stop().await;
+
+    // 每个端点一个统计通道
+    {
+        let stats = receiver.stats.lock().unwrap();
+        let channels = stats.get_channels();
+        assert_eq!(channels.len(), 2);
+        assert_eq!(
+            channels[0].label,
+            format!("127.0.0.1:{}", ports[0])
+        );
+        assert_eq!(channels[0].packets, 5);
+        assert_eq!(channels[1].packets, 10);
+    }
+
+    // 每个端点一个数据集，元数据记录接收端点
+    for (index, port) in ports.iter().enumerate() {
+        let name = format!("received_127_0_0_1_{port}");
+        let mut sidecar = SidecarReader::open(
+            &receiver.output.join(&name),
+        )
+        .unwrap()
+        .unwrap();
+        let mut records = 0;
+        while let Some(record) =
+            sidecar.next_record().unwrap()
+        {
+            assert_eq!(
+                record.meta.endpoint,
+                Some(
+                    format!("127.0.0.1:{port}")
+                        .parse()
+                        .unwrap()
+                )
+            );
+            records += 1;
+        }
+        assert_eq!(records, (index + 1) * 5);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_tracks_sequence_header() {
+    let mut receiver = TestReceiver::start(
+        "sequence",
+        ReceiverOptions {
+            sequence: SequenceOptions {
+                mode: SequenceMode::Header,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    // 序号 0..10 中丢弃 3，交换 5 与 6，重复发送 8
+    let mut stamper = HeaderStamper::new(1);
+    let packets: Vec<Vec<u8>> = (0..10)
+        .map(|_| stamper.stamp(b"payload"))
+        .collect();
+    let order = [0, 1, 2, 4, 6, 5, 7, 8, 8, 9];
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for index in order {
+        socket
+            .send_to(&packets[index], receiver.addr())
+            .await
+            .unwrap();
+    }
+    receiver.wait_for_packets(10).await;
+    receiver.stop().await;
+
+    let stats = receiver.stats.lock().unwrap();
+    let sequence = stats.get_sequence().unwrap();
+    assert_eq!(sequence.streams, 1);
+    assert_eq!(sequence.sequenced, 10);
+    assert_eq!(sequence.lost, 1);
+    assert_eq!(sequence.reordered, 1);
+    assert_eq!(sequence.duplicates, 1);
+    assert!(stats.summary().contains("1 lost"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_measures_latency() {
+    let mut receiver = TestReceiver::start(
+        "latency",
+        ReceiverOptions {
+            latency: LatencyOptions {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let mut stamper =
+        HeaderStamper::new(1).with_timestamps();
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for _ in 0..20 {
+        socket
+            .send_to(
+                &stamper.stamp(b"probe"),
+                receiver.addr(),
+            )
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+    }
+    receiver.wait_for_packets(20).await;
+    receiver.stop().await;
+
+    // 回环上的时延应为正且远小于 1 秒
+    let stats = receiver.stats.lock().unwrap();
+    let latency = stats.get_latency().unwrap();
+    assert_eq!(latency.samples, 20);
+    assert!(latency.min_ns >= 0);
+    assert!(latency.max_ns < 1_000_000_000);
+    assert!(latency.p99_ns >= latency.min_ns);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_reflects_packets() {
+    let mut receiver = TestReceiver::start(
+        "reflector",
+        ReceiverOptions {
+            reflector: ReflectorOptions {
+                mode: ReflectMode::Prefix,
+                prefix_bytes: 40,
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let mut stamper =
+        HeaderStamper::new(5).with_timestamps();
+    let mut tracker = RttTracker::new(5);
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    let mut buffer = [0u8; 2048];
+    for _ in 0..10 {
+        socket
+            .send_to(
+                &stamper.stamp(&[0xAB; 200]),
+                receiver.addr(),
+            )
+            .await
+            .unwrap();
+        let len = tokio::time::timeout(
+            Duration::from_secs(1),
+            socket.recv(&mut buffer),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        // 回送包截断为前缀长度
+        assert_eq!(len, 40);
+        tracker.observe(&buffer[..len], chrono::Utc::now());
+    }
+
+    receiver.stop().await;
+
+    let rtt = tracker.stats(stamper.stamped());
+    assert_eq!(rtt.echoes, 10);
+    assert_eq!(rtt.forward_lost, 0);
+    assert_eq!(rtt.return_lost, 0);
+    assert_eq!(rtt.unanswered, 0);
+    assert!(rtt.max_ns < 1_000_000_000);
+
+    let stats = receiver.stats.lock().unwrap();
+    assert_eq!(stats.get_reflected(), Some(10));
+    assert_eq!(stats.get_packets_processed(), 10);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_receiver_writes_report() {
+    let reports = test_dir("report").join("reports");
+    let mut receiver = TestReceiver::start_with(
+        "report",
+        "reported",
+        free_port(),
+        ReceiverOptions {
+            report: ReportOptions {
+                enabled: true,
+                interval_ms: 100,
+                directory: reports
+                    .to_string_lossy()
+                    .into_owned(),
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for i in 0u8..10 {
+        socket
+            .send_to(&[i; 64], receiver.addr())
+            .await
+            .unwrap();
+    }
+    receiver.wait_for_packets(10).await;
+    // 按 100ms 间隔采样数次后再停止
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    receiver.stop().await;
+
+    let mut names: Vec<String> =
+        std::fs::read_dir(&reports)
+            .unwrap()
+            .map(|entry| {
+                entry
+                    .unwrap()
+                    .file_name()
+                    .into_string()
+                    .unwrap()
+            })
+            .collect();
+    names.sort();
+    assert_eq!(names.len(), 3);
+    assert!(names[0].starts_with("reported_"));
+    assert!(names[0].ends_with("_receiver_report.json"));
+    assert!(names[1].ends_with("_receiver_series.csv"));
+    assert!(names[2].ends_with("_receiver_summary.csv"));
+
+    let report: serde_json::Value = serde_json::from_str(
+        &std::fs::read_to_string(reports.join(&names[0]))
+            .unwrap(),
+    )
+    .unwrap();
+    let summary = &report["summary"];
+    assert_eq!(summary["packets"], 10);
+    assert_eq!(summary["bytes"], 640);
+    assert_eq!(summary["stop_reason"], "Stopped by user");
+    assert_eq!(summary["config"]["port"], receiver.port);
+    let samples = report["samples"].as_array().unwrap();
+    assert!(samples.len() >= 4);
+    assert_eq!(samples.last().unwrap()["packets"], 10);
+
+    let summary_csv =
+        std::fs::read_to_string(reports.join(&names[2]))
+            .unwrap();
+    let row = summary_csv.lines().nth(1).unwrap();
+    assert!(row.starts_with("receiver,reported,"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_throughput_search_against_reflector() {
+    let mut receiver = TestReceiver::start(
+        "throughput",
+        ReceiverOptions {
+            reflector: ReflectorOptions {
+                mode: ReflectMode::Full,
+                prefix_bytes: 0,
+            },
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let results = run_throughput_test(
+        &ThroughputConfig {
+            reflector: Some(receiver.addr()),
+            packet_sizes: vec![100],
+            search: SearchMode::Step,
+            min_rate_mbps: 0.2,
+            max_rate_mbps: 0.4,
+            step_mbps: 0.2,
+            trial_duration: Duration::from_millis(250),
+            settle_time: Duration::from_millis(200),
+            ..Default::default()
+        },
+        |_, _| {},
+    )
+    .await
+    .unwrap();
+
+    receiver.stop().await;
+
+    let result = &results[0];
+    assert_eq!(result.trials.len(), 2);
+    assert_eq!(result.max_rate_mbps, Some(0.4));
+    let sent: u64 =
+        result.trials.iter().map(|trial| trial.sent).sum();
+    assert!(result
+        .trials
+        .iter()
+        .all(|trial| trial.received == trial.sent));
+    assert_eq!(
+        receiver.stats.lock().unwrap().get_reflected(),
+        Some(sent)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_export_dataset_as_pcapng() {
+    let mut receiver = TestReceiver::start(
+        "export",
+        ReceiverOptions::default(),
+    )
+    .await;
+
+    let socket = tokio::net::UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap();
+    for i in 0u8..5 {
+        socket
+            .send_to(&[i; 16], receiver.addr())
+            .await
+            .unwrap();
+    }
+    receiver.wait_for_packets(5).await;
+    receiver.stop().await;
+
+    let dataset = receiver.output.join("received");
+    let capture = receiver.output.join("received.pcapng");
+    let summary = export_dataset(
+        &dataset,
+        &capture,
+        &ExportOptions {
+            format: CaptureFormat::PcapNg,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert_eq!(summary.packets, 5);
+    assert_eq!(summary.with_metadata, 5);
+
+    // 导出文件的时间戳与数据集一致，地址取自接收元数据
+    let mut packets = PcapReader::new_with_config(
+        &receiver.output,
+        "received",
+        ReaderConfig::default(),
+    )
+    .unwrap();
+    let mut reader = CaptureReader::open(&capture).unwrap();
+    let mut count = 0;
+    while let Some(frame) = reader.next_frame().unwrap() {
+        let packet =
+            packets.read_packet().unwrap().unwrap();
+        assert_eq!(frame.timestamp, packet.capture_time());
+
+        let datagram =
+            udp_datagram(frame.link_type, &frame.data)
+                .unwrap();
+        assert_eq!(
+            datagram.source,
+            socket.local_addr().unwrap()
+        );
+        assert_eq!(
+            datagram.destination.port(),
+            receiver.port
+        );
+        assert_eq!(datagram.payload, [count as u8; 16]);
+        count += 1;
+    }
+    assert_eq!(count, 5);
+}